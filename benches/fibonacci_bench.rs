@@ -2,9 +2,15 @@
 //!
 //! Run with: `cargo bench --bench fibonacci_bench`
 
-use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use num_bigint::BigUint;
 use std::hint::black_box;
-use zeck::{fast_doubling_fibonacci_biguint, slow_fibonacci_biguint_iterative};
+use std::sync::Arc;
+use zeck::{
+    clear_all_caches, fast_doubling_fibonacci_biguint, fibonacci,
+    memoized_fast_doubling_fibonacci_biguint, memoized_slow_fibonacci_biguint_iterative,
+    slow_fibonacci_biguint_iterative,
+};
 
 /// Fibonacci indices to benchmark.
 const FIBONACCI_INDICES: [u64; 4] = [10_000, 20_000, 50_000, 100_000];
@@ -39,9 +45,69 @@ fn bench_fast_doubling_fibonacci_biguint(c: &mut Criterion) {
     group.finish();
 }
 
+/// A memoized Fibonacci function returning the shared cached value.
+type MemoizedFibonacci = fn(u64) -> Arc<BigUint>;
+
+/// Index workloads for comparing the memoized Fibonacci functions, each run against empty caches.
+///
+/// Sequential small indices favor the contiguous cache, and sparse large indices favor fast doubling.
+fn fibonacci_workloads() -> [(&'static str, Vec<u64>); 3] {
+    [
+        ("sequential_0_to_3000", (0..3_000).collect()),
+        (
+            "sequential_0_to_10000_step_100",
+            (0..10_000).step_by(100).collect(),
+        ),
+        (
+            "sparse_10000_to_100000",
+            vec![10_000, 20_000, 50_000, 100_000],
+        ),
+    ]
+}
+
+fn bench_adaptive_fibonacci(c: &mut Criterion) {
+    let mut group = c.benchmark_group("adaptive_fibonacci");
+    group.sample_size(10);
+
+    let functions: [(&str, MemoizedFibonacci); 3] = [
+        (
+            "memoized_slow_fibonacci_biguint_iterative",
+            memoized_slow_fibonacci_biguint_iterative,
+        ),
+        (
+            "memoized_fast_doubling_fibonacci_biguint",
+            memoized_fast_doubling_fibonacci_biguint,
+        ),
+        ("fibonacci", fibonacci),
+    ];
+
+    for (workload_name, indices) in fibonacci_workloads() {
+        for (function_name, function) in functions {
+            group.bench_with_input(
+                BenchmarkId::new(function_name, workload_name),
+                &indices,
+                |b, indices| {
+                    b.iter_batched(
+                        clear_all_caches,
+                        |()| {
+                            for &fi in indices {
+                                black_box(function(black_box(fi)));
+                            }
+                        },
+                        BatchSize::PerIteration,
+                    );
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_slow_fibonacci_biguint_iterative,
-    bench_fast_doubling_fibonacci_biguint
+    bench_fast_doubling_fibonacci_biguint,
+    bench_adaptive_fibonacci
 );
 criterion_main!(benches);
@@ -8,6 +8,14 @@
 //! - compress
 //! - decompress
 //! - round trip, which is the compress and decompress functions combined
+//! - compress, decompress, and round trip again for each data distribution in [`DISTRIBUTIONS_TO_BENCH`]
+//! - compress, decompress, and round trip through the .zeck file format, with best endianness selection and header
+//!   serialization, as `zeck-compress` and `zeck-decompress` do by default
+//! - bit packing, comparing the fast and scalar EZBA bit packing for a 1M-bit input
+//! - bit unpacking, comparing the lookup table and scalar EZBA bit unpacking for a 1M-bit output
+//! - Fibonacci summing, timing [`zeck::zl_to_biguint`] alone on the Zeckendorf lists of [`ZL_BYTE_SIZES_TO_BENCH`] inputs
+//! - parallel Fibonacci summing, comparing `zeck::zl_to_biguint_parallel` with the sequential sum on a list of about 50K
+//!   indices, when built with `--features parallel`
 //!
 //! Criterion notes:
 //! To save a new named baseline, run:
@@ -19,8 +27,9 @@
 //!
 //! Any time `cargo bench` is run without any arguments, it will, by default, save the result to a baseline called "new" and compare it to the previous run, called "base".
 
-use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
 use std::hint::black_box;
+use zeck::zeck_file_format::compress::BestCompressionResult;
 
 /// The byte sizes to benchmark.
 ///
@@ -29,17 +38,44 @@ const BYTE_SIZES_TO_BENCH: [usize; 7] = [4, 16, 64, 256, 1024, 4096, 16384];
 
 /// Generates test data of the given size
 ///
-/// The test data is a vector of bytes, where the bytes are the numbers from 0 to size - 1, modulo 256. This is to ensure that the data has a simple variety of values. TODO: Consider different data distributions in the future.
-///
-/// # Examples
-///
-/// ```
-/// # use zeckendorf_bench::generate_test_data;
-/// let data = generate_test_data(10);
-/// assert_eq!(data, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
-/// ```
+/// The test data is ramp data, where the bytes are the numbers from 0 to size - 1, modulo 256. This is to ensure that the data has a simple variety of values. See [`DISTRIBUTIONS_TO_BENCH`] for other data distributions.
 fn generate_test_data(size: usize) -> Vec<u8> {
-    (0..size).map(|i| (i % 256) as u8).collect()
+    zeck::test_data::generate_ramp_data(size)
+}
+
+/// Generates benchmark input data of the given size.
+type DataGenerator = fn(usize) -> Vec<u8>;
+
+/// The named data distributions benchmarked by the `*_by_distribution` groups.
+///
+/// All-zero data compresses to almost nothing, all-ones data is the worst case, and the random and sparse data use a
+/// fixed seed so runs are comparable across baselines.
+const DISTRIBUTIONS_TO_BENCH: [(&str, DataGenerator); 5] = [
+    ("ramp", zeck::test_data::generate_ramp_data),
+    ("zeros", zeck::test_data::generate_zeros_data),
+    ("ones", zeck::test_data::generate_ones_data),
+    ("random", |size| {
+        zeck::test_data::generate_random_data(size, zeck::test_data::DEFAULT_TEST_DATA_SEED)
+    }),
+    ("sparse", |size| {
+        zeck::test_data::generate_sparse_data(size, zeck::test_data::DEFAULT_TEST_DATA_SEED)
+    }),
+];
+
+/// The number of EZBA bits used by the bit packing benchmarks.
+const EZBA_BITS_TO_BENCH: usize = 1_000_000;
+
+/// The input byte sizes whose Zeckendorf lists are summed by the Fibonacci summing benchmark.
+const ZL_BYTE_SIZES_TO_BENCH: [usize; 3] = [1024, 4096, 16384];
+
+/// The size of the all-ones input whose Zeckendorf list, of about 50K indices, is summed by the parallel Fibonacci
+/// summing benchmark.
+#[cfg(feature = "parallel")]
+const PARALLEL_ZL_BYTE_SIZE: usize = 16384;
+
+/// Generates an EZBA bit vector of the given length with a mix of use and skip bits.
+fn generate_test_ezba(len: usize) -> Vec<u8> {
+    (0..len).map(|i| ((i * 7 + i / 3) % 2) as u8).collect()
 }
 
 fn bench_compress(c: &mut Criterion) {
@@ -100,5 +136,265 @@ fn bench_round_trip(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_compress, bench_decompress, bench_round_trip);
+/// Compresses data the way `zeck-compress` does by default, keeping the better endianness with
+/// [`zeck::compress_zeck_best`], and serializes the .zeck file.
+///
+/// Data that neither endianness makes smaller is stored little endian, so every input produces a file to decompress.
+fn compress_to_zeck_file_bytes(data: &[u8]) -> Vec<u8> {
+    let zeck_file = match zeck::compress_zeck_best(data).expect("Failed to compress") {
+        BestCompressionResult::BigEndianBest { zeck_file, .. }
+        | BestCompressionResult::LittleEndianBest { zeck_file, .. } => zeck_file,
+        BestCompressionResult::Neither { .. } => {
+            zeck::compress_zeck_le(data).expect("Failed to compress")
+        }
+    };
+    zeck_file.to_bytes()
+}
+
+/// Deserializes a .zeck file and decompresses it, the way `zeck-decompress` does.
+fn decompress_zeck_file_bytes(zeck_file_bytes: &[u8]) -> Vec<u8> {
+    let zeck_file =
+        zeck::deserialize_zeck_file(zeck_file_bytes).expect("Failed to deserialize .zeck file");
+    zeck::decompress_zeck_file(&zeck_file).expect("Failed to decompress")
+}
+
+/// Times the full .zeck format path, including trying both endiannesses and the header, so the overhead over the
+/// padless `compress` group is the real cost to an end user.
+fn bench_zeck_file_compress(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zeck_file_compress");
+
+    for size in BYTE_SIZES_TO_BENCH {
+        let data = generate_test_data(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| {
+                let zeck_file_bytes = compress_to_zeck_file_bytes(black_box(data));
+                black_box(zeck_file_bytes);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_zeck_file_decompress(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zeck_file_decompress");
+
+    for size in BYTE_SIZES_TO_BENCH {
+        let zeck_file_bytes = compress_to_zeck_file_bytes(&generate_test_data(size));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &zeck_file_bytes,
+            |b, zeck_file_bytes| {
+                b.iter(|| {
+                    let decompressed = decompress_zeck_file_bytes(black_box(zeck_file_bytes));
+                    black_box(decompressed);
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_zeck_file_round_trip(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zeck_file_round_trip");
+
+    for size in BYTE_SIZES_TO_BENCH {
+        let data = generate_test_data(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| {
+                let zeck_file_bytes = compress_to_zeck_file_bytes(black_box(data));
+                let decompressed = decompress_zeck_file_bytes(&zeck_file_bytes);
+                black_box(decompressed);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_compress_by_distribution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compress_by_distribution");
+
+    for (distribution, generate) in DISTRIBUTIONS_TO_BENCH {
+        for size in BYTE_SIZES_TO_BENCH {
+            let data = generate(size);
+            group.bench_with_input(BenchmarkId::new(distribution, size), &data, |b, data| {
+                b.iter(|| {
+                    let compressed =
+                        zeck::padless_zeckendorf_compress_be_dangerous(black_box(data));
+                    black_box(compressed);
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_decompress_by_distribution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decompress_by_distribution");
+
+    for (distribution, generate) in DISTRIBUTIONS_TO_BENCH {
+        for size in BYTE_SIZES_TO_BENCH {
+            let compressed = zeck::padless_zeckendorf_compress_be_dangerous(&generate(size));
+            group.bench_with_input(
+                BenchmarkId::new(distribution, size),
+                &compressed,
+                |b, compressed| {
+                    b.iter(|| {
+                        let decompressed =
+                            zeck::padless_zeckendorf_decompress_be_dangerous(black_box(compressed));
+                        black_box(decompressed);
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_round_trip_by_distribution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("round_trip_by_distribution");
+
+    for (distribution, generate) in DISTRIBUTIONS_TO_BENCH {
+        for size in BYTE_SIZES_TO_BENCH {
+            let data = generate(size);
+            group.bench_with_input(BenchmarkId::new(distribution, size), &data, |b, data| {
+                b.iter(|| {
+                    let compressed_data =
+                        zeck::padless_zeckendorf_compress_be_dangerous(black_box(data));
+                    let decompressed =
+                        zeck::padless_zeckendorf_decompress_be_dangerous(&compressed_data);
+                    black_box(decompressed);
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_pack_ezba_bits_to_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pack_ezba_bits_to_bytes");
+    let ezba = generate_test_ezba(EZBA_BITS_TO_BENCH);
+
+    group.bench_with_input(
+        BenchmarkId::new("fast", EZBA_BITS_TO_BENCH),
+        &ezba,
+        |b, ezba| {
+            b.iter(|| {
+                let packed = zeck::pack_ezba_bits_to_bytes(black_box(ezba));
+                black_box(packed);
+            });
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("scalar", EZBA_BITS_TO_BENCH),
+        &ezba,
+        |b, ezba| {
+            b.iter(|| {
+                let packed = zeck::pack_ezba_bits_to_bytes_scalar(black_box(ezba));
+                black_box(packed);
+            });
+        },
+    );
+
+    group.finish();
+}
+
+fn bench_unpack_bytes_to_ezba_bits(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unpack_bytes_to_ezba_bits");
+    let bytes = zeck::pack_ezba_bits_to_bytes(&generate_test_ezba(EZBA_BITS_TO_BENCH));
+
+    group.bench_with_input(
+        BenchmarkId::new("lookup_table", EZBA_BITS_TO_BENCH),
+        &bytes,
+        |b, bytes| {
+            b.iter(|| {
+                let unpacked = zeck::unpack_bytes_to_ezba_bits(black_box(bytes));
+                black_box(unpacked);
+            });
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("scalar", EZBA_BITS_TO_BENCH),
+        &bytes,
+        |b, bytes| {
+            b.iter(|| {
+                let unpacked = zeck::unpack_bytes_to_ezba_bits_scalar(black_box(bytes));
+                black_box(unpacked);
+            });
+        },
+    );
+
+    group.finish();
+}
+
+/// Times summing the Fibonacci values of a Zeckendorf list, the dominant cost of decompression, without the EZBA bit
+/// unpacking or byte conversion around it. Results are reported per list length, with the throughput in list entries.
+fn bench_zl_to_biguint(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zl_to_biguint");
+
+    for size in ZL_BYTE_SIZES_TO_BENCH {
+        let zl = zeck::zeckendorf_list_descending_for_bytes_be(&generate_test_data(size));
+        group.throughput(Throughput::Elements(zl.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(zl.len()), &zl, |b, zl| {
+            b.iter(|| {
+                let sum = zeck::zl_to_biguint(black_box(zl));
+                black_box(sum);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Compares the sequential and parallel Fibonacci sums on one long Zeckendorf list, from all-ones data, the worst
+/// case for list length.
+#[cfg(feature = "parallel")]
+fn bench_zl_to_biguint_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zl_to_biguint_parallel");
+    let zl = zeck::zeckendorf_list_descending_for_bytes_be(&zeck::test_data::generate_ones_data(
+        PARALLEL_ZL_BYTE_SIZE,
+    ));
+    group.throughput(Throughput::Elements(zl.len() as u64));
+
+    group.bench_with_input(BenchmarkId::new("sequential", zl.len()), &zl, |b, zl| {
+        b.iter(|| {
+            let sum = zeck::zl_to_biguint(black_box(zl));
+            black_box(sum);
+        });
+    });
+    group.bench_with_input(BenchmarkId::new("parallel", zl.len()), &zl, |b, zl| {
+        b.iter(|| {
+            let sum = zeck::zl_to_biguint_parallel(black_box(zl));
+            black_box(sum);
+        });
+    });
+
+    group.finish();
+}
+
+/// Stands in for the parallel Fibonacci summing benchmark when the `parallel` feature is off.
+#[cfg(not(feature = "parallel"))]
+fn bench_zl_to_biguint_parallel(_c: &mut Criterion) {}
+
+criterion_group!(
+    benches,
+    bench_compress,
+    bench_decompress,
+    bench_round_trip,
+    bench_compress_by_distribution,
+    bench_decompress_by_distribution,
+    bench_round_trip_by_distribution,
+    bench_zeck_file_compress,
+    bench_zeck_file_decompress,
+    bench_zeck_file_round_trip,
+    bench_pack_ezba_bits_to_bytes,
+    bench_unpack_bytes_to_ezba_bits,
+    bench_zl_to_biguint,
+    bench_zl_to_biguint_parallel
+);
 criterion_main!(benches);
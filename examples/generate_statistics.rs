@@ -2,6 +2,16 @@
 //!
 //! The statistics are saved in the statistics_history directory in a file named statistics_up_to_<limit>_inputs.csv and sampled_statistics_up_to_<limit>_bits.csv
 //!
+//! The additional savings and time cost of adaptive compression over plain best compression are saved in adaptive_vs_best_statistics_up_to_<limit>_bytes.csv
+//!
+//! How often rotating the input to a nonzero byte offset beats offset 0, and by how much, is saved in rotation_statistics_up_to_<limit>_bytes.csv
+//!
+//! The sizes of delta pre-transformed and plain compression on a text sample and binary samples are saved in delta_vs_none_statistics_up_to_<limit>_bytes.csv
+//!
+//! The sizes of two-stage (entropy coded) and single-stage compression across several data distributions are saved in two_stage_vs_single_stage_statistics_up_to_<limit>_bytes.csv
+//!
+//! The sizes of the experimental Lucas codec and Fibonacci (Zeckendorf) compression across several data distributions are saved in lucas_vs_fibonacci_statistics_up_to_<limit>_bytes.csv
+//!
 //! The purpose of this example is to determine the average compression ratio, median compression ratio, best compression ratio, and chance of compression being favorable for a given limit. As we compress to higher limits, the statistics should become more stable.
 //!
 //! The Zeckendorf compression oscillates between being favorable and unfavorable, as the data changes, and the statistics are used to determine the average and median compression ratios, and the chance of compression being favorable. See this crate's `plot` example for more details about the oscillation and to visualize the compression ratios.
@@ -9,14 +19,29 @@
 //! The meaning of "compression up to input" in the csv header is such that the statistics are gathered for all inputs up to and including the given limit. For example, "compression up to 100" means that the corresponding statistics in that row in the csv are gathered for all inputs from 1 to 100.
 //!
 //! Run with: `cargo run --release --example generate-statistics`
+//!
+//! The bit limit and sampled statistics compress every input independently, so they are gathered in parallel. By default
+//! every available core is used; pass `--threads N` to use N threads instead, e.g.
+//! `cargo run --release --example generate-statistics -- --threads 4`. The statistics are the same for any thread count.
 
 use num_format::{Locale, ToFormattedString};
 use plotters::prelude::*;
 
 use num_bigint::BigUint;
 use rand::{Rng, SeedableRng, rngs::StdRng};
-use std::{cmp::Ordering, fs, path::Path, time::Instant};
-use zeck::padless_zeckendorf_compress_be_dangerous;
+use rayon::prelude::*;
+use std::{fs, path::Path, time::Instant};
+use zeck::{
+    Endianness, compress_zeck_adaptive, compress_zeck_delta, compress_zeck_entropy_coded,
+    exact_compression_preview, lucas_compress_be, padless_zeckendorf_compress_be_dangerous,
+    padless_zeckendorf_compress_le_dangerous,
+    stats::{compression_amount_percent, compression_amount_percent_bytes},
+    test_data::{
+        DEFAULT_TEST_DATA_SEED, generate_ones_data, generate_ramp_data, generate_random_data,
+        generate_sparse_data, generate_zeros_data,
+    },
+    zeck_file_format::compress::compress_zeck_rotations,
+};
 
 const AXIS_FONT_SIZE: u32 = 100;
 const AXIS_TICK_FONT_SIZE: u32 = 64;
@@ -60,10 +85,33 @@ const WIDE_SCALE_BIT_SIZE_LIMITS: [u64; 3] = [1_000, 10_000, 100_000];
 const WIDE_SCALE_STARTING_SAMPLES_PER_BIT_SIZE: u64 = 100_000;
 const WIDE_SCALE_SAMPLE_REDUCTION_FACTOR: f64 = 0.15;
 
+// Adaptive vs best statistics configuration
+// Adaptive compression tries eight representations per input, so the byte sizes are kept small.
+const ADAPTIVE_BYTE_SIZES: [usize; 5] = [4, 16, 64, 256, 1_024];
+const ADAPTIVE_SAMPLES_PER_BYTE_SIZE: u64 = 1_000;
+
+// Rotation statistics configuration
+const ROTATION_BYTE_SIZES: [usize; 5] = [4, 16, 64, 256, 1_024];
+const ROTATION_SAMPLES_PER_BYTE_SIZE: u64 = 1_000;
+
+// Delta vs none statistics configuration
+const DELTA_BYTE_SIZES: [usize; 4] = [16, 64, 256, 1_024];
+/// English text sample for the delta vs none statistics, cut to each size in [`DELTA_BYTE_SIZES`].
+const DELTA_TEXT_SAMPLE: &[u8] = include_bytes!("../LICENSE.txt");
+
+// Two-stage vs single-stage statistics configuration
+const TWO_STAGE_BYTE_SIZES: [usize; 4] = [16, 64, 256, 1_024];
+
+// Lucas vs Fibonacci statistics configuration
+const LUCAS_BYTE_SIZES: [usize; 4] = [16, 64, 256, 1_024];
+
+/// Generates test data of the given byte size.
+type DataGenerator = fn(usize) -> Vec<u8>;
+
 // Seed for the random number generator to ensure reproducible results
 const RNG_SEED: u64 = 42;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct CompressionStats {
     limit: u64,
     favorable_pct: f64,
@@ -78,11 +126,24 @@ struct CompressionStats {
 fn main() {
     let start_time = Instant::now();
 
-    // debug_2000_bits_case();
+    let thread_pool = build_thread_pool(parse_threads_arg());
+    println!(
+        "Gathering statistics with {} threads",
+        thread_pool.current_num_threads()
+    );
 
-    generate_bit_limit_stats();
-    generate_sampled_bit_limit_stats();
-    generate_wide_scale_sampled_bit_limit_stats();
+    thread_pool.install(|| {
+        // debug_2000_bits_case();
+
+        generate_bit_limit_stats();
+        generate_sampled_bit_limit_stats();
+        generate_wide_scale_sampled_bit_limit_stats();
+        generate_adaptive_vs_best_stats();
+        generate_rotation_stats();
+        generate_delta_vs_none_stats();
+        generate_two_stage_vs_single_stage_stats();
+        generate_lucas_vs_fibonacci_stats();
+    });
 
     let end_time = Instant::now();
     println!(
@@ -93,6 +154,31 @@ fn main() {
     );
 }
 
+/// Parses the optional `--threads N` argument, returning 0 to let rayon use every available core if it is absent.
+fn parse_threads_arg() -> usize {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(flag_index) = args.iter().position(|arg| arg == "--threads") else {
+        return 0;
+    };
+    let Some(threads) = args
+        .get(flag_index + 1)
+        .and_then(|threads_str| threads_str.parse::<usize>().ok())
+        .filter(|&threads| threads > 0)
+    else {
+        eprintln!("Error: --threads requires a positive integer");
+        std::process::exit(1);
+    };
+    threads
+}
+
+/// Builds the thread pool the statistics are gathered on; 0 threads means one per available core.
+fn build_thread_pool(threads: usize) -> rayon::ThreadPool {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .expect("Failed to build thread pool")
+}
+
 fn generate_stats_csv(stats: &[CompressionStats], csv_header: &str) -> String {
     let mut output = String::new();
     output.push_str(csv_header);
@@ -262,136 +348,430 @@ fn generate_wide_scale_sampled_bit_limit_stats() {
 
 fn gather_stats_for_limit(limit: u64) -> CompressionStats {
     let start_time = Instant::now();
-    let mut compression_amounts = Vec::new();
-    let mut maybe_best_value_amount_pair: Option<(u64, f64)> = None;
 
-    for value_to_compress in 1..=limit {
-        let Some(compression_amount) = compression_amount_percent(value_to_compress) else {
-            continue; // If the compression is not possible, skip this value
-        };
-        compression_amounts.push(compression_amount);
-        maybe_best_value_amount_pair = maybe_best_value_amount_pair.map_or(
-            Some((value_to_compress, compression_amount)),
-            |(current_best_compressed_value, current_best_compression_amount)| {
-                if compression_amount < current_best_compression_amount {
-                    Some((value_to_compress, compression_amount))
-                } else {
-                    Some((
-                        current_best_compressed_value,
-                        current_best_compression_amount,
-                    ))
+    let partial_stats = (1..=limit)
+        .into_par_iter()
+        .fold(PartialStats::default, |partial_stats, value_to_compress| {
+            // If the compression is not possible, skip this value
+            match compression_amount_percent(value_to_compress) {
+                Some(compression_amount) => {
+                    partial_stats.push(value_to_compress, compression_amount)
                 }
-            },
+                None => partial_stats,
+            }
+        })
+        .reduce(PartialStats::default, PartialStats::merge);
+
+    let end_time = Instant::now();
+    println!(
+        "Time taken to gather statistics for limit {:?}: {:?}",
+        limit,
+        end_time.duration_since(start_time)
+    );
+
+    partial_stats.into_compression_stats(limit, true)
+}
+
+/// Compression amounts gathered from a contiguous run of inputs, so runs gathered on different threads can be merged
+/// back in input order.
+#[derive(Debug, Default)]
+struct PartialStats {
+    /// Compression amounts in input order
+    compression_amounts: Vec<f64>,
+    /// The input with the smallest compression amount and that amount, preferring the earliest input on a tie
+    maybe_best_input_amount_pair: Option<(u64, f64)>,
+}
+
+impl PartialStats {
+    fn push(mut self, input: u64, compression_amount: f64) -> Self {
+        self.compression_amounts.push(compression_amount);
+        self.maybe_best_input_amount_pair = Self::better_pair(
+            self.maybe_best_input_amount_pair,
+            Some((input, compression_amount)),
         );
+        self
     }
 
-    if compression_amounts.is_empty() {
-        return CompressionStats {
-            limit,
-            favorable_pct: 0.0,
-            average_pct: 0.0,
-            median_pct: 0.0,
-            maybe_best_compression_amount: None,
-            maybe_best_compressed_input: None,
-            maybe_average_favorable_pct: None,
-            maybe_median_favorable_pct: None,
-        };
+    /// Merges `later`, gathered from the inputs after the ones in `self`.
+    fn merge(mut self, later: Self) -> Self {
+        self.compression_amounts.extend(later.compression_amounts);
+        self.maybe_best_input_amount_pair = Self::better_pair(
+            self.maybe_best_input_amount_pair,
+            later.maybe_best_input_amount_pair,
+        );
+        self
     }
 
-    let total = compression_amounts.len() as f64;
-    let favorable_count = compression_amounts
-        .iter()
-        .filter(|ratio| **ratio < 1.0)
-        .count() as f64;
+    /// Returns the pair with the smaller compression amount, keeping `maybe_earlier` on a tie.
+    fn better_pair(
+        maybe_earlier: Option<(u64, f64)>,
+        maybe_later: Option<(u64, f64)>,
+    ) -> Option<(u64, f64)> {
+        match (maybe_earlier, maybe_later) {
+            (Some(earlier), Some(later)) if later.1 < earlier.1 => Some(later),
+            (Some(earlier), _) => Some(earlier),
+            (None, maybe_later) => maybe_later,
+        }
+    }
 
-    let favorable_pct = (favorable_count / total) * 100.0;
-    let average_pct = compression_amounts.iter().sum::<f64>() / total;
+    /// Aggregates the compression amounts into statistics. Sums are taken in input order, so the statistics do not
+    /// depend on how the inputs were split between threads.
+    fn into_compression_stats(self, limit: u64, record_best_input: bool) -> CompressionStats {
+        let mut compression_amounts = self.compression_amounts;
+        if compression_amounts.is_empty() {
+            return CompressionStats {
+                limit,
+                favorable_pct: 0.0,
+                average_pct: 0.0,
+                median_pct: 0.0,
+                maybe_best_compression_amount: None,
+                maybe_best_compressed_input: None,
+                maybe_average_favorable_pct: None,
+                maybe_median_favorable_pct: None,
+            };
+        }
 
-    let maybe_median = median(&mut compression_amounts);
-    let median_pct = maybe_median.unwrap_or(0.0);
+        let total = compression_amounts.len() as f64;
+        let favorable_count = compression_amounts
+            .iter()
+            .filter(|ratio| **ratio < 1.0)
+            .count() as f64;
 
-    let mut favorable_amounts: Vec<f64> = compression_amounts
-        .iter()
-        .copied()
-        .filter(|ratio| *ratio < 1.0)
-        .collect();
+        let favorable_pct = (favorable_count / total) * 100.0;
+        let average_pct = compression_amounts.iter().sum::<f64>() / total;
 
-    let maybe_average_favorable_pct = if favorable_amounts.is_empty() {
-        None
-    } else {
-        Some(favorable_amounts.iter().sum::<f64>() / favorable_amounts.len() as f64)
-    };
+        let mut favorable_amounts: Vec<f64> = compression_amounts
+            .iter()
+            .copied()
+            .filter(|ratio| *ratio < 1.0)
+            .collect();
 
-    let maybe_median_favorable_pct = median(&mut favorable_amounts);
+        let maybe_median = median(&mut compression_amounts);
+        let median_pct = maybe_median.unwrap_or(0.0);
 
-    let (maybe_best_compressed_input, maybe_best_compression_amount) =
-        if let Some((best_value_input, best_compression_amount)) = maybe_best_value_amount_pair {
-            (Some(best_value_input), Some(best_compression_amount))
+        let maybe_average_favorable_pct = if favorable_amounts.is_empty() {
+            None
         } else {
-            (None, None)
+            Some(favorable_amounts.iter().sum::<f64>() / favorable_amounts.len() as f64)
         };
 
+        let maybe_median_favorable_pct = median(&mut favorable_amounts);
+
+        let maybe_best_compressed_input = self
+            .maybe_best_input_amount_pair
+            .filter(|_| record_best_input)
+            .map(|(input, _)| input);
+        let maybe_best_compression_amount =
+            self.maybe_best_input_amount_pair.map(|(_, amount)| amount);
+
+        CompressionStats {
+            limit,
+            favorable_pct,
+            average_pct,
+            median_pct,
+            maybe_best_compressed_input,
+            maybe_best_compression_amount,
+            maybe_average_favorable_pct,
+            maybe_median_favorable_pct,
+        }
+    }
+}
+
+/// Measures the additional savings of [`compress_zeck_adaptive`] over the plain best of big and little endian compression,
+/// and the extra time it takes, for random data of each size in [`ADAPTIVE_BYTE_SIZES`].
+fn generate_adaptive_vs_best_stats() {
+    let csv_header = "byte size,samples,average best compressed size,average adaptive compressed size,average additional savings bytes,chance of adaptive being smaller,average best time,average adaptive time,adaptive time cost factor\n";
+
+    println!("\n=== Generating adaptive vs best statistics ===");
+    let start_time = Instant::now();
+    let mut csv_content = String::from(csv_header);
+    for byte_size in ADAPTIVE_BYTE_SIZES {
+        let line = gather_adaptive_vs_best_stats(byte_size, ADAPTIVE_SAMPLES_PER_BYTE_SIZE);
+        println!("{}", line);
+        csv_content.push_str(&line);
+        csv_content.push('\n');
+    }
+    let statistics_file_name = format!(
+        "adaptive_vs_best_statistics_up_to_{}_bytes",
+        ADAPTIVE_BYTE_SIZES.last().unwrap()
+    );
+    write_stats_csv(&csv_content, &statistics_file_name);
     let end_time = Instant::now();
     println!(
-        "Time taken to gather statistics for limit {:?}: {:?}",
-        limit,
+        "Time taken to generate adaptive vs best statistics: {:?}",
         end_time.duration_since(start_time)
     );
+}
 
-    CompressionStats {
-        limit,
-        favorable_pct,
-        average_pct,
-        median_pct,
-        maybe_best_compressed_input,
-        maybe_best_compression_amount,
-        maybe_average_favorable_pct,
-        maybe_median_favorable_pct,
+/// Returns a csv line comparing adaptive and plain best compression for random data of the given byte size.
+fn gather_adaptive_vs_best_stats(byte_size: usize, num_samples: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let mut total_best_size = 0usize;
+    let mut total_adaptive_size = 0usize;
+    let mut adaptive_smaller_count = 0u64;
+    let mut total_best_time = std::time::Duration::ZERO;
+    let mut total_adaptive_time = std::time::Duration::ZERO;
+
+    for _ in 0..num_samples {
+        let random_data = generate_random_bytes_of_roughly_bit_size(byte_size as u64 * 8, &mut rng);
+
+        let best_start_time = Instant::now();
+        let best_size = padless_zeckendorf_compress_be_dangerous(&random_data)
+            .len()
+            .min(padless_zeckendorf_compress_le_dangerous(&random_data).len());
+        total_best_time += best_start_time.elapsed();
+
+        let adaptive_start_time = Instant::now();
+        let adaptive_size = compress_zeck_adaptive(&random_data)
+            .expect("Adaptive compression failed")
+            .compressed_data
+            .len();
+        total_adaptive_time += adaptive_start_time.elapsed();
+
+        total_best_size += best_size;
+        total_adaptive_size += adaptive_size;
+        if adaptive_size < best_size {
+            adaptive_smaller_count += 1;
+        }
     }
+
+    let samples = num_samples as f64;
+    let average_best_size = total_best_size as f64 / samples;
+    let average_adaptive_size = total_adaptive_size as f64 / samples;
+    let average_best_time = total_best_time.div_f64(samples);
+    let average_adaptive_time = total_adaptive_time.div_f64(samples);
+    format!(
+        "{},{},{:.6},{:.6},{:.6},{:.6},{:?},{:?},{:.6}",
+        byte_size,
+        num_samples,
+        average_best_size,
+        average_adaptive_size,
+        average_best_size - average_adaptive_size,
+        (adaptive_smaller_count as f64 / samples) * 100.0,
+        average_best_time,
+        average_adaptive_time,
+        average_adaptive_time.as_secs_f64() / average_best_time.as_secs_f64()
+    )
 }
 
-/// Calculates the compression ratio for a given input value, converting the input to a bigint as big endian bytes and then compressing it.
-///
-/// Returns:
-/// - Some(f64) if the compression is possible. The compression ratio as a normalized value where 1.0 = 100% of original size.
-///   Values < 1.0 indicate favorable compression (compressed is smaller), values > 1.0 indicate unfavorable compression (compressed is larger).
-/// - None if the compression is not possible (e.g. if the input is 0)
-fn compression_amount_percent(value: u64) -> Option<f64> {
-    let original_number = BigUint::from(value);
-    let original_bit_size = original_number.bits();
-
-    if original_bit_size == 0 {
-        return None;
+/// Measures how often [`compress_zeck_rotations`] picks a nonzero rotation offset over offset 0, and how many bytes it
+/// saves on average, for random data of each size in [`ROTATION_BYTE_SIZES`].
+fn generate_rotation_stats() {
+    let csv_header = "byte size,samples,average offset 0 compressed size,average rotated compressed size,average savings bytes,chance of nonzero rotation winning\n";
+
+    println!("\n=== Generating rotation statistics ===");
+    let start_time = Instant::now();
+    let mut csv_content = String::from(csv_header);
+    for byte_size in ROTATION_BYTE_SIZES {
+        let line = gather_rotation_stats(byte_size, ROTATION_SAMPLES_PER_BYTE_SIZE);
+        println!("{}", line);
+        csv_content.push_str(&line);
+        csv_content.push('\n');
     }
+    let statistics_file_name = format!(
+        "rotation_statistics_up_to_{}_bytes",
+        ROTATION_BYTE_SIZES.last().unwrap()
+    );
+    write_stats_csv(&csv_content, &statistics_file_name);
+    let end_time = Instant::now();
+    println!(
+        "Time taken to generate rotation statistics: {:?}",
+        end_time.duration_since(start_time)
+    );
+}
 
-    let data_bytes = original_number.to_bytes_be();
-    let compressed_as_zeckendorf_data = padless_zeckendorf_compress_be_dangerous(&data_bytes);
-    let compressed_as_bigint = BigUint::from_bytes_le(&compressed_as_zeckendorf_data);
-    let compressed_bit_size = compressed_as_bigint.bits();
+/// Returns a csv line comparing rotated and offset 0 compression for random data of the given byte size.
+fn gather_rotation_stats(byte_size: usize, num_samples: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let mut total_unrotated_size = 0usize;
+    let mut total_rotated_size = 0usize;
+    let mut nonzero_rotation_count = 0u64;
+
+    for _ in 0..num_samples {
+        let random_data = generate_random_bytes_of_roughly_bit_size(byte_size as u64 * 8, &mut rng);
 
-    let ratio = compressed_bit_size as f64 / original_bit_size as f64;
-    Some(ratio)
+        let preview = exact_compression_preview(&random_data);
+        let (rotation, zeck_file) =
+            compress_zeck_rotations(&random_data).expect("Rotation compression failed");
+
+        total_unrotated_size += preview.be_size.min(preview.le_size);
+        total_rotated_size += zeck_file.compressed_data.len();
+        if rotation != 0 {
+            nonzero_rotation_count += 1;
+        }
+    }
+
+    let samples = num_samples as f64;
+    let average_unrotated_size = total_unrotated_size as f64 / samples;
+    let average_rotated_size = total_rotated_size as f64 / samples;
+    format!(
+        "{},{},{:.6},{:.6},{:.6},{:.6}",
+        byte_size,
+        num_samples,
+        average_unrotated_size,
+        average_rotated_size,
+        average_unrotated_size - average_rotated_size,
+        (nonzero_rotation_count as f64 / samples) * 100.0
+    )
 }
 
-/// Calculates the compression ratio for a given data in bytes.
-///
-/// Returns:
-/// - Some(f64) if the compression is possible. The compression ratio as a normalized value where 1.0 = 100% of original size.
-///   Values < 1.0 indicate favorable compression (compressed is smaller), values > 1.0 indicate unfavorable compression (compressed is larger).
-/// - None if the compression is not possible (e.g. if the input is an empty bytes array)
-fn compression_amount_percent_bytes(data: &[u8]) -> Option<f64> {
-    let original_bit_size = data.len() * 8;
-
-    if original_bit_size == 0 {
-        return None;
+/// Compares compression with and without the delta encoding pre-transform on a text sample and binary samples, for
+/// each size in [`DELTA_BYTE_SIZES`]. Both sides keep the better of big and little endian.
+fn generate_delta_vs_none_stats() {
+    let csv_header = "sample,byte size,plain compressed size,delta compressed size,delta savings bytes,delta is smaller\n";
+    let samples: [(&str, DataGenerator); 3] = [
+        ("text", |size| DELTA_TEXT_SAMPLE[..size].to_vec()),
+        ("ramp", generate_ramp_data),
+        ("random", |size| {
+            generate_random_data(size, DEFAULT_TEST_DATA_SEED)
+        }),
+    ];
+
+    println!("\n=== Generating delta vs none statistics ===");
+    let start_time = Instant::now();
+    let mut csv_content = String::from(csv_header);
+    for (sample_name, generate_data) in samples {
+        for byte_size in DELTA_BYTE_SIZES {
+            let data = generate_data(byte_size);
+            let preview = exact_compression_preview(&data);
+            let plain_size = preview.be_size.min(preview.le_size);
+            let delta_size = [Endianness::Big, Endianness::Little]
+                .into_iter()
+                .map(|endianness| {
+                    compress_zeck_delta(&data, endianness)
+                        .expect("Delta compression failed")
+                        .compressed_data
+                        .len()
+                })
+                .min()
+                .expect("There are two endiannesses");
+            let line = format!(
+                "{},{},{},{},{},{}",
+                sample_name,
+                byte_size,
+                plain_size,
+                delta_size,
+                plain_size as i64 - delta_size as i64,
+                delta_size < plain_size
+            );
+            println!("{}", line);
+            csv_content.push_str(&line);
+            csv_content.push('\n');
+        }
     }
+    let statistics_file_name = format!(
+        "delta_vs_none_statistics_up_to_{}_bytes",
+        DELTA_BYTE_SIZES.last().unwrap()
+    );
+    write_stats_csv(&csv_content, &statistics_file_name);
+    let end_time = Instant::now();
+    println!(
+        "Time taken to generate delta vs none statistics: {:?}",
+        end_time.duration_since(start_time)
+    );
+}
 
-    let compressed_as_zeckendorf_data = padless_zeckendorf_compress_be_dangerous(data);
-    let compressed_as_bigint = BigUint::from_bytes_le(&compressed_as_zeckendorf_data);
-    let compressed_bit_size = compressed_as_bigint.bits();
+/// Measures whether entropy coding the EZBA bits in a second stage beats single-stage compression, for each data
+/// distribution in [`zeck::test_data`] and each size in [`TWO_STAGE_BYTE_SIZES`].
+fn generate_two_stage_vs_single_stage_stats() {
+    let csv_header = "distribution,byte size,single-stage compressed size,two-stage compressed size,two-stage savings bytes,two-stage is smaller\n";
+    let distributions: [(&str, DataGenerator); 5] = [
+        ("random", |size| {
+            generate_random_data(size, DEFAULT_TEST_DATA_SEED)
+        }),
+        ("sparse", |size| {
+            generate_sparse_data(size, DEFAULT_TEST_DATA_SEED)
+        }),
+        ("ramp", generate_ramp_data),
+        ("zeros", generate_zeros_data),
+        ("ones", generate_ones_data),
+    ];
+
+    println!("\n=== Generating two-stage vs single-stage statistics ===");
+    let start_time = Instant::now();
+    let mut csv_content = String::from(csv_header);
+    for (distribution_name, generate_data) in distributions {
+        for byte_size in TWO_STAGE_BYTE_SIZES {
+            let data = generate_data(byte_size);
+            let single_stage_size = padless_zeckendorf_compress_be_dangerous(&data).len();
+            let two_stage_size = compress_zeck_entropy_coded(&data, Endianness::Big)
+                .expect("Two-stage compression failed")
+                .compressed_data
+                .len();
+            let line = format!(
+                "{},{},{},{},{},{}",
+                distribution_name,
+                byte_size,
+                single_stage_size,
+                two_stage_size,
+                single_stage_size as i64 - two_stage_size as i64,
+                two_stage_size < single_stage_size
+            );
+            println!("{}", line);
+            csv_content.push_str(&line);
+            csv_content.push('\n');
+        }
+    }
+    let statistics_file_name = format!(
+        "two_stage_vs_single_stage_statistics_up_to_{}_bytes",
+        TWO_STAGE_BYTE_SIZES.last().unwrap()
+    );
+    write_stats_csv(&csv_content, &statistics_file_name);
+    let end_time = Instant::now();
+    println!(
+        "Time taken to generate two-stage vs single-stage statistics: {:?}",
+        end_time.duration_since(start_time)
+    );
+}
 
-    let ratio = compressed_bit_size as f64 / original_bit_size as f64;
-    Some(ratio)
+/// Measures whether the experimental Lucas codec beats Fibonacci (Zeckendorf) compression, for each data distribution in
+/// [`zeck::test_data`] and each size in [`LUCAS_BYTE_SIZES`].
+fn generate_lucas_vs_fibonacci_stats() {
+    let csv_header = "distribution,byte size,fibonacci compressed size,lucas compressed size,lucas savings bytes,lucas is smaller\n";
+    let distributions: [(&str, DataGenerator); 5] = [
+        ("random", |size| {
+            generate_random_data(size, DEFAULT_TEST_DATA_SEED)
+        }),
+        ("sparse", |size| {
+            generate_sparse_data(size, DEFAULT_TEST_DATA_SEED)
+        }),
+        ("ramp", generate_ramp_data),
+        ("zeros", generate_zeros_data),
+        ("ones", generate_ones_data),
+    ];
+
+    println!("\n=== Generating Lucas vs Fibonacci statistics ===");
+    let start_time = Instant::now();
+    let mut csv_content = String::from(csv_header);
+    for (distribution_name, generate_data) in distributions {
+        for byte_size in LUCAS_BYTE_SIZES {
+            let data = generate_data(byte_size);
+            let fibonacci_size = padless_zeckendorf_compress_be_dangerous(&data).len();
+            let lucas_size = lucas_compress_be(&data).len();
+            let line = format!(
+                "{},{},{},{},{},{}",
+                distribution_name,
+                byte_size,
+                fibonacci_size,
+                lucas_size,
+                fibonacci_size as i64 - lucas_size as i64,
+                lucas_size < fibonacci_size
+            );
+            println!("{}", line);
+            csv_content.push_str(&line);
+            csv_content.push('\n');
+        }
+    }
+    let statistics_file_name = format!(
+        "lucas_vs_fibonacci_statistics_up_to_{}_bytes",
+        LUCAS_BYTE_SIZES.last().unwrap()
+    );
+    write_stats_csv(&csv_content, &statistics_file_name);
+    let end_time = Instant::now();
+    println!(
+        "Time taken to generate Lucas vs Fibonacci statistics: {:?}",
+        end_time.duration_since(start_time)
+    );
 }
 
 /// Generates a random bytes array with roughly the specified number of bits (the number of bits is rounded up to the nearest byte).
@@ -500,64 +880,21 @@ fn _debug_2000_bits_case() {
 
 fn gather_sampled_stats(bit_size_limit: u64, num_samples: u64) -> CompressionStats {
     let start_time = Instant::now();
-    let mut rng = StdRng::seed_from_u64(RNG_SEED);
-    let mut compression_amounts = Vec::new();
-    let mut maybe_best_compression_amount: Option<f64> = None;
 
-    for _ in 0..num_samples {
-        let random_data = generate_random_bytes_of_roughly_bit_size(bit_size_limit, &mut rng);
-        let Some(compression_amount) = compression_amount_percent_bytes(&random_data) else {
-            continue; // If the compression is not possible, skip this sample
-        };
-        compression_amounts.push(compression_amount);
-        maybe_best_compression_amount =
-            maybe_best_compression_amount.map_or(Some(compression_amount), |current_best| {
-                if compression_amount < current_best {
-                    Some(compression_amount)
-                } else {
-                    Some(current_best)
-                }
-            });
-    }
-
-    if compression_amounts.is_empty() {
-        return CompressionStats {
-            limit: bit_size_limit,
-            favorable_pct: 0.0,
-            average_pct: 0.0,
-            median_pct: 0.0,
-            maybe_best_compression_amount: None,
-            maybe_best_compressed_input: None,
-            maybe_average_favorable_pct: None,
-            maybe_median_favorable_pct: None,
-        };
-    }
-
-    let total = compression_amounts.len() as f64;
-    let favorable_count = compression_amounts
-        .iter()
-        .filter(|ratio| **ratio < 1.0)
-        .count() as f64;
-
-    let favorable_pct = (favorable_count / total) * 100.0;
-    let average_pct = compression_amounts.iter().sum::<f64>() / total;
-
-    let maybe_median = median(&mut compression_amounts);
-    let median_pct = maybe_median.unwrap_or(0.0);
-
-    let mut favorable_amounts: Vec<f64> = compression_amounts
-        .iter()
-        .copied()
-        .filter(|ratio| *ratio < 1.0)
-        .collect();
-
-    let maybe_average_favorable_pct = if favorable_amounts.is_empty() {
-        None
-    } else {
-        Some(favorable_amounts.iter().sum::<f64>() / favorable_amounts.len() as f64)
-    };
-
-    let maybe_median_favorable_pct = median(&mut favorable_amounts);
+    let partial_stats = (0..num_samples)
+        .into_par_iter()
+        .fold(PartialStats::default, |partial_stats, sample_index| {
+            let random_data = generate_random_bytes_of_roughly_bit_size(
+                bit_size_limit,
+                &mut sample_rng(sample_index),
+            );
+            // If the compression is not possible, skip this sample
+            match compression_amount_percent_bytes(&random_data) {
+                Some(compression_amount) => partial_stats.push(sample_index, compression_amount),
+                None => partial_stats,
+            }
+        })
+        .reduce(PartialStats::default, PartialStats::merge);
 
     let end_time = Instant::now();
     println!(
@@ -570,27 +907,25 @@ fn gather_sampled_stats(bit_size_limit: u64, num_samples: u64) -> CompressionSta
             .div_f64(num_samples as f64)
     );
 
-    CompressionStats {
-        limit: bit_size_limit,
-        favorable_pct,
-        average_pct,
-        median_pct,
-        maybe_best_compressed_input: None,
-        maybe_best_compression_amount,
-        maybe_average_favorable_pct,
-        maybe_median_favorable_pct,
-    }
+    partial_stats.into_compression_stats(bit_size_limit, false)
 }
 
+/// Returns the random number generator for one sample, seeded from the sample index so that every sample gets the same
+/// data no matter which thread generates it.
+fn sample_rng(sample_index: u64) -> StdRng {
+    StdRng::seed_from_u64(RNG_SEED.wrapping_add(sample_index))
+}
+
+/// Returns the median of `values`, sorting them in place, or [`None`] if there are none.
+///
+/// The values are sorted with [`f64::total_cmp`], so the order is total and deterministic: NaNs (without the sign bit)
+/// sort after every number, and a NaN only becomes the median if at least half of the values are NaN.
 fn median(values: &mut [f64]) -> Option<f64> {
     if values.is_empty() {
         return None;
     }
 
-    values.sort_by(|a, b| match a.partial_cmp(b) {
-        Some(order) => order,
-        None => Ordering::Equal,
-    });
+    values.sort_by(f64::total_cmp);
 
     let len = values.len();
     let mid = len / 2;
@@ -1645,3 +1980,44 @@ fn plot_wide_scale_sampled_favorable_percentages(
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_sorts_nan_to_the_end() {
+        // Arrange
+        let mut odd = [f64::NAN, 3.0, 1.0, 2.0, 5.0];
+        let mut even = [4.0, f64::NAN, 1.0, 2.0];
+
+        // Act
+        let odd_median = median(&mut odd);
+        let even_median = median(&mut even);
+
+        // Assert
+        assert_eq!(odd_median, Some(3.0));
+        assert_eq!(even_median, Some(3.0));
+        assert!(odd[4].is_nan() && even[3].is_nan());
+    }
+
+    #[test]
+    fn stats_match_across_thread_counts() {
+        // Arrange
+        let gather = |threads: usize| {
+            build_thread_pool(threads).install(|| {
+                (
+                    gather_stats_for_limit(5_000),
+                    gather_sampled_stats(200, 500),
+                )
+            })
+        };
+
+        // Act
+        let single_threaded = gather(1);
+        let multi_threaded = gather(4);
+
+        // Assert
+        assert_eq!(single_threaded, multi_threaded);
+    }
+}
@@ -2,6 +2,11 @@
 //!
 //! Example usages:
 //! `cargo run --release --example playground`
+//! `cargo run --release --example playground -- --self-check`
+//!
+//! With `--self-check`, the exploratory output is skipped and only the invariant checks run: compression round-trips,
+//! Fibonacci cross-checks, and all ones Zeckendorf ratios. The process exits with a nonzero status if any fails, so
+//! CI can run it as a smoke test.
 
 use num_bigint::BigUint;
 use num_format::ToFormattedString;
@@ -13,7 +18,15 @@ use zeck::*;
 // Seed for the random number generator to ensure reproducible results
 const RNG_SEED: u64 = 42;
 
+// The ratio of phi squared to the nth power to the nth all ones Zeckendorf number converges to this, see
+// `test_phi_squared_and_all_ones_zeckendorf_ratios`
+const PHI_SQUARED_TO_ALL_ONES_ZECKENDORF_LIMIT: f64 = 1.3819660112501047;
+
 fn main() {
+    if std::env::args().skip(1).any(|arg| arg == "--self-check") {
+        std::process::exit(if run_self_checks() { 0 } else { 1 });
+    }
+
     let start_time = Instant::now();
 
     for i in 0..20 {
@@ -22,7 +35,7 @@ fn main() {
     for i in 0..20 {
         println!(
             "The {i}th Fibonacci number is: {}",
-            memoized_slow_fibonacci_recursive(i)
+            memoized_fibonacci_u64(i)
         );
     }
     for i in 0..20 {
@@ -123,6 +136,153 @@ fn main() {
     println!("Time taken: {:?}", end_time.duration_since(start_time));
 }
 
+/// An invariant check for `--self-check`, returning a description of the first violation it finds.
+type SelfCheck = fn() -> Result<(), String>;
+
+/// Runs every self check, printing the outcome of each, and returns whether they all passed.
+fn run_self_checks() -> bool {
+    let checks: [(&str, SelfCheck); 3] = [
+        ("compression round-trips", check_round_trips),
+        ("Fibonacci cross-checks", check_fibonacci_cross_checks),
+        (
+            "all ones Zeckendorf ratios",
+            check_all_ones_zeckendorf_ratios,
+        ),
+    ];
+    let mut failure_count = 0;
+    for (name, check) in checks {
+        match check() {
+            Ok(()) => println!("ok: {name}"),
+            Err(message) => {
+                println!("FAILED: {name}: {message}");
+                failure_count += 1;
+            }
+        }
+    }
+    println!(
+        "{} of {} self checks passed",
+        checks.len() - failure_count,
+        checks.len()
+    );
+    failure_count == 0
+}
+
+/// Checks that numbers and random data, including data with leading and trailing zero bytes, decompress back to the
+/// original with both the padless functions and the .zeck file format.
+fn check_round_trips() -> Result<(), String> {
+    for number in (0..1_000u64).chain([u64::MAX - 1, u64::MAX]) {
+        let data = BigUint::from(number).to_bytes_be();
+        let compressed_data = padless_zeckendorf_compress_be_dangerous(&data);
+        let decompressed_number = BigUint::from_bytes_be(
+            &padless_zeckendorf_decompress_be_dangerous(&compressed_data),
+        );
+        if decompressed_number != BigUint::from(number) {
+            return Err(format!(
+                "{number} decompressed to {decompressed_number} with the padless functions"
+            ));
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    for size in [0, 1, 2, 15, 64, 257] {
+        let random_bytes = (0..size).map(|_| rng.random::<u8>()).collect::<Vec<u8>>();
+        let data = [vec![0; 3], random_bytes, vec![0; 3]].concat();
+        let compressed_files = [
+            ("big endian", compress_zeck_be(&data)),
+            ("little endian", compress_zeck_le(&data)),
+            ("adaptive", compress_zeck_adaptive(&data)),
+        ];
+        for (name, compressed_file) in compressed_files {
+            let zeck_file =
+                compressed_file.map_err(|error| format!("{name} compression failed: {error}"))?;
+            let decompressed_data = decompress_from_zeck_bytes(&zeck_file.to_bytes())
+                .map_err(|error| format!("{name} decompression failed: {error}"))?;
+            if decompressed_data != data {
+                return Err(format!(
+                    "{} bytes did not round-trip with {name} compression",
+                    data.len()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every Fibonacci function agrees on small and large indices.
+fn check_fibonacci_cross_checks() -> Result<(), String> {
+    for fi in 0..=93u64 {
+        let expected = BigUint::from(memoized_fibonacci_u64(fi));
+        let results = [
+            ("slow iterative", slow_fibonacci_biguint_iterative(fi)),
+            ("fast doubling", fast_doubling_fibonacci_biguint(fi)),
+            ("fibonacci", fibonacci(fi)),
+        ];
+        for (name, result) in results {
+            if *result != expected {
+                return Err(format!(
+                    "the {fi}th Fibonacci number is {expected}, but {name} returned {result}"
+                ));
+            }
+        }
+    }
+
+    for fi in [1_000, 10_000, 50_000] {
+        if slow_fibonacci_biguint_iterative(fi) != fast_doubling_fibonacci_biguint(fi) {
+            return Err(format!(
+                "slow iterative and fast doubling disagree on the {fi}th Fibonacci number"
+            ));
+        }
+        // F(n - 1) * F(n + 1) - F(n)^2 = (-1)^n (Cassini's identity)
+        let previous = fast_doubling_fibonacci_biguint(fi - 1);
+        let next = fast_doubling_fibonacci_biguint(fi + 1);
+        let current = fast_doubling_fibonacci_biguint(fi);
+        let product = &*previous * &*next;
+        let square = &*current * &*current;
+        let is_identity_held = if fi % 2 == 0 {
+            product == square + BigUint::one()
+        } else {
+            square == product + BigUint::one()
+        };
+        if !is_identity_held {
+            return Err(format!(
+                "Cassini's identity does not hold for the {fi}th Fibonacci number"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that consecutive all ones Zeckendorf numbers grow by phi squared, and that phi squared to the nth power
+/// converges to a fixed multiple of the nth all ones Zeckendorf number.
+fn check_all_ones_zeckendorf_ratios() -> Result<(), String> {
+    let tolerance = 1e-6;
+    // We stop at 46 because the 47th all ones Zeckendorf number is too large to fit in a u64, which causes the f64 approximation to be inaccurate.
+    for i in 20..=46 {
+        let curr = all_ones_zeckendorf_to_biguint(i);
+        if all_ones_zeckendorf_to_biguint_fast(i) != curr {
+            return Err(format!(
+                "the fast {i}th all ones Zeckendorf number does not match {curr}"
+            ));
+        }
+
+        let prev = all_ones_zeckendorf_to_biguint(i - 1);
+        let growth_ratio = biguint_to_approximate_f64(&curr) / biguint_to_approximate_f64(&prev);
+        if (growth_ratio - PHI_SQUARED).abs() > tolerance {
+            return Err(format!(
+                "the {i}th all ones Zeckendorf number is {growth_ratio} times the previous one, not phi squared"
+            ));
+        }
+
+        let phi_squared_ratio = PHI_SQUARED.powi(i as i32) / biguint_to_approximate_f64(&curr);
+        if (phi_squared_ratio - PHI_SQUARED_TO_ALL_ONES_ZECKENDORF_LIMIT).abs() > tolerance {
+            return Err(format!(
+                "phi squared to the {i}th power is {phi_squared_ratio} times the {i}th all ones Zeckendorf number, not {PHI_SQUARED_TO_ALL_ONES_ZECKENDORF_LIMIT}"
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn _test_zeckendorf_compress_and_decompress_number(number: u64) {
     println!("Number to compress: {:?}", number);
     let data = BigUint::from(number).to_bytes_be();
@@ -205,15 +365,11 @@ fn _test_bit_count_for_all_ones_effective_zeckendorf_bits_ascending() {
 /// - The first element is the Fibonacci index
 /// - The second element is the Fibonacci number value
 fn _find_fibonacci_by_bit_count(target_bits: u64) -> (u64, BigUint) {
-    let mut index = 0u64;
-    loop {
-        let fibonacci = memoized_slow_fibonacci_biguint_iterative(index);
-        let bit_count = fibonacci.bits();
-        if bit_count >= target_bits {
-            return (index, (*fibonacci).clone());
-        }
-        index += 1;
-    }
+    let (index, fibonacci) = fibonacci_iter()
+        .enumerate()
+        .find(|(_, fibonacci)| fibonacci.bits() >= target_bits)
+        .expect("The Fibonacci numbers grow without bound");
+    (index as u64, (*fibonacci).clone())
 }
 
 fn _test_find_fibonacci_by_bit_count() {
@@ -264,19 +420,16 @@ fn _test_slow_fibonacci_bigint_iterative_large(fi: u64) {
 
 fn _test_fast_doubling_fibonacci_bigint() {
     println!("Testing fast doubling Fibonacci bigint function");
-    let fibonacci = memoized_fast_doubling_fibonacci_biguint(100);
+    // The isolated computation counts the cached values independently of what earlier calls have cached
+    let (fibonacci, cached_count) = fast_doubling_fibonacci_biguint_isolated(100);
     println!("The 100th Fibonacci number is: {}", fibonacci);
-    let cache = zeck::FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE
-        .read()
-        .expect("Failed to read fast doubling Fibonacci cache");
     println!(
         "Querying the 100th Fibonacci number, using the fast doubling algorithm, generated only {} cached Fibonacci numbers",
-        cache.len()
+        cached_count
     );
-    assert_eq!(cache.len(), 10);
-    let mut sorted_cache = cache.iter().collect::<Vec<_>>();
-    sorted_cache.sort_by_key(|(fi, _)| *fi);
-    for (fi, value) in sorted_cache.iter() {
+    assert_eq!(cached_count, 10);
+    memoized_fast_doubling_fibonacci_biguint(100);
+    for (fi, value) in fast_doubling_cache_snapshot() {
         println!(
             "The {fi}th Fibonacci number, using the fast doubling algorithm, is: {}",
             value
@@ -55,6 +55,10 @@ fn main() {
     )
     .expect("Failed to plot Fibonacci, binary, all-ones Zeckendorf, 3^n, φⁿ, and φ²ⁿ numbers");
 
+    // Example: Plot the ratio of consecutive all-ones Zeckendorf numbers against φ²
+    plot_all_ones_ratio_convergence("plots/all_ones_ratio_convergence_2_to_30.png", 30)
+        .expect("Failed to plot all-ones Zeckendorf ratio convergence");
+
     // _plot_all_compression_ratios();
 
     // _plot_all_histograms();
@@ -1172,11 +1176,8 @@ fn _plot_compression_ratios(
             // println!("Original bit size: {:?}", original_bit_size);
             let data_bytes = original_number.to_bytes_be();
             // println!("Data bytes as big endian: {:?}", data_bytes);
-            let compressed_as_zeckendorf_data =
-                padless_zeckendorf_compress_be_dangerous(&data_bytes);
-            // println!("Compressed: {:?}", compressed_as_zeckendorf_data);
-            // Since the last step of the compression outputs the data with the least significant bits and bytes first, we need to interpret the data as little endian.
-            let compressed_as_bigint = BigUint::from_bytes_le(&compressed_as_zeckendorf_data);
+            // Since the last step of the compression outputs the data with the least significant bits and bytes first, compressed_value interprets the data as little endian.
+            let compressed_as_bigint = compressed_value(&data_bytes);
             // println!("Compressed as bigint: {:?}", compressed_as_bigint);
             // Calculate bits required to store the compressed representation
             let compressed_bit_size = compressed_as_bigint.bits() as f64;
@@ -1882,3 +1883,157 @@ fn plot_fibonacci_square_root_error_convergence(
     );
     Ok(())
 }
+
+/// Plots the ratio of each all-ones Zeckendorf number to the previous one, overlaid with a horizontal line at φ².
+///
+/// The ratio of consecutive all-ones Zeckendorf numbers converges to the golden ratio squared (φ² = φ + 1), which
+/// `_test_all_ones_zeckendorf_ratios` in the playground example prints numerically. This plot visualizes that
+/// convergence. The ratios are computed from the top bits of the [`BigUint`] values, so they stay accurate beyond the
+/// range of [`u64`].
+///
+/// # Arguments
+///
+/// * `filename` - The path where the plot image will be saved (e.g., "plots/all_ones_ratio.png")
+/// * `max_n` - The largest number of ones n to plot; ratios are plotted for n from 2 to `max_n`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the plot was successfully created, or an error if plotting failed.
+///
+/// # Examples
+///
+/// ```
+/// plot_all_ones_ratio_convergence("plots/all_ones_ratio_convergence_2_to_30.png", 30)?;
+/// ```
+fn plot_all_ones_ratio_convergence(
+    filename: &str,
+    max_n: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let start_time = Instant::now();
+    println!(
+        "Plotting all-ones Zeckendorf ratio convergence to φ² for n up to {}",
+        max_n
+    );
+
+    let ratio_data: Vec<(f64, f64)> = (2..=max_n)
+        .map(|n| {
+            let curr = all_ones_zeckendorf_to_biguint(n as usize);
+            let prev = all_ones_zeckendorf_to_biguint(n as usize - 1);
+            (n as f64, biguint_ratio(&curr, &prev))
+        })
+        .collect();
+
+    if ratio_data.is_empty() {
+        return Err("max_n must be at least 2 to plot any ratios".into());
+    }
+
+    // Include φ² in the y-axis range so the line is always visible, with some padding
+    let min_y = ratio_data
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(PHI_SQUARED, |acc, y| acc.min(y));
+    let max_y = ratio_data
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(PHI_SQUARED, |acc, y| acc.max(y));
+    let y_padding = (max_y - min_y) * 0.1;
+    let x_range = 2.0f64..max_n as f64;
+
+    let root = BitMapBackend::new(filename, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            "All-Ones Zeckendorf Ratio Convergence to φ²",
+            ("sans-serif", CAPTION_FONT_SIZE).into_font(),
+        )
+        .margin(CHART_MARGIN)
+        .x_label_area_size(260)
+        .y_label_area_size(300)
+        .build_cartesian_2d(x_range.clone(), min_y - y_padding..max_y + y_padding)?;
+
+    let axis_label_style =
+        TextStyle::from(("sans-serif", AXIS_FONT_SIZE).into_font()).color(&BLACK);
+    let axis_tick_style =
+        TextStyle::from(("sans-serif", AXIS_TICK_FONT_SIZE).into_font()).color(&BLACK);
+
+    chart
+        .configure_mesh()
+        .x_desc("Number of ones n")
+        .y_desc("AOZN(n) / AOZN(n-1)")
+        .y_label_formatter(&|y: &f64| format!("{:.3}", y))
+        .label_style(axis_tick_style)
+        .axis_desc_style(axis_label_style)
+        .draw()?;
+
+    // Draw phi squared line
+    chart
+        .draw_series(LineSeries::new(
+            [(x_range.start, PHI_SQUARED), (x_range.end, PHI_SQUARED)],
+            RED.stroke_width(SERIES_LINE_STROKE_WIDTH),
+        ))?
+        .label(format!("Phi Squared (φ² ≈ {:.6})", PHI_SQUARED))
+        .legend(|(x, y)| {
+            PathElement::new(
+                vec![
+                    (x - LEGEND_PATH_LEFT_OFFSET, y),
+                    (x + LEGEND_PATH_RIGHT_OFFSET, y),
+                ],
+                RED.stroke_width(SERIES_LINE_STROKE_WIDTH),
+            )
+        });
+
+    // Draw the measured ratio series
+    chart
+        .draw_series(LineSeries::new(
+            ratio_data.iter().copied(),
+            GREEN.stroke_width(SERIES_LINE_STROKE_WIDTH),
+        ))?
+        .label("All-Ones Zeckendorf Ratio")
+        .legend(|(x, y)| {
+            PathElement::new(
+                vec![
+                    (x - LEGEND_PATH_LEFT_OFFSET, y),
+                    (x + LEGEND_PATH_RIGHT_OFFSET, y),
+                ],
+                GREEN.stroke_width(SERIES_LINE_STROKE_WIDTH),
+            )
+        });
+
+    // Draw dots at each point for the ratio
+    chart.draw_series(
+        ratio_data
+            .iter()
+            .map(|point| Circle::new(*point, SERIES_LINE_DOT_SIZE, GREEN.filled())),
+    )?;
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .margin(LEGEND_MARGIN)
+        .label_font(("sans-serif", LEGEND_FONT_SIZE).into_font())
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    println!(
+        "All-ones Zeckendorf ratio convergence plot saved to {}",
+        filename
+    );
+    let end_time = Instant::now();
+    println!(
+        "Time taken to plot all-ones Zeckendorf ratio convergence for n up to {}: {:?}",
+        max_n,
+        end_time.duration_since(start_time)
+    );
+    Ok(())
+}
+
+/// Divides two [`BigUint`]s as [`f64`]s, shifting both down to their top 64 bits first so that values beyond the
+/// range of [`f64`] still give an accurate ratio. The numerator must not be smaller than the denominator.
+fn biguint_ratio(numerator: &BigUint, denominator: &BigUint) -> f64 {
+    let shift = numerator.bits().saturating_sub(64);
+    biguint_to_approximate_f64(&(numerator >> shift))
+        / biguint_to_approximate_f64(&(denominator >> shift))
+}
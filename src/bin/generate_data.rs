@@ -1,10 +1,12 @@
-//! Generate random test data files
+//! Generate test data files
 //!
-//! This tool generates random test data files in the `generated_data/` directory.
+//! This tool generates test data files in the `generated_data/` directory. The bytes are random by default, or follow
+//! one of the structured patterns of [`zeck::test_data::TestDataPattern`] with `--pattern`, such as all-ones data to
+//! exercise the worst case of Zeckendorf compression.
 //!
 //! Building and running the tool:
 //! `cargo build --release --bin zeck-generate-data --features development_tools`
-//! `cargo run --release --bin zeck-generate-data --features development_tools -- <size_in_bytes> [filename]`
+//! `cargo run --release --bin zeck-generate-data --features development_tools -- [--pattern <pattern>] <size_in_bytes> [filename] [seed]`
 //!
 //! # Examples
 //!
@@ -17,20 +19,55 @@
 //! ```bash
 //! cargo run --release --bin zeck-generate-data --features development_tools -- 1024 my_file.bin
 //! ```
+//!
+//! Generate the same file on every run by passing a seed:
+//! ```bash
+//! cargo run --release --bin zeck-generate-data --features development_tools -- 1024 my_file.bin 42
+//! ```
+//!
+//! Generate a file of all-ones bytes:
+//! ```bash
+//! cargo run --release --bin zeck-generate-data --features development_tools -- --pattern ones 1024
+//! ```
 
 use rand::RngCore;
 use std::env;
 use std::fs;
 use std::io::Write;
 use std::path::Path;
+use zeck::test_data::TestDataPattern;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // Take the optional `--pattern <pattern>` option out of the positional arguments
+    let mut pattern = TestDataPattern::default();
+    if let Some(option_index) = args.iter().position(|arg| arg == "--pattern") {
+        let Some(pattern_str) = args.get(option_index + 1) else {
+            eprintln!("Error: --pattern requires a value");
+            std::process::exit(1);
+        };
+        pattern = match pattern_str.parse::<TestDataPattern>() {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        };
+        args.drain(option_index..=option_index + 1);
+    }
 
     if args.len() < 2 {
-        eprintln!("Usage: {} <size_in_bytes> [filename]", args[0]);
+        eprintln!(
+            "Usage: {} [--pattern <pattern>] <size_in_bytes> [filename] [seed]",
+            args[0]
+        );
+        eprintln!("  --pattern: zeros, ones, ramp, stripe, sparse, or random (default: random)");
         eprintln!("  size_in_bytes: The size of the file to generate in bytes");
-        eprintln!("  filename: Optional filename (default: random_data_<size>_bytes.bin)");
+        eprintln!("  filename: Optional filename (default: <pattern>_data_<size>_bytes.bin)");
+        eprintln!(
+            "  seed: Optional u64 seed that makes the sparse and random patterns reproducible"
+        );
         std::process::exit(1);
     }
 
@@ -54,7 +91,19 @@ fn main() {
     let filename = if let Some(name) = maybe_filename {
         name.clone()
     } else {
-        format!("random_data_{size}_bytes.bin")
+        format!("{pattern}_data_{size}_bytes.bin")
+    };
+
+    let maybe_seed = match args.get(3).map(|seed_str| seed_str.parse::<u64>()) {
+        None => None,
+        Some(Ok(seed)) => Some(seed),
+        Some(Err(_)) => {
+            eprintln!(
+                "Error: '{}' is not a valid seed. Please provide a u64.",
+                args[3]
+            );
+            std::process::exit(1);
+        }
     };
 
     // Create the generated_data directory if it doesn't exist
@@ -64,10 +113,9 @@ fn main() {
         std::process::exit(1);
     }
 
-    // Generate random data
-    let mut rng = rand::rng();
-    let mut data = vec![0u8; size];
-    rng.fill_bytes(&mut data);
+    // Generate the data, reproducibly if a seed was given
+    let seed = maybe_seed.unwrap_or_else(|| rand::rng().next_u64());
+    let data = pattern.generate(size, seed);
 
     // Write the file
     let file_path = output_dir.join(&filename);
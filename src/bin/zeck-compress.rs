@@ -25,29 +25,88 @@
 //! zeck-compress input.bin --endian big
 //! # Creates input.bin.zeck
 //! ```
+//!
+//! Print the compressed value as a decimal number, for example to pipe into an arbitrary-precision calculator:
+//! ```bash
+//! zeck-compress input.bin --emit decimal
+//! ```
+//!
+//! Print the compression statistics as JSON on stdout for a script to parse:
+//! ```bash
+//! zeck-compress input.bin --json-stats
+//! ```
+//!
+//! Compare the big and little endian compressed sizes without writing any output:
+//! ```bash
+//! zeck-compress input.bin --compare
+//! ```
+//!
+//! Report the output path, endianness, and sizes a compression would produce, without writing anything:
+//! ```bash
+//! zeck-compress input.bin --dry-run
+//! ```
 
 // Include the generated version string from the build.rs script
 include!(concat!(env!("OUT_DIR"), "/version_string.rs"));
 
 use clap::Parser;
+use num_bigint::BigUint;
+use serde::Serialize;
+use std::fmt;
 use std::fs;
 use std::io::{self, IsTerminal, Read, Write};
-use zeck::zeck_file_format::compress::BestCompressionResult;
-use zeck::zeck_file_format::{
-    compress::compress_zeck_be, compress::compress_zeck_best, compress::compress_zeck_le,
+use std::str::FromStr;
+use std::time::UNIX_EPOCH;
+use zeck::zeck_file_format::compress::{
+    BestCompressionResult, ThresholdCompressionResult, ZECK_BEST_EXHAUSTIVE_THRESHOLD,
+    compress_zeck_best_with_threshold,
 };
+use zeck::{EndianChoice, Endianness, ZeckFile, compress_zeck, exact_compression_preview};
+
+/// How the compressed output is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmitFormat {
+    /// A binary .zeck file.
+    Binary,
+    /// The compressed data, read as a little endian big integer, as a decimal string.
+    Decimal,
+    /// The compressed data, read as a little endian big integer, as a lowercase hexadecimal string.
+    Hex,
+}
+
+impl EmitFormat {
+    /// Returns the compressed data as text, or [`None`] for [`EmitFormat::Binary`].
+    fn format_compressed_data(self, compressed_data: &[u8]) -> Option<String> {
+        let radix = match self {
+            EmitFormat::Binary => return None,
+            EmitFormat::Decimal => 10,
+            EmitFormat::Hex => 16,
+        };
+        Some(BigUint::from_bytes_le(compressed_data).to_str_radix(radix))
+    }
+}
+
+impl FromStr for EmitFormat {
+    type Err = String;
 
-#[derive(Debug, Clone, Copy)]
-enum EndianUsed {
-    Big,
-    Little,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "binary" => Ok(EmitFormat::Binary),
+            "decimal" => Ok(EmitFormat::Decimal),
+            "hex" => Ok(EmitFormat::Hex),
+            _ => Err(format!(
+                "Invalid emit format '{s}'. Expected 'binary', 'decimal', or 'hex'"
+            )),
+        }
+    }
 }
 
-impl EndianUsed {
-    fn display_name(&self) -> &'static str {
+impl fmt::Display for EmitFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            EndianUsed::Big => "big endian",
-            EndianUsed::Little => "little endian",
+            EmitFormat::Binary => write!(f, "binary"),
+            EmitFormat::Decimal => write!(f, "decimal"),
+            EmitFormat::Hex => write!(f, "hex"),
         }
     }
 }
@@ -82,11 +141,67 @@ struct Args {
         value_name = "ENDIAN",
         default_value = "best"
     )]
-    endian: String,
+    endian: EndianChoice,
 
-    /// Show compression statistics (default: true)
-    #[arg(short, long, default_value_t = true)]
+    /// Output format.
+    /// - "binary": Write a .zeck file (default)
+    /// - "decimal": Print the compressed data, read as a little endian big integer, as a decimal number
+    /// - "hex": Print the compressed data, read as a little endian big integer, as a hexadecimal number
+    ///
+    /// Text output goes to stdout, or verbatim to the `--output` path if one is given; the `.zeck` extension is not added.
+    #[arg(long = "emit", value_name = "FORMAT", default_value = "binary")]
+    emit: EmitFormat,
+
+    /// Show compression statistics. This is the default; the flag only overrides an earlier `--quiet`.
+    #[arg(short, long, overrides_with = "quiet")]
     verbose: bool,
+
+    /// Don't print compression statistics to stderr
+    #[arg(short, long, overrides_with = "verbose")]
+    quiet: bool,
+
+    /// Print the compression statistics to stdout as a single JSON object instead of printing the output path.
+    ///
+    /// The compressed output must then go to a file, so this needs an input file or `--output`.
+    #[arg(long = "json-stats")]
+    json_stats: bool,
+
+    /// Print the big and little endian compressed sizes and ratios to stdout and exit without writing any output.
+    #[arg(long = "compare", conflicts_with_all = ["maybe_output", "json_stats"])]
+    compare: bool,
+
+    /// Print the output path, endianness, sizes, and whether compression is favorable to stdout, without writing
+    /// any output.
+    ///
+    /// Exits with an error, like a real run, if `--endian best` would find no endianness that compresses the data.
+    #[arg(long = "dry-run", conflicts_with_all = ["compare", "json_stats"])]
+    dry_run: bool,
+}
+
+/// Compression statistics printed by `--json-stats`.
+#[derive(Debug, Serialize)]
+struct ZeckStats {
+    /// The endianness used for the output, `"big"` or `"little"`
+    endianness: String,
+    /// The size of the input in bytes
+    original_size: usize,
+    /// The size of the compressed data in bytes, without the header
+    compressed_size: usize,
+    /// The size of the whole .zeck file in bytes, with the header
+    total_size: usize,
+    /// The compressed size divided by the original size
+    ratio: f64,
+    /// The compressed size in bytes with big endian interpretation, or `null` if it was not computed
+    be_size: Option<usize>,
+    /// The compressed size in bytes with little endian interpretation, or `null` if it was not computed
+    le_size: Option<usize>,
+}
+
+/// A compressed file and the compressed data size for each endianness, [`None`] where a size was not computed.
+struct Compression {
+    zeck_file: ZeckFile,
+    maybe_be_size: Option<usize>,
+    maybe_le_size: Option<usize>,
 }
 
 fn main() {
@@ -95,135 +210,101 @@ fn main() {
     // Read input data
     let input_data = read_input_data(&args);
 
-    if input_data.is_empty() {
-        eprintln!("Error: Input data is empty");
-        std::process::exit(1);
-    }
-
     let original_size = input_data.len();
 
+    if args.compare {
+        print_comparison(&input_data);
+        return;
+    }
+    if args.dry_run {
+        print_dry_run(&args, &input_data);
+        return;
+    }
+
     // Compress data based on endianness option
-    let (zeck_file, maybe_be_size, maybe_le_size) = match args.endian.to_lowercase().as_str() {
-        "big" => {
-            let zeck_file = match compress_zeck_be(&input_data) {
-                Ok(file) => file,
-                Err(e) => {
-                    eprintln!("Error: Compression failed: {}", e);
-                    std::process::exit(1);
-                }
-            };
-            let be_size = zeck_file.compressed_data.len();
-            (zeck_file, Some(be_size), None)
-        }
-        "little" => {
-            let zeck_file = match compress_zeck_le(&input_data) {
-                Ok(file) => file,
-                Err(e) => {
-                    eprintln!("Error: Compression failed: {}", e);
-                    std::process::exit(1);
-                }
-            };
-            let le_size = zeck_file.compressed_data.len();
-            (zeck_file, None, Some(le_size))
-        }
-        "best" => {
-            let best_compression_result = match compress_zeck_best(&input_data) {
-                Ok(result) => result,
-                Err(e) => {
-                    eprintln!("Error: Best compression failed: {}", e);
-                    std::process::exit(1);
-                }
-            };
-            match best_compression_result {
-                BestCompressionResult::BigEndianBest { zeck_file, le_size } => {
-                    let be_size = zeck_file.compressed_data.len();
-                    (zeck_file, Some(be_size), Some(le_size))
-                }
-                BestCompressionResult::LittleEndianBest { zeck_file, be_size } => {
-                    let le_size = zeck_file.compressed_data.len();
-                    (zeck_file, Some(be_size), Some(le_size))
-                }
-                BestCompressionResult::Neither { be_size, le_size } => {
-                    eprintln!(
-                        "Error: Neither compression method produced a smaller output than the original. Big endian size: {} bytes, Little endian size: {} bytes",
-                        be_size, le_size
-                    );
-                    std::process::exit(1);
-                }
-            }
-        }
-        _ => {
-            eprintln!(
-                "Error: Invalid endianness '{}'. Must be 'big', 'little', or 'best'.",
-                args.endian
-            );
-            std::process::exit(1);
-        }
-    };
+    let Compression {
+        mut zeck_file,
+        maybe_be_size,
+        maybe_le_size,
+    } = compress_input(&input_data, args.endian);
+
+    // Record the modification time of the input file, so zeck-decompress can restore it
+    zeck_file.maybe_mtime = args.maybe_input.as_deref().and_then(input_mtime);
 
     // Determine endianness from the zeck_file
-    let endian_used = if zeck_file.is_big_endian() {
-        EndianUsed::Big
-    } else {
-        EndianUsed::Little
-    };
+    let endian_used = zeck_file.endianness();
 
-    let zeck_file_as_data = zeck_file.to_bytes();
     let compressed_data_size = zeck_file.compressed_data.len();
     let total_size = zeck_file.total_size();
+    let maybe_compressed_text = args.emit.format_compressed_data(&zeck_file.compressed_data);
+    let output_data = match &maybe_compressed_text {
+        Some(compressed_text) => format!("{compressed_text}\n").into_bytes(),
+        None => zeck_file.to_bytes(),
+    };
 
-    // Use .zeck extension for the new format
-    let file_extension = ".zeck";
+    let final_output_path = output_path(&args, maybe_compressed_text.is_some());
 
-    // Determine output path
-    let final_output_path = if let Some(output_path) = &args.maybe_output {
-        // If output is explicitly specified, use it (add extension if needed)
-        if output_path.ends_with(".zeck") {
-            output_path.clone()
-        } else {
-            format!("{output_path}{file_extension}")
-        }
-    } else if let Some(input_path) = &args.maybe_input {
-        // If no output specified but input file exists, use input filename + extension
-        format!("{input_path}{file_extension}")
-    } else {
-        // Reading from stdin, no output file - will write to stdout
-        String::new()
-    };
+    if args.json_stats && final_output_path.is_empty() {
+        eprintln!("Error: --json-stats prints to stdout, so the compressed output needs a file.");
+        eprintln!("Hint: Provide an input file path or an output path with --output.");
+        std::process::exit(2);
+    }
 
     // Write output data
     if final_output_path.is_empty() {
         // Write to stdout
-        if let Err(err) = io::stdout().write_all(&zeck_file_as_data) {
+        if let Err(err) = io::stdout().write_all(&output_data) {
             eprintln!("Error: Failed to write to stdout: {}", err);
             std::process::exit(1);
         }
     } else {
         // Write to file
-        if let Err(err) = fs::write(&final_output_path, &zeck_file_as_data) {
+        if let Err(err) = fs::write(&final_output_path, &output_data) {
             eprintln!(
                 "Error: Failed to write output file '{}': {}",
                 final_output_path, err
             );
             std::process::exit(1);
         }
-        // Output filename to stdout
-        println!("Compressed to: {}", final_output_path);
+        // Output filename to stdout, unless stdout is reserved for the JSON statistics
+        if !args.json_stats {
+            println!("Compressed to: {}", final_output_path);
+        }
     }
 
-    // Print statistics if verbose
-    if args.verbose {
-        let compression_ratio = compressed_data_size as f64 / original_size as f64;
-        let compression_percentage = if compression_ratio < 1.0 {
-            (1.0 - compression_ratio) * 100.0
-        } else {
-            (compression_ratio - 1.0) * 100.0
+    if args.json_stats {
+        let stats = ZeckStats {
+            endianness: endian_used.to_string(),
+            original_size,
+            compressed_size: compressed_data_size,
+            total_size,
+            ratio: zeck_file.compression_ratio(),
+            be_size: maybe_be_size,
+            le_size: maybe_le_size,
         };
+        match serde_json::to_string(&stats) {
+            Ok(json) => println!("{json}"),
+            Err(err) => {
+                eprintln!("Error: Failed to serialize statistics: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Print statistics unless quiet
+    if !args.quiet {
+        let compression_ratio = zeck_file.compression_ratio();
+        let compression_percentage = zeck_file.space_saving().abs() * 100.0;
 
         eprintln!("Endianness used: {}", endian_used.display_name());
-        if let (Some(be_size), Some(le_size)) = (maybe_be_size, maybe_le_size) {
-            eprintln!("Big endian size: {} bytes", be_size);
-            eprintln!("Little endian size: {} bytes", le_size);
+        if args.endian == EndianChoice::Best {
+            if maybe_be_size.is_none() || maybe_le_size.is_none() {
+                eprintln!(
+                    "Skipped the exhaustive endianness comparison for input larger than {ZECK_BEST_EXHAUSTIVE_THRESHOLD} bytes; the endianness was predicted"
+                );
+            }
+            eprintln!("Big endian size: {}", format_size(maybe_be_size));
+            eprintln!("Little endian size: {}", format_size(maybe_le_size));
         }
         eprintln!("Original size: {} bytes", original_size);
         eprintln!("Compressed data size: {} bytes", compressed_data_size);
@@ -240,6 +321,214 @@ fn main() {
     }
 }
 
+/// Compresses the input with the chosen endianness, exiting with an error if best mode finds nothing that compresses.
+///
+/// The sizes are the ones the compression measured, so nothing is compressed twice. Big and little endian modes only
+/// measure their own endianness, and best mode only tries both endiannesses up to
+/// [`ZECK_BEST_EXHAUSTIVE_THRESHOLD`] bytes; the size for an endianness that was not tried is not computed.
+fn compress_input(input_data: &[u8], endian: EndianChoice) -> Compression {
+    let result = match endian {
+        EndianChoice::Best => {
+            compress_zeck_best_with_threshold(input_data, ZECK_BEST_EXHAUSTIVE_THRESHOLD)
+        }
+        EndianChoice::Big | EndianChoice::Little => {
+            return match compress_zeck(input_data, endian) {
+                Ok(zeck_file) => {
+                    let size = Some(zeck_file.compressed_data.len());
+                    let (maybe_be_size, maybe_le_size) = match zeck_file.endianness() {
+                        Endianness::Big => (size, None),
+                        Endianness::Little => (None, size),
+                    };
+                    Compression {
+                        zeck_file,
+                        maybe_be_size,
+                        maybe_le_size,
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: Compression failed: {}", e);
+                    std::process::exit(1);
+                }
+            };
+        }
+    };
+
+    match result {
+        Ok(ThresholdCompressionResult::Compared(BestCompressionResult::BigEndianBest {
+            zeck_file,
+            le_size,
+        })) => Compression {
+            maybe_be_size: Some(zeck_file.compressed_data.len()),
+            maybe_le_size: Some(le_size),
+            zeck_file,
+        },
+        Ok(ThresholdCompressionResult::Compared(BestCompressionResult::LittleEndianBest {
+            zeck_file,
+            be_size,
+        })) => Compression {
+            maybe_be_size: Some(be_size),
+            maybe_le_size: Some(zeck_file.compressed_data.len()),
+            zeck_file,
+        },
+        Ok(ThresholdCompressionResult::Compared(BestCompressionResult::Neither {
+            be_size,
+            le_size,
+        })) => {
+            eprintln!(
+                "Error: Neither compression method produced a smaller output than the original. Big endian size: {} bytes, Little endian size: {} bytes",
+                be_size, le_size
+            );
+            std::process::exit(1);
+        }
+        Ok(ThresholdCompressionResult::Predicted {
+            endianness,
+            maybe_zeck_file: Some(zeck_file),
+            size,
+        }) => {
+            let (maybe_be_size, maybe_le_size) = match endianness {
+                Endianness::Big => (Some(size), None),
+                Endianness::Little => (None, Some(size)),
+            };
+            Compression {
+                zeck_file,
+                maybe_be_size,
+                maybe_le_size,
+            }
+        }
+        Ok(ThresholdCompressionResult::Predicted {
+            endianness,
+            maybe_zeck_file: None,
+            size,
+        }) => {
+            eprintln!(
+                "Error: The predicted {} compression did not produce a smaller output than the original. Size: {} bytes. The other endianness was not tried for input larger than {ZECK_BEST_EXHAUSTIVE_THRESHOLD} bytes",
+                endianness.display_name(),
+                size
+            );
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error: Compression failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Formats a compressed size for the statistics, or "not computed" if it was skipped.
+fn format_size(maybe_size: Option<usize>) -> String {
+    match maybe_size {
+        Some(size) => format!("{size} bytes"),
+        None => "not computed".to_string(),
+    }
+}
+
+/// Returns the path the output is written to, or an empty string for stdout.
+fn output_path(args: &Args, is_text_output: bool) -> String {
+    // Use .zeck extension for the new format
+    let file_extension = ".zeck";
+
+    if is_text_output {
+        // Text output is written verbatim to the output path if given, otherwise to stdout
+        args.maybe_output.clone().unwrap_or_default()
+    } else if let Some(output_path) = &args.maybe_output {
+        // If output is explicitly specified, use it (add extension if needed)
+        if output_path.ends_with(".zeck") {
+            output_path.clone()
+        } else {
+            format!("{output_path}{file_extension}")
+        }
+    } else if let Some(input_path) = &args.maybe_input {
+        // If no output specified but input file exists, use input filename + extension
+        format!("{input_path}{file_extension}")
+    } else {
+        // Reading from stdin, no output file - will write to stdout
+        String::new()
+    }
+}
+
+/// Prints what compressing would do for `--dry-run`: the output path, the endianness, and the compressed size.
+///
+/// The sizes come from [`exact_compression_preview`], so nothing is compressed or written. Exits with an error if
+/// `--endian best` would fail because neither endianness makes the data smaller.
+fn print_dry_run(args: &Args, input_data: &[u8]) {
+    let preview = exact_compression_preview(input_data);
+    let endianness = match args.endian {
+        EndianChoice::Big => Endianness::Big,
+        EndianChoice::Little => Endianness::Little,
+        EndianChoice::Best => preview.best_endianness,
+    };
+    let compressed_size = match endianness {
+        Endianness::Big => preview.be_size,
+        Endianness::Little => preview.le_size,
+    };
+    let is_favorable = compressed_size < input_data.len();
+    let ratio = if input_data.is_empty() {
+        1.0
+    } else {
+        compressed_size as f64 / input_data.len() as f64
+    };
+    let output_path = output_path(args, args.emit != EmitFormat::Binary);
+
+    println!(
+        "Output: {}",
+        if output_path.is_empty() {
+            "stdout"
+        } else {
+            &output_path
+        }
+    );
+    println!("Endianness: {}", endianness.display_name());
+    println!("Original size: {} bytes", input_data.len());
+    println!("Compressed data size: {compressed_size} bytes (ratio {ratio:.4})");
+    println!("Favorable: {}", if is_favorable { "yes" } else { "no" });
+
+    if args.endian == EndianChoice::Best && !is_favorable {
+        eprintln!(
+            "Error: Neither compression method would produce a smaller output than the original. Big endian size: {} bytes, Little endian size: {} bytes",
+            preview.be_size, preview.le_size
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Prints the compressed sizes and ratios of both endiannesses for `--compare`.
+///
+/// The ratios are the compressed size divided by the original size, like [`zeck::ZeckFile::compression_ratio`].
+fn print_comparison(input_data: &[u8]) {
+    let preview = exact_compression_preview(input_data);
+    let ratio = |size: usize| {
+        if input_data.is_empty() {
+            1.0
+        } else {
+            size as f64 / input_data.len() as f64
+        }
+    };
+
+    println!("Original size: {} bytes", input_data.len());
+    println!(
+        "Big endian size: {} bytes (ratio {:.4})",
+        preview.be_size,
+        ratio(preview.be_size)
+    );
+    println!(
+        "Little endian size: {} bytes (ratio {:.4})",
+        preview.le_size,
+        ratio(preview.le_size)
+    );
+    println!(
+        "Best endianness: {}",
+        preview.best_endianness.display_name()
+    );
+}
+
+/// Returns the modification time of the input file in seconds since the Unix epoch, or [`None`] if the platform or
+/// filesystem does not provide one.
+fn input_mtime(input_path: &str) -> Option<u64> {
+    let modified = fs::metadata(input_path).and_then(|metadata| metadata.modified());
+    let since_epoch = modified.ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(since_epoch.as_secs())
+}
+
 fn read_input_data(args: &Args) -> Vec<u8> {
     let Some(input_path) = &args.maybe_input else {
         return read_stdin_piped_only();
@@ -1,7 +1,8 @@
 //! Zeckendorf decompression CLI tool
 //!
 //! Decompresses data that was compressed using the Zeckendorf representation algorithm.
-//! Automatically detects endianness from the file header.
+//! Automatically detects endianness from the header of `.zeck` files. Raw legacy files without a header
+//! (`.zbe`/`.zle`) take their endianness from the extension or the `--endian` flag.
 //!
 //! Building and running the tool:
 //! `cargo build --release --bin zeck-decompress --features cli_tools`
@@ -19,15 +20,112 @@
 //! ```bash
 //! cat input.zeck | zeck-decompress
 //! ```
+//!
+//! Decompress a raw legacy file without a header:
+//! ```bash
+//! zeck-decompress input.zbe
+//! # Big endian is implied by the .zbe extension, creates output file "input"
+//! cat input.raw | zeck-decompress --endian little
+//! ```
+//!
+//! Decompress raw legacy input of unknown endianness, using the CRC-32 of the original data to pick the right one:
+//! ```bash
+//! cat input.raw | zeck-decompress --auto --checksum cbf43926
+//! ```
+//!
+//! Print the decompressed data as a decimal number, for data that was a number to begin with:
+//! ```bash
+//! zeck-decompress input.zeck --emit decimal
+//! ```
+//!
+//! Refuse to write more than 1 MiB of decompressed data:
+//! ```bash
+//! zeck-decompress input.zeck --max-output 1048576
+//! ```
 
 // Include the generated version string from the build.rs script
 include!(concat!(env!("OUT_DIR"), "/version_string.rs"));
 
 use clap::Parser;
+use num_bigint::BigUint;
+use std::fmt;
 use std::fs;
 use std::io::{self, IsTerminal, Read, Write};
+use std::str::FromStr;
+use std::time::{Duration, UNIX_EPOCH};
+use zeck::zeck_file_format::checksum::crc32;
 use zeck::zeck_file_format::decompress::decompress_zeck_file;
 use zeck::zeck_file_format::file::deserialize_zeck_file;
+use zeck::{
+    Endianness, padless_zeckendorf_decompress_be_dangerous,
+    padless_zeckendorf_decompress_le_dangerous,
+};
+
+/// Extension of .zeck files, whose header records the endianness.
+const ZECK_EXTENSION: &str = ".zeck";
+/// Extension of raw legacy files compressed with big endian.
+const LEGACY_BIG_ENDIAN_EXTENSION: &str = ".zbe";
+/// Extension of raw legacy files compressed with little endian.
+const LEGACY_LITTLE_ENDIAN_EXTENSION: &str = ".zle";
+/// Default limit on the decompressed output size, in bytes (1 GiB).
+const DEFAULT_MAX_OUTPUT_BYTES: u64 = 1 << 30;
+
+/// How the decompressed output is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmitFormat {
+    /// The raw decompressed bytes.
+    Binary,
+    /// The decompressed data, read as a big integer in the endianness it was compressed with, as a decimal string.
+    Decimal,
+    /// The decompressed data, read as a big integer in the endianness it was compressed with, as a lowercase
+    /// hexadecimal string.
+    Hex,
+}
+
+impl EmitFormat {
+    /// Returns the decompressed data as text, or [`None`] for [`EmitFormat::Binary`].
+    fn format_decompressed_data(
+        self,
+        decompressed_data: &[u8],
+        endianness: Endianness,
+    ) -> Option<String> {
+        let radix = match self {
+            EmitFormat::Binary => return None,
+            EmitFormat::Decimal => 10,
+            EmitFormat::Hex => 16,
+        };
+        let value = match endianness {
+            Endianness::Big => BigUint::from_bytes_be(decompressed_data),
+            Endianness::Little => BigUint::from_bytes_le(decompressed_data),
+        };
+        Some(value.to_str_radix(radix))
+    }
+}
+
+impl FromStr for EmitFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "binary" => Ok(EmitFormat::Binary),
+            "decimal" => Ok(EmitFormat::Decimal),
+            "hex" => Ok(EmitFormat::Hex),
+            _ => Err(format!(
+                "Invalid emit format '{s}'. Expected 'binary', 'decimal', or 'hex'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for EmitFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmitFormat::Binary => write!(f, "binary"),
+            EmitFormat::Decimal => write!(f, "decimal"),
+            EmitFormat::Hex => write!(f, "hex"),
+        }
+    }
+}
 
 #[derive(Parser, Debug)]
 #[command(
@@ -44,63 +142,203 @@ struct Args {
     #[arg(value_name = "INPUT")]
     maybe_input: Option<String>,
 
-    /// Output file path. If not specified and input is a file, uses the input filename with `.zeck` extension removed.
+    /// Output file path. If not specified and input is a file, uses the input filename with the `.zeck`, `.zbe`,
+    /// or `.zle` extension removed.
     /// If not specified and reading from stdin, writes to stdout.
     #[arg(short = 'o', long = "output", value_name = "FILE")]
     maybe_output: Option<String>,
 
-    /// Show decompression statistics (default: true)
-    #[arg(short, long, default_value_t = true)]
+    /// Endianness of a raw legacy file without a .zeck header ("big" or "little").
+    ///
+    /// Only needed for raw legacy input; `.zbe` and `.zle` files imply big and little endian, and `.zeck` files
+    /// record their endianness in the header. Setting this treats input without a `.zeck` extension as raw legacy data.
+    #[arg(short = 'e', long = "endian", value_name = "ENDIAN")]
+    maybe_endian: Option<Endianness>,
+
+    /// Detect the endianness of raw legacy input by decompressing it both ways.
+    ///
+    /// Raw legacy data has no header, so this needs `--checksum` to tell which result is the original data, and
+    /// fails asking for `--endian` without it. Has no effect on `.zeck` files, whose header records the endianness.
+    #[arg(long = "auto", conflicts_with = "maybe_endian")]
+    auto_endian: bool,
+
+    /// CRC-32 of the original data as hexadecimal (for example `cbf43926`), used by `--auto` to pick the endianness
+    /// whose result matches.
+    #[arg(long = "checksum", value_name = "CRC32", value_parser = parse_crc32)]
+    maybe_checksum: Option<u32>,
+
+    /// Output format.
+    /// - "binary": Write the decompressed bytes (default)
+    /// - "decimal": Print the decompressed data, read as a big integer in the endianness it was compressed with, as a
+    ///   decimal number
+    /// - "hex": Print the decompressed data, read as a big integer in the endianness it was compressed with, as a
+    ///   hexadecimal number
+    ///
+    /// Text output goes to stdout, or to the `--output` path if one is given.
+    #[arg(long = "emit", value_name = "FORMAT", default_value = "binary")]
+    emit: EmitFormat,
+
+    /// Largest decompressed output to produce, in bytes. Decompression fails instead of producing more.
+    ///
+    /// A small .zeck file can declare a huge original size, so this is checked against the header before
+    /// decompressing.
+    #[arg(long = "max-output", value_name = "BYTES", default_value_t = DEFAULT_MAX_OUTPUT_BYTES)]
+    max_output: u64,
+
+    /// Show decompression statistics. This is the default; the flag only overrides an earlier `--quiet`.
+    #[arg(short, long, overrides_with = "quiet")]
     verbose: bool,
+
+    /// Don't print decompression statistics to stderr
+    #[arg(short, long, overrides_with = "verbose")]
+    quiet: bool,
+}
+
+/// How the input data is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputFormat {
+    /// A .zeck file whose header records the endianness and original size.
+    Zeck,
+    /// Raw compressed data without a header, as written by older versions of `zeck-compress`.
+    Legacy(Endianness),
+    /// Raw compressed data of unknown endianness, detected by decompressing it both ways with `--auto`.
+    LegacyAuto,
+}
+
+impl InputFormat {
+    /// Determines the input format from the input file extension and the `--endian` flag.
+    ///
+    /// `.zeck` files and input without a recognized extension or `--endian` flag are parsed as .zeck files.
+    fn detect(args: &Args) -> Self {
+        let maybe_input = args.maybe_input.as_deref();
+        if maybe_input.is_some_and(|input_path| input_path.ends_with(ZECK_EXTENSION)) {
+            if args.maybe_endian.is_some() {
+                eprintln!(
+                    "Warning: Ignoring --endian because .zeck files record their endianness in the header"
+                );
+            }
+            if args.auto_endian {
+                eprintln!(
+                    "Warning: Ignoring --auto because .zeck files record their endianness in the header"
+                );
+            }
+            return InputFormat::Zeck;
+        }
+        if args.auto_endian {
+            return InputFormat::LegacyAuto;
+        }
+
+        let maybe_extension_endianness = maybe_input.and_then(|input_path| {
+            if input_path.ends_with(LEGACY_BIG_ENDIAN_EXTENSION) {
+                Some(Endianness::Big)
+            } else if input_path.ends_with(LEGACY_LITTLE_ENDIAN_EXTENSION) {
+                Some(Endianness::Little)
+            } else {
+                None
+            }
+        });
+        match args.maybe_endian.or(maybe_extension_endianness) {
+            Some(endianness) => InputFormat::Legacy(endianness),
+            None => InputFormat::Zeck,
+        }
+    }
 }
 
 fn main() {
     let args = Args::parse();
 
     // Read input data
-    let zeck_file_data = read_input_data(&args);
-
-    if zeck_file_data.is_empty() {
-        eprintln!("Error: Input data is empty");
-        std::process::exit(1);
-    }
+    let input_data = read_input_data(&args);
 
-    // Deserialize and decompress data (endianness is automatically detected from header)
-    let zeck_file = match deserialize_zeck_file(&zeck_file_data) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Error: Failed to deserialize .zeck file: {}", e);
-            std::process::exit(1);
+    let (
+        decompressed_data,
+        compressed_size,
+        total_size,
+        endianness,
+        compression_ratio,
+        maybe_mtime,
+    ) = match InputFormat::detect(&args) {
+        InputFormat::Zeck => {
+            // Deserialize and decompress data (endianness is automatically detected from header)
+            let zeck_file = match deserialize_zeck_file(&input_data) {
+                Ok(file) => file,
+                Err(e) => {
+                    eprintln!("Error: Failed to deserialize .zeck file: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            // The header records the exact decompressed size, so check it before doing any work
+            check_output_size(zeck_file.original_size, args.max_output);
+            let decompressed_data = match decompress_zeck_file(&zeck_file) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("Error: Decompression failed: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            (
+                decompressed_data,
+                zeck_file.compressed_data.len(),
+                zeck_file.total_size(),
+                zeck_file.endianness(),
+                zeck_file.compression_ratio(),
+                zeck_file.maybe_mtime,
+            )
         }
-    };
-    let decompressed_data = match decompress_zeck_file(&zeck_file) {
-        Ok(data) => data,
-        Err(e) => {
-            eprintln!("Error: Decompression failed: {}", e);
-            std::process::exit(1);
+        legacy_format @ (InputFormat::Legacy(_) | InputFormat::LegacyAuto) => {
+            let (endianness, decompressed_data) = match legacy_format {
+                InputFormat::Legacy(endianness) => {
+                    (endianness, decompress_legacy(&input_data, endianness))
+                }
+                _ => detect_legacy_endianness(&input_data, args.maybe_checksum),
+            };
+            // Raw legacy data has no header to check first, but its output is not much larger than the input
+            check_output_size(decompressed_data.len() as u64, args.max_output);
+            // There is no ZeckFile to ask for the ratio, so compute it the same way
+            let compression_ratio = input_data.len() as f64 / decompressed_data.len() as f64;
+            (
+                decompressed_data,
+                input_data.len(),
+                input_data.len(),
+                endianness,
+                compression_ratio,
+                None,
+            )
         }
     };
-
-    let compressed_size = zeck_file.compressed_data.len();
-    let total_size = zeck_file.total_size();
     let decompressed_size = decompressed_data.len();
+    let maybe_decompressed_text = args
+        .emit
+        .format_decompressed_data(&decompressed_data, endianness);
+    let output_data = match &maybe_decompressed_text {
+        Some(decompressed_text) => format!("{decompressed_text}\n").into_bytes(),
+        None => decompressed_data,
+    };
 
     // Determine output path
-    let final_output_path = if let Some(output_path) = &args.maybe_output {
+    let final_output_path = if maybe_decompressed_text.is_some() {
+        // Text output is written to the output path if given, otherwise to stdout
+        args.maybe_output.clone().unwrap_or_default()
+    } else if let Some(output_path) = &args.maybe_output {
         // Use explicitly specified output path
         output_path.clone()
     } else if let Some(input_path) = &args.maybe_input {
-        // Remove .zeck extension from input filename
-        if input_path.ends_with(".zeck") {
-            input_path
-                .strip_suffix(".zeck")
-                .unwrap_or(input_path)
-                .to_string()
-        } else {
-            eprintln!(
-                "Error: Input file extension did not end with '.zeck'. An output file path must be specified or the input file must have a '.zeck' extension."
-            );
-            std::process::exit(1);
+        // Remove the compressed file extension from input filename
+        let maybe_stripped = [
+            ZECK_EXTENSION,
+            LEGACY_BIG_ENDIAN_EXTENSION,
+            LEGACY_LITTLE_ENDIAN_EXTENSION,
+        ]
+        .into_iter()
+        .find_map(|extension| input_path.strip_suffix(extension));
+        match maybe_stripped {
+            Some(stripped) => stripped.to_string(),
+            None => {
+                eprintln!(
+                    "Error: Input file extension did not end with '.zeck', '.zbe', or '.zle'. An output file path must be specified or the input file must have one of these extensions."
+                );
+                std::process::exit(1);
+            }
         }
     } else {
         // Reading from stdin, no output file - will write to stdout
@@ -110,34 +348,33 @@ fn main() {
     // Write output data
     if final_output_path.is_empty() {
         // Write to stdout
-        if let Err(err) = io::stdout().write_all(&decompressed_data) {
+        if let Err(err) = io::stdout().write_all(&output_data) {
             eprintln!("Error: Failed to write to stdout: {}", err);
             std::process::exit(1);
         }
     } else {
         // Write to file
-        if let Err(err) = fs::write(&final_output_path, &decompressed_data) {
+        if let Err(err) = fs::write(&final_output_path, &output_data) {
             eprintln!(
                 "Error: Failed to write output file '{}': {}",
                 final_output_path, err
             );
             std::process::exit(1);
         }
+        // Restore the modification time of the original file onto the decompressed data, not onto a text rendering
+        if let Some(mtime) = maybe_mtime.filter(|_| maybe_decompressed_text.is_none()) {
+            restore_mtime(&final_output_path, mtime);
+        }
         // Output filename to stdout
         println!("Decompressed to: {}", final_output_path);
     }
 
-    // Print statistics if verbose
-    if args.verbose {
-        let expansion_ratio = decompressed_size as f64 / compressed_size as f64;
+    // Print statistics unless quiet
+    if !args.quiet {
+        let expansion_ratio = 1.0 / compression_ratio;
         let expansion_percentage = (expansion_ratio - 1.0) * 100.0;
 
-        let endianness_used = if zeck_file.is_big_endian() {
-            "big endian"
-        } else {
-            "little endian"
-        };
-        eprintln!("Endianness used: {}", endianness_used);
+        eprintln!("Endianness used: {}", endianness.display_name());
         if decompressed_size < compressed_size {
             // File got smaller during decompression
             let shrink_percentage = (1.0 - expansion_ratio) * 100.0;
@@ -153,6 +390,88 @@ fn main() {
     }
 }
 
+/// Sets the modification time of the output file to `mtime` seconds since the Unix epoch, warning if it fails, since
+/// the decompressed data itself was written successfully.
+fn restore_mtime(output_path: &str, mtime: u64) {
+    let result = fs::File::options()
+        .write(true)
+        .open(output_path)
+        .and_then(|file| file.set_modified(UNIX_EPOCH + Duration::from_secs(mtime)));
+    if let Err(err) = result {
+        eprintln!("Warning: Failed to restore the modification time of '{output_path}': {err}");
+    }
+}
+
+/// Exits with an error if the decompressed output would be larger than the `--max-output` limit.
+fn check_output_size(output_size: u64, max_output: u64) {
+    if output_size > max_output {
+        eprintln!(
+            "Error: Decompressed output would be {output_size} bytes, more than the --max-output limit of {max_output} bytes"
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Decompresses raw legacy data without a header.
+///
+/// Raw legacy data has no header, so leading (big endian) or trailing (little endian) zero bytes of the original data
+/// cannot be restored.
+fn decompress_legacy(input_data: &[u8], endianness: Endianness) -> Vec<u8> {
+    match endianness {
+        Endianness::Big => padless_zeckendorf_decompress_be_dangerous(input_data),
+        Endianness::Little => padless_zeckendorf_decompress_le_dangerous(input_data),
+    }
+}
+
+/// Decompresses raw legacy data both ways and returns the endianness whose result matches the checksum, exiting with
+/// an error if there is no checksum or if it does not single out one result.
+fn detect_legacy_endianness(
+    input_data: &[u8],
+    maybe_checksum: Option<u32>,
+) -> (Endianness, Vec<u8>) {
+    let Some(checksum) = maybe_checksum else {
+        eprintln!(
+            "Error: --auto needs --checksum to tell big and little endian apart, since raw input has no header."
+        );
+        eprintln!("Hint: Pass the endianness explicitly with --endian big or --endian little.");
+        std::process::exit(2);
+    };
+
+    let mut matches = [Endianness::Big, Endianness::Little]
+        .into_iter()
+        .map(|endianness| (endianness, decompress_legacy(input_data, endianness)))
+        .filter(|(_, decompressed_data)| crc32(decompressed_data) == checksum)
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [] => {
+            eprintln!(
+                "Error: Neither big nor little endian decompression matches checksum {checksum:08x}"
+            );
+            std::process::exit(1);
+        }
+        [(_, big_endian_data), (_, little_endian_data)]
+            if big_endian_data != little_endian_data =>
+        {
+            eprintln!(
+                "Error: Both big and little endian decompression match checksum {checksum:08x} with different data."
+            );
+            eprintln!("Hint: Pass the endianness explicitly with --endian big or --endian little.");
+            std::process::exit(1);
+        }
+        _ => matches.swap_remove(0),
+    }
+}
+
+/// Parses a CRC-32 written as hexadecimal, with or without a `0x` prefix.
+fn parse_crc32(checksum: &str) -> Result<u32, String> {
+    let digits = checksum
+        .strip_prefix("0x")
+        .or_else(|| checksum.strip_prefix("0X"))
+        .unwrap_or(checksum);
+    u32::from_str_radix(digits, 16)
+        .map_err(|_| format!("Invalid checksum '{checksum}'. Expected a hexadecimal CRC-32"))
+}
+
 fn read_input_data(args: &Args) -> Vec<u8> {
     let Some(input_path) = &args.maybe_input else {
         return read_stdin_piped_only();
@@ -0,0 +1,269 @@
+//! Bit-level writing and reading in the packed layout used by compressed data
+//!
+//! Bits are packed least significant bit first: the first bit is bit 0 of the first byte, the ninth bit is bit 0 of
+//! the second byte, and so on. The last byte is padded with zero bits. This is the layout of
+//! [`crate::pack_ezba_bits_to_bytes`] and [`crate::EzbaBits`], so format features that store extra bit fields can
+//! share it with the EZBA bits.
+//!
+//! # Examples
+//!
+//! ```
+//! # use zeck::bits::{BitReader, BitWriter};
+//! let mut writer = BitWriter::new();
+//! writer.write_bit(1);
+//! writer.write_value(0b101, 3);
+//! writer.write_bits(&[0, 0, 1]);
+//! assert_eq!(writer.len(), 7);
+//! let bytes = writer.finish();
+//! assert_eq!(bytes, vec![0b100_1011]);
+//!
+//! let mut reader = BitReader::new(&bytes);
+//! assert_eq!(reader.read_bit(), Some(1));
+//! assert_eq!(reader.read_value(3), Some(0b101));
+//! assert_eq!(reader.remaining(), 4);
+//! ```
+
+/// Writes bits into bytes, least significant bit first, padding the last byte with zero bits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    /// Creates an empty writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty writer with room for at least `bit_capacity` bits.
+    pub fn with_capacity(bit_capacity: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(bit_capacity.div_ceil(8)),
+            len: 0,
+        }
+    }
+
+    /// Appends one bit, the low bit of `bit`.
+    pub fn write_bit(&mut self, bit: u8) {
+        let bit_index = self.len % 8;
+        if bit_index == 0 {
+            self.bytes.push(0);
+        }
+        // The byte was pushed above when bit_index was 0, so the last byte always exists here.
+        *self.bytes.last_mut().expect("Bytes are non-empty") |= (bit & 1) << bit_index;
+        self.len += 1;
+    }
+
+    /// Appends a slice of bits, one per [`u8`], using the low bit of each.
+    ///
+    /// When the writer is at a byte boundary, full bytes are packed 8 bits at a time with a multiplication bit trick,
+    /// which is several times faster than writing them one at a time.
+    pub fn write_bits(&mut self, bits: &[u8]) {
+        if !self.len.is_multiple_of(8) {
+            for &bit in bits {
+                self.write_bit(bit);
+            }
+            return;
+        }
+
+        self.bytes.reserve(bits.len().div_ceil(8));
+        let mut chunks = bits.chunks_exact(8);
+        for chunk in &mut chunks {
+            // Load the 8 bits as the 8 byte lanes of a u64, then gather the low bit of every lane into the top byte.
+            let lanes = u64::from_le_bytes(chunk.try_into().expect("Chunks are exactly 8 bits"));
+            self.bytes.push(
+                ((lanes & BYTE_LANES_LOW_BITS).wrapping_mul(GATHER_LOW_BITS_MAGIC) >> 56) as u8,
+            );
+        }
+        self.len += bits.len() - chunks.remainder().len();
+        for &bit in chunks.remainder() {
+            self.write_bit(bit);
+        }
+    }
+
+    /// Appends the low `bit_count` bits of `value`, least significant bit first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit_count` is greater than 64.
+    pub fn write_value(&mut self, value: u64, bit_count: u32) {
+        assert!(bit_count <= u64::BITS, "Cannot write more than 64 bits");
+        for bit_index in 0..bit_count {
+            self.write_bit(((value >> bit_index) & 1) as u8);
+        }
+    }
+
+    /// Returns the number of bits written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bits were written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes written so far; the last byte is padded with zero bits.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the writer and returns the bytes; the last byte is padded with zero bits.
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Selects the low bit of each of the 8 byte lanes of a [`u64`].
+const BYTE_LANES_LOW_BITS: u64 = 0x0101_0101_0101_0101;
+
+/// Multiplying the low bits of the 8 byte lanes of a [`u64`] by this constant shifts the bit of lane `i` to bit `56 + i`,
+/// without any carries, so the top byte of the product holds the 8 bits packed in ascending order.
+const GATHER_LOW_BITS_MAGIC: u64 = 0x0102_0408_1020_4080;
+
+/// Reads bits from bytes, least significant bit first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader positioned at the first bit of `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// Reads the next bit, returning [`None`] at the end of the bytes.
+    pub fn read_bit(&mut self) -> Option<u8> {
+        let byte = self.bytes.get(self.position / 8)?;
+        let bit = (byte >> (self.position % 8)) & 1;
+        self.position += 1;
+        Some(bit)
+    }
+
+    /// Reads the next `bit_count` bits as a value whose first bit is the least significant, the inverse of
+    /// [`BitWriter::write_value`]. Returns [`None`] without consuming anything if fewer bits remain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit_count` is greater than 64.
+    pub fn read_value(&mut self, bit_count: u32) -> Option<u64> {
+        assert!(bit_count <= u64::BITS, "Cannot read more than 64 bits");
+        if self.remaining() < bit_count as usize {
+            return None;
+        }
+        let mut value = 0u64;
+        for bit_index in 0..bit_count {
+            value |= u64::from(self.read_bit()?) << bit_index;
+        }
+        Some(value)
+    }
+
+    /// Reads every remaining bit, one per [`u8`].
+    ///
+    /// When the reader is at a byte boundary, each byte is expanded by copying its 8 bits from a precomputed lookup
+    /// table, which is faster than reading them one at a time.
+    pub fn read_to_end(&mut self) -> Vec<u8> {
+        let mut bits = Vec::with_capacity(self.remaining());
+        while !self.position.is_multiple_of(8) {
+            let Some(bit) = self.read_bit() else {
+                return bits;
+            };
+            bits.push(bit);
+        }
+        for &byte in &self.bytes[self.position / 8..] {
+            bits.extend_from_slice(&BITS_FOR_BYTE[byte as usize]);
+        }
+        self.position = self.bytes.len() * 8;
+        bits
+    }
+
+    /// Returns the number of bits read so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of bits left to read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+}
+
+/// Lookup table from every byte value to its 8 bits in ascending significance, built at compile time.
+const BITS_FOR_BYTE: [[u8; 8]; 256] = {
+    let mut table = [[0u8; 8]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut i = 0;
+        while i < 8 {
+            table[byte][i] = ((byte >> i) & 1) as u8;
+            i += 1;
+        }
+        byte += 1;
+    }
+    table
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_non_byte_aligned_lengths() {
+        let bits: Vec<u8> = (0..70u32).map(|i| ((i * 5 + i / 4) % 2) as u8).collect();
+        for len in [0, 1, 7, 9, 15, 17, 63, 65, 70] {
+            // Arrange
+            let mut writer = BitWriter::new();
+            writer.write_bits(&bits[..len]);
+
+            // Act
+            let bytes = writer.finish();
+            let read_back = BitReader::new(&bytes).read_to_end();
+
+            // Assert
+            assert_eq!(bytes.len(), len.div_ceil(8), "len: {len}");
+            assert_eq!(read_back[..len], bits[..len], "len: {len}");
+            assert!(read_back[len..].iter().all(|&bit| bit == 0), "len: {len}");
+        }
+    }
+
+    #[test]
+    fn interleaved_writes_read_back_in_order() {
+        // Arrange
+        let mut writer = BitWriter::new();
+        writer.write_value(0b10, 2);
+        writer.write_bits(&[1, 1, 0, 1, 0, 0, 0, 1, 1]);
+        writer.write_bit(1);
+        writer.write_value(u64::MAX, 64);
+        writer.write_bits(&[0, 1, 0]);
+        let bytes = writer.finish();
+
+        // Act
+        let mut reader = BitReader::new(&bytes);
+
+        // Assert
+        assert_eq!(reader.read_value(2), Some(0b10));
+        assert_eq!(reader.read_value(9), Some(0b1_1000_1011));
+        assert_eq!(reader.read_bit(), Some(1));
+        assert_eq!(reader.read_value(64), Some(u64::MAX));
+        assert_eq!(reader.position(), 76);
+        assert_eq!(reader.read_to_end(), vec![0, 1, 0, 0]);
+        assert_eq!(reader.read_bit(), None);
+    }
+
+    #[test]
+    fn read_value_past_end_consumes_nothing() {
+        // Arrange
+        let mut reader = BitReader::new(&[0xFF]);
+        reader.read_value(5);
+
+        // Act
+        let result = reader.read_value(4);
+
+        // Assert
+        assert_eq!(result, None);
+        assert_eq!(reader.read_value(3), Some(0b111));
+    }
+}
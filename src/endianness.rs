@@ -0,0 +1,209 @@
+//! Byte order choices shared by the library and the command-line tools
+
+use std::fmt;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// The byte order used to interpret input data as a big integer before compression.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// The first byte is the most significant byte.
+    Big,
+    /// The first byte is the least significant byte.
+    Little,
+}
+
+impl Endianness {
+    /// Returns a human-readable name, such as `"big endian"`, for statistics output.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Endianness::Big => "big endian",
+            Endianness::Little => "little endian",
+        }
+    }
+}
+
+/// Parses `"big"` or `"little"`, ignoring ASCII case.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// assert_eq!("big".parse(), Ok(Endianness::Big));
+/// assert_eq!("Little".parse(), Ok(Endianness::Little));
+/// assert!("best".parse::<Endianness>().is_err());
+/// ```
+impl FromStr for Endianness {
+    type Err = ParseEndiannessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "big" => Ok(Endianness::Big),
+            "little" => Ok(Endianness::Little),
+            _ => Err(ParseEndiannessError {
+                input: s.to_string(),
+                expected: "'big' or 'little'",
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Endianness::Big => write!(f, "big"),
+            Endianness::Little => write!(f, "little"),
+        }
+    }
+}
+
+/// Which byte order to compress with: a fixed [`Endianness`], or whichever of the two compresses best.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EndianChoice {
+    /// Always interpret the input as big endian.
+    Big,
+    /// Always interpret the input as little endian.
+    Little,
+    /// Try both byte orders and keep the smaller result.
+    #[default]
+    Best,
+}
+
+/// Parses `"big"`, `"little"`, or `"best"`, ignoring ASCII case.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::EndianChoice;
+/// assert_eq!("big".parse(), Ok(EndianChoice::Big));
+/// assert_eq!("BEST".parse(), Ok(EndianChoice::Best));
+/// assert!("middle".parse::<EndianChoice>().is_err());
+/// ```
+impl FromStr for EndianChoice {
+    type Err = ParseEndiannessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "big" => Ok(EndianChoice::Big),
+            "little" => Ok(EndianChoice::Little),
+            "best" => Ok(EndianChoice::Best),
+            _ => Err(ParseEndiannessError {
+                input: s.to_string(),
+                expected: "'big', 'little', or 'best'",
+            }),
+        }
+    }
+}
+
+impl fmt::Display for EndianChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndianChoice::Big => write!(f, "big"),
+            EndianChoice::Little => write!(f, "little"),
+            EndianChoice::Best => write!(f, "best"),
+        }
+    }
+}
+
+impl From<Endianness> for EndianChoice {
+    fn from(endianness: Endianness) -> Self {
+        match endianness {
+            Endianness::Big => EndianChoice::Big,
+            Endianness::Little => EndianChoice::Little,
+        }
+    }
+}
+
+/// The error returned when parsing an [`Endianness`] or [`EndianChoice`] from an unrecognized string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEndiannessError {
+    /// The string that failed to parse
+    pub input: String,
+    /// The accepted values, for the error message
+    expected: &'static str,
+}
+
+impl fmt::Display for ParseEndiannessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid endianness '{}'. Must be {}.",
+            self.input, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseEndiannessError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endianness_parses_valid_inputs_ignoring_case() {
+        for (input, expected) in [
+            ("big", Endianness::Big),
+            ("BIG", Endianness::Big),
+            ("little", Endianness::Little),
+            ("Little", Endianness::Little),
+        ] {
+            // Act
+            let result = input.parse::<Endianness>();
+
+            // Assert
+            assert_eq!(result, Ok(expected), "input: {input}");
+        }
+    }
+
+    #[test]
+    fn endianness_rejects_best_and_unknown_inputs() {
+        for input in ["best", "", "middle", "big "] {
+            // Act
+            let result = input.parse::<Endianness>();
+
+            // Assert
+            assert_eq!(result.unwrap_err().input, input);
+        }
+    }
+
+    #[test]
+    fn endian_choice_parses_valid_inputs_ignoring_case() {
+        for (input, expected) in [
+            ("big", EndianChoice::Big),
+            ("little", EndianChoice::Little),
+            ("best", EndianChoice::Best),
+            ("Best", EndianChoice::Best),
+        ] {
+            // Act
+            let result = input.parse::<EndianChoice>();
+
+            // Assert
+            assert_eq!(result, Ok(expected), "input: {input}");
+        }
+    }
+
+    #[test]
+    fn endian_choice_error_message_lists_accepted_values() {
+        // Act
+        let error = "middle".parse::<EndianChoice>().unwrap_err();
+
+        // Assert
+        assert_eq!(
+            error.to_string(),
+            "Invalid endianness 'middle'. Must be 'big', 'little', or 'best'."
+        );
+    }
+
+    #[test]
+    fn display_round_trips_through_from_str() {
+        for choice in [EndianChoice::Big, EndianChoice::Little, EndianChoice::Best] {
+            // Act
+            let result = choice.to_string().parse::<EndianChoice>();
+
+            // Assert
+            assert_eq!(result, Ok(choice));
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Errors for the core Zeckendorf functions
+
+/// Errors that can occur when validating Zeckendorf representations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZeckError {
+    /// A Fibonacci index below 2 was used. Index 0 has the value 0, and index 1 duplicates the value of index 2,
+    /// so neither can appear in a Zeckendorf representation.
+    InvalidFibonacciIndex {
+        /// The invalid Fibonacci index
+        fi: u64,
+    },
+    /// The Fibonacci indices are not in strictly descending order.
+    NotDescending {
+        /// The earlier index in the list
+        previous: u64,
+        /// The later index in the list, which is not smaller than `previous`
+        next: u64,
+    },
+    /// Two consecutive Fibonacci indices were used, which the Zeckendorf theorem forbids.
+    ConsecutiveIndices {
+        /// The earlier, larger index in the list
+        previous: u64,
+        /// The later index in the list, which is exactly `previous - 1`
+        next: u64,
+    },
+}
+
+impl std::fmt::Display for ZeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ZeckError::InvalidFibonacciIndex { fi } => {
+                write!(
+                    f,
+                    "Invalid Fibonacci index {}: Zeckendorf representations only use indices of 2 or more",
+                    fi
+                )
+            }
+            ZeckError::NotDescending { previous, next } => {
+                write!(
+                    f,
+                    "Fibonacci indices are not descending: {} is followed by {}",
+                    previous, next
+                )
+            }
+            ZeckError::ConsecutiveIndices { previous, next } => {
+                write!(
+                    f,
+                    "Consecutive Fibonacci indices {} and {} are not allowed in a Zeckendorf representation",
+                    previous, next
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ZeckError {}
@@ -0,0 +1,183 @@
+//! Entropy coding of EZBA bits, as an optional second compression stage
+//!
+//! An EZBA (Effective Zeckendorf Bits Ascending) is a sequence of runs of [`SKIP_BIT`]s, each ended by a [`USE_BIT`].
+//! Instead of storing every bit, the second stage stores the length of each skip run with an
+//! [Elias gamma code](https://en.wikipedia.org/wiki/Elias_gamma_coding), which spends fewer bits on the short runs
+//! that are most common.
+//!
+//! The encoded bit stream is, with `gamma(n)` being the Elias gamma code of `n >= 1`:
+//! - `gamma(run_count + 1)`: the number of [`USE_BIT`]s
+//! - `gamma(skip_count + 1)` for each run: the number of [`SKIP_BIT`]s before each [`USE_BIT`]
+//! - `gamma(trailing_skip_count + 1)`: the number of [`SKIP_BIT`]s after the last [`USE_BIT`]
+//!
+//! The stream is written with a [`BitWriter`], so the last byte is padded with zero bits.
+
+use crate::bits::{BitReader, BitWriter};
+use crate::{SKIP_BIT, USE_BIT};
+
+/// Encodes EZBA bits (0s and 1s) as Elias gamma coded skip-run lengths, packed into bytes.
+///
+/// Any non-zero bit is treated as a [`USE_BIT`]. Use [`entropy_decode_ezba`] to restore the bits.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{entropy_decode_ezba, entropy_encode_ezba};
+/// let ezba = vec![0, 0, 0, 1, 1, 0, 1];
+/// let encoded = entropy_encode_ezba(&ezba);
+/// assert_eq!(entropy_decode_ezba(&encoded, ezba.len()), Some(ezba));
+/// ```
+pub fn entropy_encode_ezba(bits: &[u8]) -> Vec<u8> {
+    let mut skip_runs = Vec::new();
+    let mut skip_count = 0u64;
+    for &bit in bits {
+        if bit == SKIP_BIT {
+            skip_count += 1;
+        } else {
+            skip_runs.push(skip_count);
+            skip_count = 0;
+        }
+    }
+
+    let mut writer = BitWriter::new();
+    write_elias_gamma(&mut writer, skip_runs.len() as u64 + 1);
+    for skip_run in skip_runs {
+        write_elias_gamma(&mut writer, skip_run + 1);
+    }
+    write_elias_gamma(&mut writer, skip_count + 1);
+    writer.finish()
+}
+
+/// Decodes bytes produced by [`entropy_encode_ezba`] back into EZBA bits.
+///
+/// Returns [`None`] if the data is truncated or malformed, if it has anything other than zero padding bits after the
+/// encoded stream, or if the decoded bits would be longer than `max_bits`. The limit guards against small inputs that
+/// decode to huge outputs; pass [`usize::MAX`] for trusted data.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{entropy_decode_ezba, entropy_encode_ezba};
+/// let encoded = entropy_encode_ezba(&[0; 100]);
+/// assert_eq!(entropy_decode_ezba(&encoded, 100), Some(vec![0; 100]));
+/// assert_eq!(entropy_decode_ezba(&encoded, 99), None);
+///
+/// let mut with_extra_byte = encoded.clone();
+/// with_extra_byte.push(0);
+/// assert_eq!(entropy_decode_ezba(&with_extra_byte, 100), None);
+/// ```
+pub fn entropy_decode_ezba(encoded: &[u8], max_bits: usize) -> Option<Vec<u8>> {
+    let mut reader = BitReader::new(encoded);
+
+    let run_count = read_elias_gamma(&mut reader)? - 1;
+    let mut bits = Vec::new();
+    for _ in 0..run_count {
+        let skip_count = read_elias_gamma(&mut reader)? - 1;
+        push_bits_within_limit(&mut bits, SKIP_BIT, skip_count, max_bits)?;
+        push_bits_within_limit(&mut bits, USE_BIT, 1, max_bits)?;
+    }
+    let trailing_skip_count = read_elias_gamma(&mut reader)? - 1;
+    push_bits_within_limit(&mut bits, SKIP_BIT, trailing_skip_count, max_bits)?;
+
+    // Only the zero padding of the last byte may follow the encoded stream
+    if reader.remaining() >= 8 || reader.read_to_end().iter().any(|&bit| bit != 0) {
+        return None;
+    }
+
+    Some(bits)
+}
+
+/// Writes the Elias gamma code of `value`: one zero for every bit after the highest set bit, then the bits of
+/// `value` from most to least significant.
+fn write_elias_gamma(writer: &mut BitWriter, value: u64) {
+    debug_assert!(value >= 1, "Elias gamma codes only encode positive values");
+    let significant_bits = u64::BITS - value.leading_zeros();
+    for _ in 1..significant_bits {
+        writer.write_bit(0);
+    }
+    for bit_index in (0..significant_bits).rev() {
+        writer.write_bit(((value >> bit_index) & 1) as u8);
+    }
+}
+
+/// Appends `count` copies of `bit`, failing instead if that would make `bits` longer than `max_bits`.
+fn push_bits_within_limit(bits: &mut Vec<u8>, bit: u8, count: u64, max_bits: usize) -> Option<()> {
+    let count = usize::try_from(count).ok()?;
+    if count > max_bits.saturating_sub(bits.len()) {
+        return None;
+    }
+    bits.extend(std::iter::repeat_n(bit, count));
+    Some(())
+}
+
+/// Reads the next Elias gamma code, returning [`None`] if the bits run out or the value does not fit in a [`u64`].
+fn read_elias_gamma(reader: &mut BitReader) -> Option<u64> {
+    let mut leading_zeros = 0u32;
+    while reader.read_bit()? == 0 {
+        leading_zeros += 1;
+    }
+    if leading_zeros >= u64::BITS {
+        return None;
+    }
+
+    // The set bit ending the zeros is the highest bit of the value
+    let mut value = 1u64;
+    for _ in 0..leading_zeros {
+        value = (value << 1) | u64::from(reader.read_bit()?);
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{DEFAULT_TEST_DATA_SEED, generate_random_data};
+    use crate::{padless_zeckendorf_compress_be_dangerous, unpack_bytes_to_ezba_bits};
+
+    #[test]
+    fn round_trips_edge_cases() {
+        let cases: [&[u8]; 6] = [&[], &[0], &[1], &[1, 1, 1], &[0, 0, 1, 0, 0], &[0; 9]];
+        for bits in cases {
+            // Act
+            let decoded = entropy_decode_ezba(&entropy_encode_ezba(bits), usize::MAX);
+
+            // Assert
+            assert_eq!(decoded.as_deref(), Some(bits));
+        }
+    }
+
+    #[test]
+    fn round_trips_compressed_random_data() {
+        // Arrange
+        let data = generate_random_data(256, DEFAULT_TEST_DATA_SEED);
+        let ezba = unpack_bytes_to_ezba_bits(&padless_zeckendorf_compress_be_dangerous(&data));
+
+        // Act
+        let decoded = entropy_decode_ezba(&entropy_encode_ezba(&ezba), ezba.len());
+
+        // Assert
+        assert_eq!(decoded, Some(ezba));
+    }
+
+    #[test]
+    fn rejects_truncated_and_oversized_codes() {
+        // Arrange
+        let encoded = entropy_encode_ezba(&[0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        // 63 zero bits, then the set bit and 63 value bits: the largest code that fits in a u64
+        let mut largest = vec![0; 7];
+        largest.extend([0x80, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        // 64 zero bits, then the set bit and enough bits after it that the code does not run out
+        let mut oversized = vec![0; 8];
+        oversized.extend([0xFF; 9]);
+
+        // Act
+        let truncated = entropy_decode_ezba(&encoded[..encoded.len() - 1], usize::MAX);
+        let largest_value = read_elias_gamma(&mut BitReader::new(&largest));
+        let too_many_leading_zeros = read_elias_gamma(&mut BitReader::new(&oversized));
+
+        // Assert
+        assert_eq!(truncated, None);
+        assert_eq!(largest_value, Some(u64::MAX));
+        assert_eq!(too_many_leading_zeros, None);
+    }
+}
@@ -25,15 +25,14 @@
 //!
 //! After installation, `zeck-compress` and `zeck-decompress` will be available in your PATH.
 //!
-//! The compression tool automatically uses `.zbe` extension for big-endian compression and `.zle`
-//! extension for little-endian compression. The decompression tool automatically detects endianness
-//! from these file extensions.
+//! The compression tool writes `.zeck` files, whose header records the endianness and original size. The
+//! decompression tool reads the endianness from that header, so no flag is needed.
 //!
 //! ### zeck-compress
 //!
 //! Compresses data using the Zeckendorf representation algorithm. Supports reading from files or stdin,
 //! writing to files or stdout, and choosing between big-endian, little-endian, or automatic best compression.
-//! Automatically adds the appropriate file extension (`.zbe` or `.zle`) based on the endianness used.
+//! Automatically adds the `.zeck` extension.
 //!
 //! When using `--endian best`, if neither compression method produces a smaller output, the tool will
 //! exit with an error showing compression statistics. When writing to a file, the output filename is
@@ -42,7 +41,7 @@
 //! ```bash
 //! # Compress a file (output filename automatically created from input with extension)
 //! zeck-compress input.bin
-//! # Creates input.bin.zbe or input.bin.zle depending on which endianness was used
+//! # Creates input.bin.zeck
 //!
 //! # Compress with best endianness (statistics shown by default)
 //! zeck-compress input.bin --endian best
@@ -54,39 +53,71 @@
 //! ### zeck-decompress
 //!
 //! Decompresses data that was compressed using the Zeckendorf representation algorithm. Supports reading
-//! from files or stdin, writing to files or stdout. Automatically detects endianness from file extension
-//! (`.zbe` for big-endian, `.zle` for little-endian), but allows manual override with the `--endian` flag.
+//! from files or stdin, writing to files or stdout. Automatically detects endianness from the `.zeck` file header.
+//! Raw legacy files without a header take their endianness from the `.zbe` (big-endian) or `.zle` (little-endian)
+//! extension, or from the `--endian` flag.
 //!
 //! ```bash
-//! # Decompress a file (endianness detected from .zbe extension, output filename automatically created)
-//! zeck-decompress input.zbe
-//! # Automatically uses big-endian decompression, creates output file "input"
+//! # Decompress a file (endianness detected from the header, output filename automatically created)
+//! zeck-decompress input.zeck
+//! # Creates output file "input"
 //!
 //! # Decompress to a specific output file
-//! zeck-decompress input.zbe -o output.bin
-//! # Automatically uses big-endian decompression
+//! zeck-decompress input.zeck -o output.bin
 //!
-//! # Override automatic detection
-//! zeck-decompress input.zbe --endian little -o output.bin
+//! # Decompress from stdin to stdout
+//! cat input.zeck | zeck-decompress
 //!
-//! # Decompress from stdin to stdout (--endian is required)
+//! # Decompress a raw legacy file from stdin (--endian is required)
 //! cat input.zbe | zeck-decompress --endian big
 //! ```
 
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
-use std::collections::HashMap;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
 use std::sync::{Arc, LazyLock, RwLock};
 use wasm_bindgen::prelude::*;
 
+pub mod bits;
+pub mod endianness;
+pub mod error;
+pub mod ezba_entropy;
+pub mod lucas;
+pub mod stats;
+pub mod test_data;
 pub mod zeck_file_format;
 
 #[cfg(feature = "research")]
 pub mod research;
 
+pub use bits::{BitReader, BitWriter};
+pub use endianness::{EndianChoice, Endianness, ParseEndiannessError};
+pub use error::ZeckError;
+pub use ezba_entropy::{entropy_decode_ezba, entropy_encode_ezba};
+pub use lucas::{
+    fast_doubling_lucas_biguint, lucas_compress_be, lucas_compress_le, lucas_decompress_be,
+    lucas_decompress_le,
+};
+#[cfg(feature = "tokio")]
+pub use zeck_file_format::async_tasks::{compress_zeck_be_async, decompress_zeck_file_async};
 pub use zeck_file_format::{
-    ZeckFile, ZeckFormatError, compress::compress_zeck_be, compress::compress_zeck_best,
-    compress::compress_zeck_le, decompress::decompress_zeck_file, file::deserialize_zeck_file,
+    ZeckFile, ZeckFileView, ZeckFormatError, compress::compress_to_zeck_bytes,
+    compress::compress_zeck, compress::compress_zeck_adaptive, compress::compress_zeck_be,
+    compress::compress_zeck_best, compress::compress_zeck_delta,
+    compress::compress_zeck_entropy_coded, compress::compress_zeck_le,
+    compress::compress_zeck_lucas, compress::compress_zeck_sparse,
+    compress::exact_compression_preview, compress::zeckendorf_compress_be_from_iter,
+    compress::zeckendorf_compress_le_from_iter, compress::zeckendorf_compress_u64_slice,
+    decompress::decompress_from_zeck_bytes, decompress::decompress_to_u64_vec,
+    decompress::decompress_view, decompress::decompress_zeck_file,
+    decompress::decompress_zeck_file_chunked, decompress::peek_original_size,
+    file::deserialize_zeck_file,
 };
 
 /// Golden ratio constant.
@@ -97,6 +128,11 @@ pub const PHI: f64 = 1.618033988749894848204586834365638118_f64;
 /// This also equals the golden ratio plus one.
 pub const PHI_SQUARED: f64 = 2.618033988749894848204586834365638118_f64;
 
+/// The largest Fibonacci index whose Fibonacci number fits in a [`u64`].
+/// F(93) = 12,200,160,415,121,876,738 fits, but F(94) overflows, so every [`u64`] has a Zeckendorf representation
+/// using indices up to and including this one.
+pub const MAX_U64_FIBONACCI_INDEX: u64 = 93;
+
 /// Returns the number of bits required to represent the given number. Returns 0 if the number is less than or equal to 0.
 ///
 /// # Examples
@@ -117,12 +153,37 @@ pub fn bit_count_for_number(n: i32) -> u32 {
     32 - n.leading_zeros()
 }
 
+/// Returns the number of bits required to represent the given [`u64`]. Returns 0 for 0.
+///
+/// Unlike [`bit_count_for_number`], this covers the full [`u64`] range, so values above 2^31 are not truncated.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::bit_count_for_u64;
+/// assert_eq!(bit_count_for_u64(0), 0);
+/// assert_eq!(bit_count_for_u64(1), 1);  // 0b1
+/// assert_eq!(bit_count_for_u64(4), 3);  // 0b100
+/// assert_eq!(bit_count_for_u64(1 << 32), 33);
+/// assert_eq!(bit_count_for_u64((1 << 40) - 1), 40);
+/// assert_eq!(bit_count_for_u64(u64::MAX), 64);
+/// ```
+#[wasm_bindgen]
+pub fn bit_count_for_u64(n: u64) -> u32 {
+    u64::BITS - n.leading_zeros()
+}
+
 // Memoization maps for Fibonacci numbers
 static FIBONACCI_CACHE: LazyLock<RwLock<Vec<u64>>> = LazyLock::new(|| RwLock::new(vec![0, 1]));
 
 static FIBONACCI_BIGUINT_CACHE: LazyLock<RwLock<Vec<Arc<BigUint>>>> =
     LazyLock::new(|| RwLock::new(vec![Arc::new(BigUint::zero()), Arc::new(BigUint::one())]));
 
+/// Sorted `(fi, bit length)` pairs for every Fibonacci index from 0 up to the largest one searched so far, see
+/// [`smallest_fibonacci_index_at_least_by_bit_length`].
+static FIBONACCI_BIT_LENGTH_CACHE: LazyLock<RwLock<Vec<(u64, u64)>>> =
+    LazyLock::new(|| RwLock::new(vec![(0, 0), (1, 1)]));
+
 /// Memoization maps for Zeckendorf representations
 static ZECKENDORF_MAP: LazyLock<RwLock<HashMap<u64, Vec<u64>>>> =
     LazyLock::new(|| RwLock::new(HashMap::new()));
@@ -145,35 +206,95 @@ pub static FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE: LazyLock<RwLock<HashMap<u64, A
         RwLock::new(map)
     });
 
-/// fibonacci(x) is equal to 0 if x is 0; 1 if x is 1; else return fibonacci(x - 1) + fibonacci(x - 2)
+/// The packed EZBA of every single byte value, built on first use, so [`compress_single_byte`] is a table lookup.
+static SINGLE_BYTE_COMPRESSION_TABLE: LazyLock<[Vec<u8>; 256]> = LazyLock::new(|| {
+    std::array::from_fn(|byte| {
+        let zld = memoized_zeckendorf_list_descending_for_integer(byte as u64);
+        EzbaBits::from_ezld(&zl_to_ezl(&zld)).into_packed_bytes()
+    })
+});
+
+/// Empties every global memoization cache, resetting the Fibonacci caches to their initial `[0, 1]` base cases.
+///
+/// This is primarily for tests, which can otherwise observe cache entries left behind by earlier calls, and for
+/// long-running programs that want to reclaim the memory held by the caches. The caches are rebuilt on demand,
+/// so clearing them never changes any results, only how long the next calls take.
 ///
-/// This function is slow and should not be used for large numbers. If you want a [`u64`] result, use the faster [`memoized_slow_fibonacci_biguint_iterative`] function instead. If you want a [`BigUint`] result, use the [`fast_doubling_fibonacci_biguint`] function instead.
+/// # Examples
+///
+/// ```
+/// # use zeck::{FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE, clear_all_caches, memoized_fast_doubling_fibonacci_biguint};
+/// # use num_bigint::BigUint;
+/// memoized_fast_doubling_fibonacci_biguint(100);
+/// clear_all_caches();
+/// assert_eq!(*memoized_fast_doubling_fibonacci_biguint(10), BigUint::from(55u64));
+/// ```
+pub fn clear_all_caches() {
+    *FIBONACCI_CACHE
+        .write()
+        .expect("Failed to write Fibonacci cache") = vec![0, 1];
+    *FIBONACCI_BIGUINT_CACHE
+        .write()
+        .expect("Failed to write Fibonacci BigUint cache") =
+        vec![Arc::new(BigUint::zero()), Arc::new(BigUint::one())];
+    *FIBONACCI_BIT_LENGTH_CACHE
+        .write()
+        .expect("Failed to write Fibonacci bit length cache") = vec![(0, 0), (1, 1)];
+    ZECKENDORF_MAP
+        .write()
+        .expect("Failed to write Zeckendorf map")
+        .clear();
+    ZECKENDORF_BIGUINT_MAP
+        .write()
+        .expect("Failed to write Zeckendorf BigUint map")
+        .clear();
+
+    let mut fast_doubling_cache = FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE
+        .write()
+        .expect("Failed to write fast doubling Fibonacci cache");
+    fast_doubling_cache.clear();
+    fast_doubling_cache.insert(0, Arc::new(BigUint::zero()));
+    fast_doubling_cache.insert(1, Arc::new(BigUint::one()));
+}
+
+/// fibonacci(x) is equal to 0 if x is 0; 1 if x is 1; else return fibonacci(x - 1) + fibonacci(x - 2)
 ///
-/// This function fails for large numbers (e.g. 100_000) with a stack overflow error.
+/// The values are computed iteratively and memoized in a [`Vec`], so the first call for an index fills in every
+/// smaller index, and later calls are a lookup. Every [`u64`] Fibonacci number fits in the cache, so this never
+/// recurses or takes more than [`MAX_U64_FIBONACCI_INDEX`] additions. If you want a [`BigUint`] result, use the
+/// [`fast_doubling_fibonacci_biguint`] function instead.
 ///
 /// `fi` stands for Fibonacci Index.
 ///
+/// # Panics
+///
+/// Panics if `fi` is above [`MAX_U64_FIBONACCI_INDEX`], because F(94) and above overflow [`u64`].
+///
 /// # Examples
 ///
 /// ```
-/// # use zeck::memoized_slow_fibonacci_recursive;
+/// # use zeck::memoized_fibonacci_u64;
 /// // Base cases
-/// assert_eq!(memoized_slow_fibonacci_recursive(0), 0);
-/// assert_eq!(memoized_slow_fibonacci_recursive(1), 1);
+/// assert_eq!(memoized_fibonacci_u64(0), 0);
+/// assert_eq!(memoized_fibonacci_u64(1), 1);
 ///
 /// // Small Fibonacci numbers
-/// assert_eq!(memoized_slow_fibonacci_recursive(2), 1);
-/// assert_eq!(memoized_slow_fibonacci_recursive(3), 2);
-/// assert_eq!(memoized_slow_fibonacci_recursive(4), 3);
-/// assert_eq!(memoized_slow_fibonacci_recursive(5), 5);
-/// assert_eq!(memoized_slow_fibonacci_recursive(6), 8);
-/// assert_eq!(memoized_slow_fibonacci_recursive(7), 13);
-/// assert_eq!(memoized_slow_fibonacci_recursive(8), 21);
-/// assert_eq!(memoized_slow_fibonacci_recursive(9), 34);
-/// assert_eq!(memoized_slow_fibonacci_recursive(10), 55);
+/// assert_eq!(memoized_fibonacci_u64(2), 1);
+/// assert_eq!(memoized_fibonacci_u64(3), 2);
+/// assert_eq!(memoized_fibonacci_u64(4), 3);
+/// assert_eq!(memoized_fibonacci_u64(5), 5);
+/// assert_eq!(memoized_fibonacci_u64(6), 8);
+/// assert_eq!(memoized_fibonacci_u64(7), 13);
+/// assert_eq!(memoized_fibonacci_u64(8), 21);
+/// assert_eq!(memoized_fibonacci_u64(9), 34);
+/// assert_eq!(memoized_fibonacci_u64(10), 55);
 /// ```
 #[wasm_bindgen]
-pub fn memoized_slow_fibonacci_recursive(fi: u64) -> u64 {
+pub fn memoized_fibonacci_u64(fi: u64) -> u64 {
+    // Fibonacci numbers above index 93 will overflow u64. Check before taking the lock so the panic cannot poison it.
+    if fi > MAX_U64_FIBONACCI_INDEX {
+        panic!("Fibonacci index {} overflows u64", fi);
+    }
     let fi = fi as usize;
 
     // Try to get the value with a read lock first
@@ -194,10 +315,6 @@ pub fn memoized_slow_fibonacci_recursive(fi: u64) -> u64 {
     // Re-check in case another thread updated it while we were waiting for the write lock
     while fibonacci_cache.len() <= fi {
         let fibonacci_cache_length = fibonacci_cache.len();
-        // Fibonacci numbers above index 93 will overflow u64
-        if fibonacci_cache_length > 93 {
-            panic!("Fibonacci index {} overflows u64", fibonacci_cache_length);
-        }
         let next_fibonacci_value = fibonacci_cache[fibonacci_cache_length - 1]
             + fibonacci_cache[fibonacci_cache_length - 2];
         fibonacci_cache.push(next_fibonacci_value);
@@ -206,6 +323,12 @@ pub fn memoized_slow_fibonacci_recursive(fi: u64) -> u64 {
     fibonacci_cache[fi]
 }
 
+/// The former name of [`memoized_fibonacci_u64`], which was never recursive.
+#[deprecated(note = "the function is iterative, not recursive; use memoized_fibonacci_u64 instead")]
+pub fn memoized_slow_fibonacci_recursive(fi: u64) -> u64 {
+    memoized_fibonacci_u64(fi)
+}
+
 /// fibonacci(x) is equal to 0 if x is 0; 1 if x is 1; else return fibonacci(x - 1) + fibonacci(x - 2)
 /// fi stands for Fibonacci Index
 ///
@@ -248,6 +371,11 @@ pub fn memoized_slow_fibonacci_biguint_iterative(fi: u64) -> Arc<BigUint> {
     }
 
     // If not found, get a write lock to update the cache
+    extend_fibonacci_biguint_cache(fi)
+}
+
+/// Extends the contiguous Fibonacci cache through index `fi` and returns the value at `fi`.
+fn extend_fibonacci_biguint_cache(fi: usize) -> Arc<BigUint> {
     let mut fibonacci_cache = FIBONACCI_BIGUINT_CACHE
         .write()
         .expect("Failed to write Fibonacci BigUint cache");
@@ -264,6 +392,113 @@ pub fn memoized_slow_fibonacci_biguint_iterative(fi: u64) -> Arc<BigUint> {
     Arc::clone(&fibonacci_cache[fi])
 }
 
+/// Returns an infinite iterator over the Fibonacci numbers F(0), F(1), F(2), ...
+///
+/// The values come from [`memoized_slow_fibonacci_biguint_iterative`], so they share its global cache: the first
+/// iteration extends the cache one value at a time, and iterating again only clones the cached [`Arc`]s.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::fibonacci_iter;
+/// # use num_bigint::BigUint;
+/// // The index of the first Fibonacci number with at least 100 bits
+/// let index = fibonacci_iter().position(|fibonacci| fibonacci.bits() >= 100);
+/// assert_eq!(index, Some(145));
+/// assert_eq!(*fibonacci_iter().nth(10).unwrap(), BigUint::from(55u64));
+/// ```
+pub fn fibonacci_iter() -> impl Iterator<Item = Arc<BigUint>> {
+    (0u64..).map(memoized_slow_fibonacci_biguint_iterative)
+}
+
+/// Saves the Fibonacci [`BigUint`] cache used by [`memoized_slow_fibonacci_biguint_iterative`] to a file, so a long-running
+/// pipeline can restore the warmed cache with [`load_fibonacci_cache`] on its next start instead of rebuilding it.
+///
+/// The file is a sequence of entries, in Fibonacci index order, where each entry is a [`u32`] little endian byte length
+/// followed by that many bytes of the Fibonacci value in big endian order.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{load_fibonacci_cache, memoized_slow_fibonacci_biguint_iterative, save_fibonacci_cache};
+/// # use num_bigint::BigUint;
+/// let path = std::env::temp_dir().join("zeck_doctest_fibonacci_cache.bin");
+/// memoized_slow_fibonacci_biguint_iterative(100);
+/// save_fibonacci_cache(&path).unwrap();
+/// load_fibonacci_cache(&path).unwrap();
+/// assert_eq!(*memoized_slow_fibonacci_biguint_iterative(10), BigUint::from(55u64));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn save_fibonacci_cache(path: impl AsRef<Path>) -> io::Result<()> {
+    let fibonacci_cache = FIBONACCI_BIGUINT_CACHE
+        .read()
+        .expect("Failed to read Fibonacci BigUint cache");
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    for fibonacci_value in fibonacci_cache.iter() {
+        let fibonacci_value_bytes = fibonacci_value.to_bytes_be();
+        let fibonacci_value_len = u32::try_from(fibonacci_value_bytes.len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Fibonacci value is too large to save",
+            )
+        })?;
+        writer.write_all(&fibonacci_value_len.to_le_bytes())?;
+        writer.write_all(&fibonacci_value_bytes)?;
+    }
+    writer.flush()
+}
+
+/// Loads a Fibonacci [`BigUint`] cache saved by [`save_fibonacci_cache`] into the cache used by [`memoized_slow_fibonacci_biguint_iterative`].
+///
+/// The current cache is only replaced if the loaded cache is longer, so loading never discards warmed values.
+///
+/// The file is trusted: only the base cases and the last entry are checked against the Fibonacci recurrence, because
+/// checking every entry would cost as much as rebuilding the cache. Returns an [`io::ErrorKind::InvalidData`] error if
+/// the file is truncated or fails those checks.
+pub fn load_fibonacci_cache(path: impl AsRef<Path>) -> io::Result<()> {
+    let bytes = std::fs::read(path)?;
+    let invalid_data =
+        |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    let mut loaded_cache = Vec::new();
+    let mut remaining = bytes.as_slice();
+    while !remaining.is_empty() {
+        let Some((len_bytes, rest)) = remaining.split_first_chunk::<4>() else {
+            return Err(invalid_data("Truncated Fibonacci cache entry length"));
+        };
+        let fibonacci_value_len = u32::from_le_bytes(*len_bytes) as usize;
+        if rest.len() < fibonacci_value_len {
+            return Err(invalid_data("Truncated Fibonacci cache entry value"));
+        }
+        let (fibonacci_value_bytes, rest) = rest.split_at(fibonacci_value_len);
+        loaded_cache.push(Arc::new(BigUint::from_bytes_be(fibonacci_value_bytes)));
+        remaining = rest;
+    }
+
+    if loaded_cache.len() < 2 || !loaded_cache[0].is_zero() || !loaded_cache[1].is_one() {
+        return Err(invalid_data("Fibonacci cache must start with 0 and 1"));
+    }
+    let last_index = loaded_cache.len() - 1;
+    if last_index >= 2
+        && *loaded_cache[last_index]
+            != &*loaded_cache[last_index - 1] + &*loaded_cache[last_index - 2]
+    {
+        return Err(invalid_data(
+            "Fibonacci cache does not follow the Fibonacci recurrence",
+        ));
+    }
+
+    let mut fibonacci_cache = FIBONACCI_BIGUINT_CACHE
+        .write()
+        .expect("Failed to write Fibonacci BigUint cache");
+    if loaded_cache.len() > fibonacci_cache.len() {
+        *fibonacci_cache = loaded_cache;
+    }
+
+    Ok(())
+}
+
 /// fibonacci(x) is equal to 0 if x is 0; 1 if x is 1; else return fibonacci(x - 1) + fibonacci(x - 2)
 /// fi stands for Fibonacci Index
 ///
@@ -418,6 +653,129 @@ pub fn memoized_fast_doubling_fibonacci_biguint(fi: u64) -> Arc<BigUint> {
     }
 
     // If not found, calculate using fast doubling and cache intermediate values
+    let (result, values_to_cache) = fast_doubling_fibonacci_biguint_with_intermediates(fi);
+
+    let mut cache = FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE
+        .write()
+        .expect("Failed to write fast doubling Fibonacci cache");
+
+    // Re-check the final value in case another thread updated it while we were computing
+    if let Some(cached_value) = cache.get(&fi) {
+        return Arc::clone(cached_value);
+    }
+
+    // Insert all values that aren't already cached
+    for (fi, value) in values_to_cache {
+        cache.entry(fi).or_insert(value);
+    }
+
+    result
+}
+
+/// The largest index [`fibonacci`] extends the contiguous cache of [`memoized_slow_fibonacci_biguint_iterative`] to.
+///
+/// The cache holds every Fibonacci number below its length, so its memory grows with the square of the index. Uncached
+/// indices at or above this always use [`memoized_fast_doubling_fibonacci_biguint`].
+pub const FIBONACCI_CONTIGUOUS_CACHE_LIMIT: u64 = 4096;
+
+/// How far past the end of the contiguous cache [`fibonacci`] extends it one addition at a time before preferring fast
+/// doubling.
+///
+/// Each addition is cheaper than a fast doubling call, but fast doubling reaches a distant index in O(log fi) steps.
+pub const FIBONACCI_CONTIGUOUS_CACHE_MAX_EXTENSION: u64 = 64;
+
+/// Returns the Fibonacci number at `fi`, picking the faster of the two memoized algorithms for the index and the
+/// current cache state.
+///
+/// Values already in the contiguous cache of [`memoized_slow_fibonacci_biguint_iterative`] are returned from it. An
+/// uncached index below [`FIBONACCI_CONTIGUOUS_CACHE_LIMIT`] and within [`FIBONACCI_CONTIGUOUS_CACHE_MAX_EXTENSION`]
+/// of the end of that cache extends it, which suits small and sequential lookups. Any other index goes to the sparse
+/// cache of [`memoized_fast_doubling_fibonacci_biguint`], which reaches it without filling in every smaller value.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{fibonacci, memoized_slow_fibonacci_biguint_iterative};
+/// # use num_bigint::BigUint;
+/// assert_eq!(*fibonacci(10), BigUint::from(55u64));
+/// assert_eq!(fibonacci(100_000), memoized_slow_fibonacci_biguint_iterative(100_000));
+/// ```
+pub fn fibonacci(fi: u64) -> Arc<BigUint> {
+    let contiguous_cache_len = {
+        let fibonacci_cache = FIBONACCI_BIGUINT_CACHE
+            .read()
+            .expect("Failed to read Fibonacci BigUint cache");
+        // An index that does not fit in usize, as on 32-bit targets, cannot be cached contiguously
+        if let Some(fibonacci_value) = usize::try_from(fi)
+            .ok()
+            .and_then(|fi| fibonacci_cache.get(fi))
+        {
+            return Arc::clone(fibonacci_value);
+        }
+        fibonacci_cache.len() as u64
+    };
+
+    if fi < FIBONACCI_CONTIGUOUS_CACHE_LIMIT
+        && fi - contiguous_cache_len < FIBONACCI_CONTIGUOUS_CACHE_MAX_EXTENSION
+    {
+        extend_fibonacci_biguint_cache(fi as usize)
+    } else {
+        memoized_fast_doubling_fibonacci_biguint(fi)
+    }
+}
+
+/// Returns the Fibonacci index of `n` if `n` is a Fibonacci number, or [`None`] otherwise.
+///
+/// Since F(1) and F(2) are both 1, 1 returns index 2, the index the Zeckendorf representation uses for it. 0 returns
+/// index 0. The index is estimated from the bit length of `n` and then checked with [`fast_doubling_fibonacci_biguint`],
+/// so this takes a few Fibonacci calculations instead of a walk up through every smaller Fibonacci number.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::fibonacci_index_of;
+/// # use num_bigint::BigUint;
+/// assert_eq!(fibonacci_index_of(&BigUint::from(0u64)), Some(0));
+/// assert_eq!(fibonacci_index_of(&BigUint::from(1u64)), Some(2));
+/// assert_eq!(fibonacci_index_of(&BigUint::from(55u64)), Some(10));
+/// assert_eq!(fibonacci_index_of(&BigUint::from(56u64)), None);
+/// ```
+pub fn fibonacci_index_of(n: &BigUint) -> Option<u64> {
+    if n.is_zero() {
+        return Some(0);
+    }
+    if n.is_one() {
+        return Some(2);
+    }
+
+    // The estimate from the bit length can be off by a few indices, which the search below corrects.
+    let estimated_fi = estimated_fibonacci_index((n.bits() - 1) as f64).max(3.0) as u64;
+
+    let mut fi = estimated_fi;
+    let mut fibonacci = fast_doubling_fibonacci_biguint(fi);
+    while *fibonacci > *n && fi > 3 {
+        fi -= 1;
+        fibonacci = fast_doubling_fibonacci_biguint(fi);
+    }
+    while *fibonacci < *n {
+        fi += 1;
+        fibonacci = fast_doubling_fibonacci_biguint(fi);
+    }
+    (*fibonacci == *n).then_some(fi)
+}
+
+/// Estimates the real Fibonacci index whose Fibonacci value is `2^log2` with Binet's formula.
+///
+/// F(i) is the nearest integer to PHI^i / sqrt(5), so i is about (log2 * ln(2) + ln(sqrt(5))) / ln(PHI).
+fn estimated_fibonacci_index(log2: f64) -> f64 {
+    (log2 * std::f64::consts::LN_2 + 5f64.sqrt().ln()) / PHI.ln()
+}
+
+/// Computes the Fibonacci number at `fi` with fast doubling, also returning every intermediate `(fi, value)` pair
+/// that [`memoized_fast_doubling_fibonacci_biguint`] caches, ending with `fi` itself. Indices may repeat.
+fn fast_doubling_fibonacci_biguint_with_intermediates(
+    fi: u64,
+) -> (Arc<BigUint>, Vec<(u64, Arc<BigUint>)>) {
     // The algorithm maintains (a, b) representing (F(m), F(m+1)) where m is the current index
     // Based on fast doubling identities from https://www.nayuki.io/page/fast-fibonacci-algorithms:
     // F(2k) = F(k) * [2*F(k+1) - F(k)]
@@ -457,25 +815,63 @@ pub fn memoized_fast_doubling_fibonacci_biguint(fi: u64) -> Arc<BigUint> {
         fi_msb >>= 1;
     }
 
-    // Cache all intermediate values and the final result in a single batch write
     let result = Arc::new(a);
     values_to_cache.push((fi, Arc::clone(&result)));
 
-    let mut cache = FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE
-        .write()
-        .expect("Failed to write fast doubling Fibonacci cache");
+    (result, values_to_cache)
+}
 
-    // Re-check the final value in case another thread updated it while we were computing
-    if let Some(cached_value) = cache.get(&fi) {
-        return Arc::clone(cached_value);
-    }
+/// Computes the Fibonacci number at `fi` like [`memoized_fast_doubling_fibonacci_biguint`], without reading or writing
+/// [`FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE`], and returns it together with the number of Fibonacci values the
+/// memoized function would leave in an empty cache, including the `0` and `1` base cases.
+///
+/// This makes the sparseness of the fast doubling cache testable deterministically, regardless of which values earlier
+/// calls have already cached. For `fi > 0` the count is the bit length of `fi` plus the number of its set bits.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::fast_doubling_fibonacci_biguint_isolated;
+/// # use num_bigint::BigUint;
+/// let (fibonacci, cached_count) = fast_doubling_fibonacci_biguint_isolated(100);
+/// assert_eq!(*fibonacci, BigUint::from(354_224_848_179_261_915_075u128));
+/// assert_eq!(cached_count, 10); // 0b1100100 has 7 bits, 3 of which are set
+/// ```
+pub fn fast_doubling_fibonacci_biguint_isolated(fi: u64) -> (Arc<BigUint>, usize) {
+    let (result, values_to_cache) = fast_doubling_fibonacci_biguint_with_intermediates(fi);
 
-    // Insert all values that aren't already cached
-    for (fi, value) in values_to_cache {
-        cache.entry(fi).or_insert(value);
-    }
+    let cached_indices: HashSet<u64> = [0, 1]
+        .into_iter()
+        .chain(values_to_cache.iter().map(|(fi, _)| *fi))
+        .collect();
 
-    result
+    (result, cached_indices.len())
+}
+
+/// Returns a copy of the entries of [`FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE`], sorted by Fibonacci index.
+///
+/// The cache is a [`HashMap`], so iterating it directly gives a different order every run. The snapshot is taken under
+/// a read lock that is released before returning, which makes the cache easy to inspect while debugging without
+/// holding the lock.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{fast_doubling_cache_snapshot, memoized_fast_doubling_fibonacci_biguint};
+/// memoized_fast_doubling_fibonacci_biguint(100);
+/// let snapshot = fast_doubling_cache_snapshot();
+/// assert!(snapshot.windows(2).all(|pair| pair[0].0 < pair[1].0));
+/// assert!(snapshot.iter().any(|(fi, _)| *fi == 100));
+/// ```
+pub fn fast_doubling_cache_snapshot() -> Vec<(u64, Arc<BigUint>)> {
+    let mut snapshot = FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE
+        .read()
+        .expect("Failed to read fast doubling Fibonacci cache")
+        .iter()
+        .map(|(fi, value)| (*fi, Arc::clone(value)))
+        .collect::<Vec<_>>();
+    snapshot.sort_unstable_by_key(|(fi, _)| *fi);
+    snapshot
 }
 
 /// Returns a [`u64`] value with only the most significant set bit of n preserved.
@@ -509,6 +905,43 @@ pub fn highest_one_bit(n: u64) -> u64 {
     1u64 << (63 - n.leading_zeros())
 }
 
+/// Returns the position of the most significant set bit of n, counting from 0 at the least significant bit, or
+/// [`None`] if n is zero.
+///
+/// Unlike [`highest_one_bit`], which returns the bit itself, this returns its position, since the bit of a [`BigUint`]
+/// can be arbitrarily large. The position is one less than [`BigUint::bits`].
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::highest_one_bit_biguint;
+/// # use num_bigint::BigUint;
+/// assert_eq!(highest_one_bit_biguint(&BigUint::from(0u64)), None);
+/// assert_eq!(highest_one_bit_biguint(&BigUint::from(1u64)), Some(0));
+/// assert_eq!(highest_one_bit_biguint(&BigUint::from(12u64)), Some(3));
+/// assert_eq!(highest_one_bit_biguint(&(BigUint::from(1u64) << 100)), Some(100));
+/// ```
+pub fn highest_one_bit_biguint(n: &BigUint) -> Option<u64> {
+    n.bits().checked_sub(1)
+}
+
+/// Returns the position of the least significant set bit of n, counting from 0 at the least significant bit, or
+/// [`None`] if n is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::lowest_one_bit_biguint;
+/// # use num_bigint::BigUint;
+/// assert_eq!(lowest_one_bit_biguint(&BigUint::from(0u64)), None);
+/// assert_eq!(lowest_one_bit_biguint(&BigUint::from(1u64)), Some(0));
+/// assert_eq!(lowest_one_bit_biguint(&BigUint::from(12u64)), Some(2));
+/// assert_eq!(lowest_one_bit_biguint(&(BigUint::from(3u64) << 100)), Some(100));
+/// ```
+pub fn lowest_one_bit_biguint(n: &BigUint) -> Option<u64> {
+    n.trailing_zeros()
+}
+
 /// A descending Zeckendorf list is a sorted list of unique Fibonacci indices, in descending order, that sum to the given number.
 ///
 /// A Fibonacci index is the index of the Fibonacci number in the Fibonacci sequence.
@@ -561,14 +994,10 @@ pub fn memoized_zeckendorf_list_descending_for_integer(n: u64) -> Vec<u64> {
     let mut low = 1u64;
     let mut high = 1u64;
 
-    // Exponential search for upper bound
-    while memoized_slow_fibonacci_recursive(high) < current_n {
+    // Exponential search for upper bound, capped at the largest index that fits in u64
+    while high < MAX_U64_FIBONACCI_INDEX && memoized_fibonacci_u64(high) < current_n {
         low = high;
-        high *= 2;
-        // Fibonacci numbers above index 93 will overflow u64
-        if high > 93 {
-            panic!("Fibonacci index {} overflows u64", high);
-        }
+        high = (high * 2).min(MAX_U64_FIBONACCI_INDEX);
     }
 
     // Binary search for the smallest index i such that F[i] >= current_n
@@ -578,18 +1007,18 @@ pub fn memoized_zeckendorf_list_descending_for_integer(n: u64) -> Vec<u64> {
             low = 1;
             break;
         }
-        if memoized_slow_fibonacci_recursive(mid) < current_n {
+        if memoized_fibonacci_u64(mid) < current_n {
             low = mid + 1;
         } else {
             high = mid - 1;
         }
     }
-    let mut max_fibonacci_index_smaller_than_n = low;
+    // Values above F(93) have no u64 Fibonacci number >= them, so they start from the largest one that fits
+    let mut max_fibonacci_index_smaller_than_n = low.min(MAX_U64_FIBONACCI_INDEX);
 
     let mut zeckendorf_list: Vec<u64> = Vec::new();
     while current_n > 0 {
-        let current_fibonacci_value =
-            memoized_slow_fibonacci_recursive(max_fibonacci_index_smaller_than_n);
+        let current_fibonacci_value = memoized_fibonacci_u64(max_fibonacci_index_smaller_than_n);
         if current_fibonacci_value > current_n {
             max_fibonacci_index_smaller_than_n -= 1;
             continue;
@@ -656,29 +1085,11 @@ pub fn memoized_zeckendorf_list_descending_for_biguint(n: &BigUint) -> Vec<u64>
 
     let original_n = n.clone();
     let mut current_n = n.clone();
-    let mut low = 1u64;
-    let mut high = 1u64;
-
-    // Exponential search for upper bound
-    while *memoized_slow_fibonacci_biguint_iterative(high) < current_n {
-        low = high;
-        high *= 2;
-    }
-
-    // Binary search for the smallest index i such that F[i] >= current_n
-    while low <= high {
-        let mid = low + (high - low) / 2;
-        if mid == 0 {
-            low = 1;
-            break;
-        }
-        if *memoized_slow_fibonacci_biguint_iterative(mid) < current_n {
-            low = mid + 1;
-        } else {
-            high = mid - 1;
-        }
-    }
-    let mut max_fibonacci_index_smaller_than_n = low;
+    // No Binet bracket is needed here: the loop below looks up every index from the starting one down anyway, so the
+    // Fibonacci cache has to reach it regardless, and the bit length search never caches past the first Fibonacci
+    // number with more bits than n, which is at most two indices beyond the one the loop starts from.
+    let mut max_fibonacci_index_smaller_than_n =
+        smallest_fibonacci_index_at_least_by_bit_length(&current_n);
 
     let mut zeckendorf_list: Vec<u64> = Vec::new();
     while current_n > BigUint::zero() {
@@ -701,6 +1112,95 @@ pub fn memoized_zeckendorf_list_descending_for_biguint(n: &BigUint) -> Vec<u64>
     zeckendorf_list
 }
 
+/// Returns the descending Zeckendorf list for the given bytes interpreted as a big endian integer.
+///
+/// This is a convenience wrapper around [`memoized_zeckendorf_list_descending_for_biguint`] matching the byte-oriented
+/// interface of [`padless_zeckendorf_compress_be_dangerous`].
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeckendorf_list_descending_for_bytes_be;
+/// assert_eq!(zeckendorf_list_descending_for_bytes_be(&[]), vec![] as Vec<u64>);
+/// assert_eq!(zeckendorf_list_descending_for_bytes_be(&[0, 12]), vec![6u64, 4u64, 2u64]); // 12 = 8 + 3 + 1
+/// assert_eq!(zeckendorf_list_descending_for_bytes_be(&[1, 0]), vec![13u64, 8u64, 3u64]); // 256 = 233 + 21 + 2
+/// ```
+#[wasm_bindgen]
+pub fn zeckendorf_list_descending_for_bytes_be(data: &[u8]) -> Vec<u64> {
+    memoized_zeckendorf_list_descending_for_biguint(&BigUint::from_bytes_be(data))
+}
+
+/// Returns the descending Zeckendorf list for the given bytes interpreted as a little endian integer.
+///
+/// This is a convenience wrapper around [`memoized_zeckendorf_list_descending_for_biguint`] matching the byte-oriented
+/// interface of [`padless_zeckendorf_compress_le_dangerous`].
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeckendorf_list_descending_for_bytes_le;
+/// assert_eq!(zeckendorf_list_descending_for_bytes_le(&[12, 0]), vec![6u64, 4u64, 2u64]); // 12 = 8 + 3 + 1
+/// assert_eq!(zeckendorf_list_descending_for_bytes_le(&[0, 1]), vec![13u64, 8u64, 3u64]); // 256 = 233 + 21 + 2
+/// ```
+#[wasm_bindgen]
+pub fn zeckendorf_list_descending_for_bytes_le(data: &[u8]) -> Vec<u64> {
+    memoized_zeckendorf_list_descending_for_biguint(&BigUint::from_bytes_le(data))
+}
+
+/// Counts how often each Fibonacci index appears in the Zeckendorf representations of a corpus of inputs.
+///
+/// Each input is interpreted as a big endian integer, as in [`padless_zeckendorf_compress_be_dangerous`], and every
+/// Fibonacci index in its descending Zeckendorf list is tallied. Each index corresponds to one [`USE_BIT`] in the
+/// compressed output, so the tallies sum to the number of set bits across all compressed inputs.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::fibonacci_index_histogram;
+/// // 12 = F(6) + F(4) + F(2) and 3 = F(4)
+/// let histogram = fibonacci_index_histogram(&[vec![12], vec![3]]);
+/// assert_eq!(histogram.get(&4), Some(&2));
+/// assert_eq!(histogram.get(&6), Some(&1));
+/// assert_eq!(histogram.get(&5), None);
+/// ```
+pub fn fibonacci_index_histogram(inputs: &[Vec<u8>]) -> HashMap<u64, u64> {
+    let mut histogram = HashMap::new();
+    for input in inputs {
+        for fibonacci_index in zeckendorf_list_descending_for_bytes_be(input) {
+            *histogram.entry(fibonacci_index).or_insert(0) += 1;
+        }
+    }
+    histogram
+}
+
+/// Returns the fraction of the bits in the Effective Zeckendorf Bits Ascending (EZBA) representation of `n` that are
+/// [`USE_BIT`]s, from 0.0 for zero (a single [`SKIP_BIT`]) up to 1.0 for an all ones Zeckendorf number
+/// (see [`all_ones_zeckendorf_to_biguint`]).
+///
+/// Lower density means a sparser representation, which tends to compress better after a second entropy coding stage
+/// (see [`ezba_entropy`]). The bits are counted the same way as in [`compressed_byte_length`], without building the EZBA.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{all_ones_zeckendorf_to_biguint, zeckendorf_density};
+/// # use num_bigint::BigUint;
+/// assert_eq!(zeckendorf_density(&all_ones_zeckendorf_to_biguint(10)), 1.0);
+/// assert_eq!(zeckendorf_density(&BigUint::from(8u64)), 0.2); // F(6) has EZBA bits [0, 0, 0, 0, 1]
+/// assert_eq!(zeckendorf_density(&BigUint::from(0u64)), 0.0);
+/// ```
+pub fn zeckendorf_density(n: &BigUint) -> f64 {
+    let zeckendorf_list = memoized_zeckendorf_list_descending_for_biguint(n);
+    let Some(&max_fi) = zeckendorf_list.first() else {
+        // Zero is a single skip bit
+        return 0.0;
+    };
+
+    let use_bit_count = zeckendorf_list.len();
+    let ezba_bit_count = fi_to_efi(max_fi) as usize + 2 - use_bit_count;
+    use_bit_count as f64 / ezba_bit_count as f64
+}
+
 /// Bit flag indicating that an effective Fibonacci index (EFI) should be used in the Zeckendorf representation.
 ///
 /// When this bit is set in an Effective Zeckendorf Bits Ascending (EZBA) sequence, it means the corresponding
@@ -746,6 +1246,88 @@ pub enum PadlessCompressionResult {
     },
 }
 
+impl PadlessCompressionResult {
+    /// Returns the length of the best compressed data, or [`None`] if neither interpretation compressed the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::PadlessCompressionResult;
+    /// let result = PadlessCompressionResult::BigEndianBest { compressed_data: vec![1, 2], le_size: 3 };
+    /// assert_eq!(result.compressed_len(), Some(2));
+    /// let result = PadlessCompressionResult::Neither { be_size: 5, le_size: 6 };
+    /// assert_eq!(result.compressed_len(), None);
+    /// ```
+    pub fn compressed_len(&self) -> Option<usize> {
+        match self {
+            PadlessCompressionResult::BigEndianBest {
+                compressed_data, ..
+            }
+            | PadlessCompressionResult::LittleEndianBest {
+                compressed_data, ..
+            } => Some(compressed_data.len()),
+            PadlessCompressionResult::Neither { .. } => None,
+        }
+    }
+
+    /// Returns the compressed data, or borrows `original` if neither interpretation compressed it.
+    ///
+    /// This is for callers that store incompressible data uncompressed: the fallback borrows the input instead of
+    /// copying it, so the common case of random data that does not compress allocates nothing. `original` must be the
+    /// data that was compressed, and the caller must record which [`Cow`] variant was returned to know whether to
+    /// decompress it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use zeck::PadlessCompressionResult;
+    /// let original = [7, 8, 9];
+    /// let result = PadlessCompressionResult::Neither { be_size: 4, le_size: 4 };
+    /// assert!(matches!(result.into_compressed_or_original(&original), Cow::Borrowed(&[7, 8, 9])));
+    /// let result = PadlessCompressionResult::BigEndianBest { compressed_data: vec![1, 2], le_size: 3 };
+    /// assert_eq!(result.into_compressed_or_original(&original), Cow::<[u8]>::Owned(vec![1, 2]));
+    /// ```
+    pub fn into_compressed_or_original(self, original: &[u8]) -> Cow<'_, [u8]> {
+        match self {
+            PadlessCompressionResult::BigEndianBest {
+                compressed_data, ..
+            }
+            | PadlessCompressionResult::LittleEndianBest {
+                compressed_data, ..
+            } => Cow::Owned(compressed_data),
+            PadlessCompressionResult::Neither { .. } => Cow::Borrowed(original),
+        }
+    }
+
+    /// Returns the result with the shortest compressed data, where [`PadlessCompressionResult::Neither`] compares as
+    /// worse than any compressed result. Ties go to the earliest result. Returns [`None`] if `results` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::PadlessCompressionResult;
+    /// let results = [
+    ///     PadlessCompressionResult::Neither { be_size: 5, le_size: 6 },
+    ///     PadlessCompressionResult::LittleEndianBest { compressed_data: vec![1], be_size: 2 },
+    /// ];
+    /// assert_eq!(PadlessCompressionResult::best_of(&results), Some(&results[1]));
+    /// ```
+    pub fn best_of(results: &[Self]) -> Option<&Self> {
+        results
+            .iter()
+            .min_by_key(|result| compressed_len_sort_key(result.compressed_len()))
+    }
+}
+
+/// Sort key that orders compressed lengths ascending, with a missing length (no compression) last.
+pub(crate) fn compressed_len_sort_key(maybe_compressed_len: Option<usize>) -> (bool, usize) {
+    match maybe_compressed_len {
+        Some(compressed_len) => (false, compressed_len),
+        None => (true, 0),
+    }
+}
+
 /// Effective Fibonacci Index to Fibonacci Index: FI(efi) === efi + 2, where efi is the Effective Fibonacci Index
 ///
 /// # Examples
@@ -793,6 +1375,11 @@ pub fn efi_to_fi_biguint(efi: BigUint) -> BigUint {
 
 /// Fibonacci Index to Effective Fibonacci Index: EFI(fi) === fi - 2, where fi is the Fibonacci Index
 ///
+/// # Panics
+///
+/// Panics if `fi` is less than 2, which has no Effective Fibonacci Index (in release builds the subtraction wraps
+/// instead). Use [`try_fi_to_efi`] when `fi` may be 0 or 1.
+///
 /// # Examples
 ///
 /// ```
@@ -808,12 +1395,35 @@ pub fn fi_to_efi(fi: u64) -> u64 {
     fi - 2
 }
 
-/// Fibonacci Index to Effective Fibonacci Index: EFI(fi) === fi - 2, where fi is the Fibonacci Index
+/// Fibonacci Index to Effective Fibonacci Index: EFI(fi) === fi - 2, where fi is the Fibonacci Index.
+///
+/// Returns [`None`] if `fi` is less than 2, the checked counterpart of [`fi_to_efi`].
 ///
 /// # Examples
 ///
 /// ```
-/// # use zeck::fi_to_efi_ref;
+/// # use zeck::try_fi_to_efi;
+/// assert_eq!(try_fi_to_efi(0), None);
+/// assert_eq!(try_fi_to_efi(1), None);
+/// assert_eq!(try_fi_to_efi(2), Some(0));
+/// assert_eq!(try_fi_to_efi(4), Some(2));
+/// ```
+#[wasm_bindgen]
+pub fn try_fi_to_efi(fi: u64) -> Option<u64> {
+    fi.checked_sub(2)
+}
+
+/// Fibonacci Index to Effective Fibonacci Index: EFI(fi) === fi - 2, where fi is the Fibonacci Index
+///
+/// # Panics
+///
+/// Panics if `fi` is less than 2, which has no Effective Fibonacci Index (in release builds the subtraction wraps
+/// instead). Use [`try_fi_to_efi_ref`] when `fi` may be 0 or 1.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::fi_to_efi_ref;
 /// assert_eq!(fi_to_efi_ref(&2), 0);
 /// assert_eq!(fi_to_efi_ref(&3), 1);
 /// assert_eq!(fi_to_efi_ref(&4), 2);
@@ -822,8 +1432,30 @@ pub fn fi_to_efi_ref(fi: &u64) -> u64 {
     *fi - 2
 }
 
+/// Fibonacci Index to Effective Fibonacci Index: EFI(fi) === fi - 2, where fi is the Fibonacci Index.
+///
+/// Returns [`None`] if `fi` is less than 2, the checked counterpart of [`fi_to_efi_ref`].
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::try_fi_to_efi_ref;
+/// assert_eq!(try_fi_to_efi_ref(&1), None);
+/// assert_eq!(try_fi_to_efi_ref(&3), Some(1));
+/// let efis: Option<Vec<u64>> = [2, 4, 6].iter().map(try_fi_to_efi_ref).collect();
+/// assert_eq!(efis, Some(vec![0, 2, 4]));
+/// ```
+pub fn try_fi_to_efi_ref(fi: &u64) -> Option<u64> {
+    fi.checked_sub(2)
+}
+
 /// Fibonacci Index to Effective Fibonacci Index: EFI(fi) === fi - 2, where fi is the Fibonacci Index
 ///
+/// # Panics
+///
+/// Panics if `fi` is less than 2, which has no Effective Fibonacci Index.
+/// Use [`try_fi_to_efi_biguint`] when `fi` may be 0 or 1.
+///
 /// # Examples
 ///
 /// ```
@@ -838,6 +1470,24 @@ pub fn fi_to_efi_biguint(fi: BigUint) -> BigUint {
     fi - BigUint::from(2u64)
 }
 
+/// Fibonacci Index to Effective Fibonacci Index: EFI(fi) === fi - 2, where fi is the Fibonacci Index.
+///
+/// Returns [`None`] if `fi` is less than 2, the checked counterpart of [`fi_to_efi_biguint`].
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::try_fi_to_efi_biguint;
+/// # use num_bigint::BigUint;
+/// # use num_traits::{One, Zero};
+/// assert_eq!(try_fi_to_efi_biguint(BigUint::one()), None);
+/// assert_eq!(try_fi_to_efi_biguint(BigUint::from(2u64)), Some(BigUint::zero()));
+/// ```
+pub fn try_fi_to_efi_biguint(fi: BigUint) -> Option<BigUint> {
+    let two = BigUint::from(2u64);
+    if fi < two { None } else { Some(fi - two) }
+}
+
 /// The memoized Fibonacci function taking an Effective Fibonacci Index as input.
 ///
 /// # Examples
@@ -860,7 +1510,7 @@ pub fn fi_to_efi_biguint(fi: BigUint) -> BigUint {
 /// ```
 #[wasm_bindgen]
 pub fn memoized_effective_fibonacci(efi: u64) -> u64 {
-    memoized_slow_fibonacci_recursive(efi_to_fi(efi))
+    memoized_fibonacci_u64(efi_to_fi(efi))
 }
 
 /// An Effective Zeckendorf List (EZL) has a lowest EFI of 0, which is an FI of 2.
@@ -899,6 +1549,165 @@ pub fn ezl_to_zl(ezl: &[u64]) -> Vec<u64> {
     ezl.iter().map(efi_to_fi_ref).collect()
 }
 
+/// A packed ezba (Effective Zeckendorf Bits Ascending), storing 8 bits per byte instead of one bit per [`u8`].
+///
+/// The packed bytes have exactly the layout produced by [`pack_ezba_bits_to_bytes`]: the first bit is the least significant
+/// bit of the first byte, and the last byte is padded with [`SKIP_BIT`]s. This means the compressed output is the packed
+/// buffer itself, and compressed data can be read without first unpacking it into a [`Vec<u8>`] of bits.
+///
+/// For the all-ones pathological inputs this cuts the peak memory of the bit vector by 8×.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{EzbaBits, ezba_from_ezld};
+/// let mut ezba_bits = EzbaBits::new();
+/// ezba_bits.push_use();
+/// ezba_bits.push_skip();
+/// ezba_bits.push_use();
+/// assert_eq!(ezba_bits.len(), 3);
+/// assert_eq!(ezba_bits.to_vec(), vec![1, 0, 1]);
+/// assert_eq!(ezba_bits.as_packed_bytes(), &[0b101]);
+/// assert_eq!(EzbaBits::from_ezld(&[2, 0]).to_vec(), ezba_from_ezld(&[2, 0]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EzbaBits {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl EzbaBits {
+    /// Creates an empty ezba.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty ezba with room for at least `bit_capacity` bits.
+    pub fn with_capacity(bit_capacity: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(bit_capacity.div_ceil(8)),
+            len: 0,
+        }
+    }
+
+    /// Wraps packed bytes, such as compressed data, as an ezba of `bytes.len() * 8` bits.
+    ///
+    /// This is the packed counterpart of [`unpack_bytes_to_ezba_bits`].
+    pub fn from_packed_bytes(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.to_vec(),
+            len: bytes.len() * 8,
+        }
+    }
+
+    /// Builds the ezba for an ezld (Effective Zeckendorf List Descending).
+    ///
+    /// This is the packed counterpart of [`ezba_from_ezld`], and produces the same bits.
+    pub fn from_ezld(effective_zeckendorf_list_descending: &[u64]) -> Self {
+        // This EZLD is guaranteed to be non-empty when there is a max EFI.
+        let Some(&max_efi) = effective_zeckendorf_list_descending.first() else {
+            let mut ezba_bits = Self::new();
+            ezba_bits.push_skip();
+            return ezba_bits;
+        };
+
+        let mut ezba_bits = Self::with_capacity(max_efi as usize + 1);
+        let mut effective_zeckendorf_list_ascending =
+            effective_zeckendorf_list_descending.iter().rev().peekable();
+
+        let mut current_efi = 0;
+        while current_efi <= max_efi {
+            if effective_zeckendorf_list_ascending
+                .next_if_eq(&&current_efi)
+                .is_some()
+            {
+                // The Zeckendorf representation never uses two consecutive Fibonacci numbers, so the skipped index must
+                // not be in the list. If it were, it would be silently dropped from the bits.
+                debug_assert!(
+                    effective_zeckendorf_list_ascending.peek() != Some(&&(current_efi + 1)),
+                    "The EZLD uses consecutive effective Fibonacci indices {} and {}",
+                    current_efi + 1,
+                    current_efi
+                );
+                ezba_bits.push_use();
+                current_efi += 2;
+            } else {
+                ezba_bits.push_skip();
+                current_efi += 1;
+            }
+        }
+
+        ezba_bits
+    }
+
+    /// Appends a [`USE_BIT`].
+    pub fn push_use(&mut self) {
+        self.push(true);
+    }
+
+    /// Appends a [`SKIP_BIT`].
+    pub fn push_skip(&mut self) {
+        self.push(false);
+    }
+
+    fn push(&mut self, is_use_bit: bool) {
+        let bit_index = self.len % 8;
+        if bit_index == 0 {
+            self.bytes.push(0);
+        }
+        if is_use_bit {
+            // The byte was pushed above when bit_index was 0, so the last byte always exists here.
+            *self.bytes.last_mut().expect("Packed bytes are non-empty") |= 1 << bit_index;
+        }
+        self.len += 1;
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at `index`, or `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<u8> {
+        if index >= self.len {
+            return None;
+        }
+        Some((self.bytes[index / 8] >> (index % 8)) & 1)
+    }
+
+    /// Iterates over the bits in ascending order, yielding [`USE_BIT`] or [`SKIP_BIT`] for each.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..self.len).map(|index| (self.bytes[index / 8] >> (index % 8)) & 1)
+    }
+
+    /// Converts the bits into the effective Fibonacci indices they use, the ezla (Effective Zeckendorf List Ascending).
+    ///
+    /// This is the packed counterpart of [`ezba_to_ezla`].
+    pub fn to_ezla(&self) -> Vec<u64> {
+        ezla_from_ezba_bits(self.iter())
+    }
+
+    /// Returns the packed bytes, which are identical to [`pack_ezba_bits_to_bytes`] of [`Self::to_vec`].
+    pub fn as_packed_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the ezba and returns the packed bytes, which are identical to [`pack_ezba_bits_to_bytes`] of [`Self::to_vec`].
+    pub fn into_packed_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Converts to one [`u8`] per bit, the representation used by [`ezba_from_ezld`] and [`ezba_to_ezla`].
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.iter().collect()
+    }
+}
+
 /// ezba is Effective Zeckendorf Bits Ascending ; ezld is Effective Zeckendorf List Descending
 ///
 /// The bits represent whether the corresponding effective Fibonacci index is used. I call these "use bits" and "skip bits" where a use bit is 1 and a skip bit is 0. This is by convention that I, Peter Ryszkiewicz decided, but it is theoretically possible to use skip bits and use bits flipped.
@@ -907,7 +1716,12 @@ pub fn ezl_to_zl(ezl: &[u64]) -> Vec<u64> {
 /// The first bit in the ezba represents whether the first effective Fibonacci index is used.
 /// The first effective Fibonacci index is always 0 and represents the Fibonacci index 2 which has a value of 1. We use effective Fibonacci indices because the first Fibonacci number, 0, is not useful for sums, and the second Fibonacci number, 1, is redundant because it is the same as the third Fibonacci number.
 ///
-/// TODO: Optimize the size of the output by using a bit vector instead of a vector of [`u8`]s. I made an initial attempt at this in the `use-bitvec` branch, but the benchmarks were slower.
+/// This returns one [`u8`] per bit for compatibility. Prefer [`EzbaBits::from_ezld`], which packs the bits 8 to a byte and uses 8× less memory.
+///
+/// # Panics
+///
+/// In debug builds, panics if the ezld uses two consecutive effective Fibonacci indices, which no Zeckendorf
+/// representation does. Release builds skip the check.
 ///
 /// # Examples
 ///
@@ -922,37 +1736,7 @@ pub fn ezl_to_zl(ezl: &[u64]) -> Vec<u64> {
 /// ```
 #[wasm_bindgen]
 pub fn ezba_from_ezld(effective_zeckendorf_list_descending: &[u64]) -> Vec<u8> {
-    if effective_zeckendorf_list_descending.is_empty() {
-        return vec![SKIP_BIT];
-    }
-
-    let effective_zeckendorf_list_ascending: Vec<u64> = effective_zeckendorf_list_descending
-        .iter()
-        .copied()
-        .rev()
-        .collect();
-
-    let mut effective_zeckendorf_bits_ascending = Vec::new();
-
-    let mut current_ezla_index = 0;
-
-    let mut current_efi = 0;
-    // This EZLD is guaranteed to be non-empty because of the guard at the beginning of the function.
-    let max_efi = effective_zeckendorf_list_descending[0];
-
-    while current_efi <= max_efi {
-        let current_ezla_value = effective_zeckendorf_list_ascending[current_ezla_index];
-        if current_ezla_value == current_efi {
-            effective_zeckendorf_bits_ascending.push(USE_BIT);
-            current_efi += 2;
-            current_ezla_index += 1
-        } else {
-            effective_zeckendorf_bits_ascending.push(SKIP_BIT);
-            current_efi += 1;
-        }
-    }
-
-    effective_zeckendorf_bits_ascending
+    EzbaBits::from_ezld(effective_zeckendorf_list_descending).to_vec()
 }
 
 /// Packs a slice of bits (0s and 1s) from an ezba (Effective Zeckendorf Bits Ascending) into bytes.
@@ -963,6 +1747,9 @@ pub fn ezba_from_ezld(effective_zeckendorf_list_descending: &[u64]) -> Vec<u8> {
 /// Every 8 bits become a [`u8`] in the output.
 /// The last byte is padded with 0s if the number of bits is not a multiple of 8.
 ///
+/// The bits are written with a [`BitWriter`], which packs full bytes 8 bits at a time with a multiplication bit trick.
+/// This is several times faster than [`pack_ezba_bits_to_bytes_scalar`] for the large bit vectors produced by large inputs.
+///
 /// # Examples
 ///
 /// ```
@@ -975,6 +1762,25 @@ pub fn ezba_from_ezld(effective_zeckendorf_list_descending: &[u64]) -> Vec<u8> {
 /// ```
 #[wasm_bindgen]
 pub fn pack_ezba_bits_to_bytes(ezba: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::with_capacity(ezba.len());
+    writer.write_bits(ezba);
+    writer.finish()
+}
+
+/// Packs a slice of bits (0s and 1s) from an ezba (Effective Zeckendorf Bits Ascending) into bytes, one bit at a time.
+///
+/// This is the straightforward reference implementation of [`pack_ezba_bits_to_bytes`], which packs 8 bits at a time
+/// with a multiplication bit trick and falls back to this function for the final partial byte. Both produce identical output.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{pack_ezba_bits_to_bytes, pack_ezba_bits_to_bytes_scalar};
+/// assert_eq!(pack_ezba_bits_to_bytes_scalar(&[0, 0, 1]), vec![0b100]);
+/// let ezba = [1, 0, 1, 0, 0, 1, 0, 1, 0, 1];
+/// assert_eq!(pack_ezba_bits_to_bytes_scalar(&ezba), pack_ezba_bits_to_bytes(&ezba));
+/// ```
+pub fn pack_ezba_bits_to_bytes_scalar(ezba: &[u8]) -> Vec<u8> {
     let mut out = Vec::with_capacity(ezba.len().div_ceil(8));
 
     for chunk in ezba.chunks(8) {
@@ -1004,6 +1810,9 @@ pub fn pack_ezba_bits_to_bytes(ezba: &[u8]) -> Vec<u8> {
 /// decompressed data with leading zeros to restore the exact original data. Without the original
 /// size, information will be lost during decompression.
 ///
+/// All-zero input of any length, including empty input, is the integer zero, which compresses to a single skip bit
+/// (`[0]`) and decompresses to a single zero byte, so only the original size can restore its length.
+///
 /// For a format that automatically handles size preservation, use [`crate::zeck_file_format::compress::compress_zeck_be`]
 /// instead, which includes a header with the original size information.
 ///
@@ -1012,7 +1821,7 @@ pub fn pack_ezba_bits_to_bytes(ezba: &[u8]) -> Vec<u8> {
 /// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
 /// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
 ///
-/// TODO: Technically, the way the input data is interpreted is arbitrary; we could interpret it as little endian which could result in a more compact representation. We could go even further and interpret the data at different byte or word boundaries to see if it results in a more compact representation, and signify to the caller which interpretation was used. We probably need a better understanding of random distributions of data to determine what is the optimal interpretation. More investigation is needed here.
+/// TODO: Technically, the way the input data is interpreted is arbitrary; we could interpret it as little endian which could result in a more compact representation. We could go even further and interpret the data at different byte or word boundaries to see if it results in a more compact representation, and signify to the caller which interpretation was used. [`zeck_file_format::compress::compress_zeck_rotations`] explores byte boundaries by rotating the data, and the `generate-statistics` example measures how often that helps. We probably need a better understanding of random distributions of data to determine what is the optimal interpretation. More investigation is needed here.
 ///
 /// # Examples
 ///
@@ -1028,19 +1837,79 @@ pub fn pack_ezba_bits_to_bytes(ezba: &[u8]) -> Vec<u8> {
 /// ```
 #[wasm_bindgen]
 pub fn padless_zeckendorf_compress_be_dangerous(data: &[u8]) -> Vec<u8> {
-    // Turn data into a biguint
-    let data_as_biguint = BigUint::from_bytes_be(data);
-    // println!("Data as biguint: {:?}", data_as_biguint);
-    // Get the effective zeckendorf list descending
-    let data_as_zld = memoized_zeckendorf_list_descending_for_biguint(&data_as_biguint);
-    // println!("Data as zld: {:?}", data_as_zld);
-    let data_as_ezld = zl_to_ezl(&data_as_zld);
-    // println!("Data as ezld: {:?}", data_as_ezld);
-    // Get the effective zeckendorf bits ascending
-    let data_as_ezba = ezba_from_ezld(&data_as_ezld);
-    // println!("Data as ezba: {:?}", data_as_ezba);
-    // Compress the data
-    pack_ezba_bits_to_bytes(&data_as_ezba)
+    // A single byte is the same integer in either byte order, and its compressed form is precomputed
+    if let &[byte] = data {
+        return compress_single_byte(byte);
+    }
+    zeckendorf_compress_biguint(&BigUint::from_bytes_be(data))
+}
+
+/// Compresses a single byte, returning the same packed EZBA bytes as [`padless_zeckendorf_compress_be_dangerous`] and
+/// [`padless_zeckendorf_compress_le_dangerous`] do for a one-byte slice.
+///
+/// The compressed form of all 256 byte values is computed once, on first use, and every call after that is a table
+/// lookup. Both padless compression functions use this for one-byte input, which speeds up loops over small values
+/// such as the ones in the statistics example.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::compress_single_byte;
+/// assert_eq!(compress_single_byte(0), vec![0]);
+/// assert_eq!(compress_single_byte(12), vec![0b111]);
+/// assert_eq!(compress_single_byte(255), vec![33, 2]);
+/// ```
+#[wasm_bindgen]
+pub fn compress_single_byte(byte: u8) -> Vec<u8> {
+    SINGLE_BYTE_COMPRESSION_TABLE[byte as usize].clone()
+}
+
+/// Compresses `n` directly if it is a Fibonacci number, returning the same packed EZBA bytes that the padless
+/// compression functions produce for it, or [`None`] if `n` is not a Fibonacci number.
+///
+/// A Fibonacci number is its own Zeckendorf representation, so its EZBA is a single [`USE_BIT`] after a [`SKIP_BIT`]
+/// for every smaller effective Fibonacci index. This detects it with [`fibonacci_index_of`] and writes that bit
+/// directly, skipping the greedy Zeckendorf search. 0 returns [`None`], since its Zeckendorf representation is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{compress_if_fibonacci, padless_zeckendorf_compress_be_dangerous};
+/// # use num_bigint::BigUint;
+/// assert_eq!(compress_if_fibonacci(&BigUint::from(233u64)), Some(vec![0, 0b1000]));
+/// assert_eq!(compress_if_fibonacci(&BigUint::from(233u64)), Some(padless_zeckendorf_compress_be_dangerous(&[233])));
+/// assert_eq!(compress_if_fibonacci(&BigUint::from(234u64)), None);
+/// ```
+pub fn compress_if_fibonacci(n: &BigUint) -> Option<Vec<u8>> {
+    let fi = fibonacci_index_of(n).filter(|&fi| fi >= 2)?;
+    let efi = fi_to_efi(fi) as usize;
+    let mut packed_ezba = vec![0u8; (efi + 1).div_ceil(8)];
+    packed_ezba[efi / 8] |= USE_BIT << (efi % 8);
+    Some(packed_ezba)
+}
+
+/// Returns the data compressed with [`padless_zeckendorf_compress_be_dangerous`], read back as a little endian integer.
+///
+/// The compressed bytes are packed least significant bit first, so this integer's bit `i` is EZBA bit `i`, and its
+/// [`BigUint::bits`] is the number of bits the compressed data really needs, without the padding of the last byte.
+/// The statistics and plotting examples use this to measure compression ratios in bits.
+///
+/// For non-zero data, `compressed_value(data).bits()` equals the EZBA length, because the last EZBA bit is always the
+/// [`USE_BIT`] of the largest Fibonacci number. Zero is the exception: its EZBA is a single [`SKIP_BIT`], and its value
+/// has 0 bits. The value itself is not bounded by the input value, since every [`USE_BIT`] also stands for the skipped
+/// index after it: 4 = 3 + 1 compresses to `0b11`, which is 3.
+///
+/// # Examples
+///
+/// ```
+/// # use num_bigint::BigUint;
+/// # use zeck::compressed_value;
+/// assert_eq!(compressed_value(&[12]), BigUint::from(0b111u8)); // 12 = 8 + 3 + 1
+/// assert_eq!(compressed_value(&[55]).bits(), 9); // 55 is EFI 8, so the EZBA is 8 skip bits and a use bit
+/// assert_eq!(compressed_value(&[0]).bits(), 0);
+/// ```
+pub fn compressed_value(data: &[u8]) -> BigUint {
+    BigUint::from_bytes_le(&padless_zeckendorf_compress_be_dangerous(data))
 }
 
 /// Compresses a slice of bytes using the Padless Zeckendorf Compression algorithm.
@@ -1055,6 +1924,9 @@ pub fn padless_zeckendorf_compress_be_dangerous(data: &[u8]) -> Vec<u8> {
 /// decompressed data with leading zeros to restore the exact original data. Without the original
 /// size, information will be lost during decompression.
 ///
+/// All-zero input of any length, including empty input, is the integer zero, which compresses to a single skip bit
+/// (`[0]`) and decompresses to a single zero byte, so only the original size can restore its length.
+///
 /// For a format that automatically handles size preservation, use [`crate::zeck_file_format::compress::compress_zeck_le`]
 /// instead, which includes a header with the original size information.
 ///
@@ -1077,23 +1949,110 @@ pub fn padless_zeckendorf_compress_be_dangerous(data: &[u8]) -> Vec<u8> {
 /// ```
 #[wasm_bindgen]
 pub fn padless_zeckendorf_compress_le_dangerous(data: &[u8]) -> Vec<u8> {
-    // Turn data into a biguint
-    let data_as_biguint = BigUint::from_bytes_le(data);
-    // println!("Data as biguint: {:?}", data_as_biguint);
-    // Get the effective zeckendorf list descending
-    let data_as_zld = memoized_zeckendorf_list_descending_for_biguint(&data_as_biguint);
-    // println!("Data as zld: {:?}", data_as_zld);
-    let data_as_ezld = zl_to_ezl(&data_as_zld);
-    // println!("Data as ezld: {:?}", data_as_ezld);
-    // Get the effective zeckendorf bits ascending
-    let data_as_ezba = ezba_from_ezld(&data_as_ezld);
-    // println!("Data as ezba: {:?}", data_as_ezba);
-    // Compress the data
-    pack_ezba_bits_to_bytes(&data_as_ezba)
+    // A single byte is the same integer in either byte order, and its compressed form is precomputed
+    if let &[byte] = data {
+        return compress_single_byte(byte);
+    }
+    zeckendorf_compress_biguint(&BigUint::from_bytes_le(data))
+}
+
+/// Compresses an integer using the Padless Zeckendorf Compression algorithm, returning its packed EZBA bytes.
+///
+/// This is what [`padless_zeckendorf_compress_be_dangerous`] and [`padless_zeckendorf_compress_le_dangerous`] do after
+/// reading their input as an integer, so a caller that already holds a [`BigUint`], for example the result of some
+/// arithmetic, can skip converting it to bytes and back. Endianness does not apply at this level: the input is a
+/// pure integer, and the output bytes are always packed least significant bit first. Use
+/// [`zeckendorf_decompress_to_biguint`] to get the integer back.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{zeckendorf_compress_biguint, zeckendorf_decompress_to_biguint};
+/// # use num_bigint::BigUint;
+/// assert_eq!(zeckendorf_compress_biguint(&BigUint::from(0u64)), vec![0]);
+/// assert_eq!(zeckendorf_compress_biguint(&BigUint::from(1u64)), vec![1]);
+/// assert_eq!(zeckendorf_compress_biguint(&BigUint::from(12u64)), vec![0b111]);
+/// assert_eq!(zeckendorf_compress_biguint(&BigUint::from(255u64)), vec![33, 2]);
+///
+/// let n = BigUint::from(3u8).pow(200);
+/// assert_eq!(zeckendorf_decompress_to_biguint(&zeckendorf_compress_biguint(&n)), n);
+/// ```
+pub fn zeckendorf_compress_biguint(n: &BigUint) -> Vec<u8> {
+    let ezld = zl_to_ezl(&memoized_zeckendorf_list_descending_for_biguint(n));
+    EzbaBits::from_ezld(&ezld).into_packed_bytes()
+}
+
+/// Decompresses packed EZBA bytes straight to the integer they represent, the inverse of
+/// [`zeckendorf_compress_biguint`].
+///
+/// Like the compression, this does not involve endianness: the padless decompression functions only differ in how
+/// they write this integer out as bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeckendorf_decompress_to_biguint;
+/// # use num_bigint::BigUint;
+/// assert_eq!(zeckendorf_decompress_to_biguint(&[0]), BigUint::from(0u64));
+/// assert_eq!(zeckendorf_decompress_to_biguint(&[0b111]), BigUint::from(12u64));
+/// assert_eq!(zeckendorf_decompress_to_biguint(&[0, 1]), BigUint::from(55u64));
+/// assert_eq!(zeckendorf_decompress_to_biguint(&[34, 2]), BigUint::from(256u64));
+/// ```
+pub fn zeckendorf_decompress_to_biguint(compressed_data: &[u8]) -> BigUint {
+    ezba_bits_to_biguint(&EzbaBits::from_packed_bytes(compressed_data))
+}
+
+/// Returns the unpacked ezba (Effective Zeckendorf Bits Ascending) of a slice of bytes read as a big endian integer,
+/// one [`u8`] of 0 or 1 per bit.
+///
+/// This is [`padless_zeckendorf_compress_be_dangerous`] without the final packing step, for callers that write the
+/// bits into their own bit-level container. [`pack_ezba_bits_to_bytes`] of the result is exactly the padless
+/// compressed output, and [`decompress_from_ezba`] reverses it. Like the padless compression, leading zero bytes are
+/// not preserved.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{decompress_from_ezba, pack_ezba_bits_to_bytes, padless_zeckendorf_compress_be_dangerous, zeckendorf_ezba_be};
+/// assert_eq!(zeckendorf_ezba_be(&[0]), vec![0]);
+/// assert_eq!(zeckendorf_ezba_be(&[12]), vec![1, 1, 1]); // 1 + 3 + 8
+/// assert_eq!(zeckendorf_ezba_be(&[55]), vec![0, 0, 0, 0, 0, 0, 0, 0, 1]);
+///
+/// let data = [1, 0];
+/// let ezba = zeckendorf_ezba_be(&data);
+/// assert_eq!(pack_ezba_bits_to_bytes(&ezba), padless_zeckendorf_compress_be_dangerous(&data));
+/// assert_eq!(decompress_from_ezba(&ezba), data);
+/// ```
+#[wasm_bindgen]
+pub fn zeckendorf_ezba_be(data: &[u8]) -> Vec<u8> {
+    let zld = memoized_zeckendorf_list_descending_for_biguint(&BigUint::from_bytes_be(data));
+    ezba_from_ezld(&zl_to_ezl(&zld))
+}
+
+/// Decompresses an unpacked ezba (Effective Zeckendorf Bits Ascending), one [`u8`] of 0 or 1 per bit, to big endian
+/// bytes, the inverse of [`zeckendorf_ezba_be`].
+///
+/// Trailing skip bits, such as the padding of a container that stores the bits in whole bytes, do not change the
+/// result. Like [`padless_zeckendorf_decompress_be_dangerous`], leading zero bytes are not restored.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::decompress_from_ezba;
+/// assert_eq!(decompress_from_ezba(&[0]), vec![0]);
+/// assert_eq!(decompress_from_ezba(&[1, 1, 1]), vec![12]);
+/// assert_eq!(decompress_from_ezba(&[1, 1, 1, 0, 0, 0, 0, 0]), vec![12]);
+/// ```
+#[wasm_bindgen]
+pub fn decompress_from_ezba(ezba: &[u8]) -> Vec<u8> {
+    sum_fibonacci_biguint(|| ezla_indices(ezba.iter().copied()).map(efi_to_fi)).to_bytes_be()
 }
 
 /// Unpacks a vector of bytes into a vector of bits (0s and 1s) from an ezba (Effective Zeckendorf Bits Ascending).
 ///
+/// The bits are read with a [`BitReader`], which expands each byte by copying its 8 bits from a precomputed 256×8
+/// lookup table. This is faster than [`unpack_bytes_to_ezba_bits_scalar`] on the decompression hot path for large inputs.
+///
 /// # Examples
 ///
 /// ```
@@ -1105,6 +2064,23 @@ pub fn padless_zeckendorf_compress_le_dangerous(data: &[u8]) -> Vec<u8> {
 /// ```
 #[wasm_bindgen]
 pub fn unpack_bytes_to_ezba_bits(bytes: &[u8]) -> Vec<u8> {
+    BitReader::new(bytes).read_to_end()
+}
+
+/// Unpacks a vector of bytes into a vector of bits (0s and 1s) from an ezba (Effective Zeckendorf Bits Ascending), one bit at a time.
+///
+/// This is the straightforward reference implementation of [`unpack_bytes_to_ezba_bits`], which copies 8 bits at a time
+/// from a precomputed lookup table. Both produce identical output.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{unpack_bytes_to_ezba_bits, unpack_bytes_to_ezba_bits_scalar};
+/// assert_eq!(unpack_bytes_to_ezba_bits_scalar(&[0b111]), vec![1, 1, 1, 0, 0, 0, 0, 0]);
+/// let bytes = [0b1010_0101, 0xFF, 0];
+/// assert_eq!(unpack_bytes_to_ezba_bits_scalar(&bytes), unpack_bytes_to_ezba_bits(&bytes));
+/// ```
+pub fn unpack_bytes_to_ezba_bits_scalar(bytes: &[u8]) -> Vec<u8> {
     let mut ezba_bits = Vec::with_capacity(bytes.len() * 8);
     for byte in bytes {
         for i in 0..8 {
@@ -1127,83 +2103,605 @@ pub fn unpack_bytes_to_ezba_bits(bytes: &[u8]) -> Vec<u8> {
 /// ```
 #[wasm_bindgen]
 pub fn ezba_to_ezla(ezba_bits: &[u8]) -> Vec<u64> {
-    let mut ezla = Vec::new();
-    let mut current_efi = 0;
-    for bit in ezba_bits {
-        if *bit == USE_BIT {
-            ezla.push(current_efi);
-            current_efi += 2;
-        } else {
-            current_efi += 1;
-        }
-    }
-    ezla
+    ezla_from_ezba_bits(ezba_bits.iter().copied())
 }
 
-/// Converts a Zeckendorf List to a [`BigUint`].
+/// Calls `f` with the effective Fibonacci index of every [`USE_BIT`] in an ezba (Effective Zeckendorf Bits Ascending),
+/// in ascending order, without allocating.
 ///
-/// The Zeckendorf List is a list of Fibonacci indices that sum to the given number.
-/// It does not matter if the ZL is ascending or descending. The sum operation is commutative.
+/// The indices are exactly those returned by [`ezba_to_ezla`], so this is useful when the caller only needs to visit them,
+/// for example to sum their Fibonacci values.
 ///
 /// # Examples
 ///
 /// ```
-/// # use zeck::zl_to_biguint;
-/// # use num_bigint::BigUint;
-/// # use num_traits::{One, Zero};
-/// assert_eq!(zl_to_biguint(&[]), BigUint::zero());
-/// assert_eq!(zl_to_biguint(&[0]), BigUint::zero());
-/// assert_eq!(zl_to_biguint(&[1]), BigUint::one());
-/// assert_eq!(zl_to_biguint(&[2]), BigUint::one());
-/// assert_eq!(zl_to_biguint(&[3]), BigUint::from(2u64));
-/// assert_eq!(zl_to_biguint(&[4]), BigUint::from(3u64));
-/// assert_eq!(zl_to_biguint(&[5]), BigUint::from(5u64));
-/// assert_eq!(zl_to_biguint(&[6]), BigUint::from(8u64));
-/// assert_eq!(zl_to_biguint(&[6, 2]), BigUint::from(9u64));
-/// assert_eq!(zl_to_biguint(&[6, 3]), BigUint::from(10u64));
-/// assert_eq!(zl_to_biguint(&[6, 4]), BigUint::from(11u64));
-/// assert_eq!(zl_to_biguint(&[6, 4, 2]), BigUint::from(12u64));
+/// # use zeck::{efi_to_fi, for_each_ezla_index, memoized_fibonacci_u64};
+/// let mut sum = 0;
+/// for_each_ezla_index(&[1, 1, 1], |efi| sum += memoized_fibonacci_u64(efi_to_fi(efi)));
+/// assert_eq!(sum, 12); // 1 + 3 + 8
 /// ```
-pub fn zl_to_biguint(zl: &[u64]) -> BigUint {
-    zl.iter().fold(BigUint::zero(), |acc, fi| {
-        acc + &*memoized_slow_fibonacci_biguint_iterative(*fi)
-        // TODO: investigate ways we can get the lower memory usage of the cached fast doubling Fibonacci algorithm but the speed of the cached slow Fibonacci algorithm. As of now, the cached fast doubling Fibonacci algorithm is slower at decompression than the cached slow Fibonacci algorithm at large data inputs, on the order of > 10kB. See the comments in scripts/poll_rss.sh for more information.
-        // acc + &*fast_doubling_fibonacci_biguint(*fi)
-    })
+pub fn for_each_ezla_index(ezba_bits: &[u8], f: impl FnMut(u64)) {
+    ezla_indices(ezba_bits.iter().copied()).for_each(f);
 }
 
-/// Creates an "all ones Zeckendorf number", or AOZN, by creating an Effective Zeckendorf Bits Ascending (EZBA)
-/// with `n` consecutive ones, then converting it to a [`BigUint`].
-///
-/// An AOZN is created by generating a Zeckendorf representation with `n`
-/// consecutive ones (in the Effective Zeckendorf Bits Ascending format), then converting that
-/// representation back to the actual number value. This is useful for understanding how Zeckendorf
-/// representations behave when they contain many ones.
-///
-/// # Arguments
-///
-/// * `n` - The number of consecutive ones in the EZBA representation
-///
-/// # Returns
+/// Shared implementation of [`ezba_to_ezla`] and [`EzbaBits::to_ezla`] over any sequence of bits.
+fn ezla_from_ezba_bits(ezba_bits: impl Iterator<Item = u8>) -> Vec<u64> {
+    ezla_indices(ezba_bits).collect()
+}
+
+/// Lazily yields the effective Fibonacci index of every [`USE_BIT`] in a sequence of ezba bits, in ascending order.
+fn ezla_indices(ezba_bits: impl Iterator<Item = u8>) -> impl Iterator<Item = u64> {
+    ezba_bits
+        .scan(0u64, |current_efi, bit| {
+            let efi = *current_efi;
+            if bit == USE_BIT {
+                *current_efi += 2;
+                Some(Some(efi))
+            } else {
+                *current_efi += 1;
+                Some(None)
+            }
+        })
+        .flatten()
+}
+
+/// Returns the number of bytes [`padless_zeckendorf_compress_be_dangerous`] or [`padless_zeckendorf_compress_le_dangerous`]
+/// would produce for `data`, without allocating the ezba bit vector or packing it.
 ///
-/// Returns [`BigUint::zero()`] if `n` is 0, otherwise returns the [`BigUint`] value of the all-ones
-/// Zeckendorf representation.
+/// The ezba ends at the largest effective Fibonacci index used, but `ceil((max_efi + 1) / 8)` is only an upper bound:
+/// every [`USE_BIT`] except the last also covers the following index, which gets no bit of its own. So the ezba has
+/// `max_efi + 2 - term_count` bits, where `term_count` is the number of terms in the Zeckendorf representation.
+/// This is useful for pre-sizing output buffers before compressing.
 ///
 /// # Examples
 ///
 /// ```
-/// # use zeck::all_ones_zeckendorf_to_biguint;
-/// # use num_bigint::BigUint;
-/// # use num_traits::Zero;
-/// assert_eq!(all_ones_zeckendorf_to_biguint(0), BigUint::zero());
-/// assert_eq!(all_ones_zeckendorf_to_biguint(1), BigUint::from(1u64)); // 1
-/// assert_eq!(all_ones_zeckendorf_to_biguint(2), BigUint::from(4u64)); // 1 + 3
-/// assert_eq!(all_ones_zeckendorf_to_biguint(3), BigUint::from(12u64)); // 1 + 3 + 8
-/// assert_eq!(all_ones_zeckendorf_to_biguint(4), BigUint::from(33u64)); // 1 + 3 + 8 + 21
+/// # use zeck::{Endianness, compressed_byte_length, padless_zeckendorf_compress_be_dangerous};
+/// assert_eq!(compressed_byte_length(&[0], Endianness::Big), 1);
+/// assert_eq!(compressed_byte_length(&[54], Endianness::Big), 1);
+/// assert_eq!(compressed_byte_length(&[55], Endianness::Big), 2);
+/// assert_eq!(compressed_byte_length(&[1, 0], Endianness::Big), padless_zeckendorf_compress_be_dangerous(&[1, 0]).len());
+/// assert_eq!(compressed_byte_length(&[1, 0], Endianness::Little), 1);
 /// ```
-pub fn all_ones_zeckendorf_to_biguint(n: usize) -> BigUint {
-    if n == 0 {
-        return BigUint::zero();
+#[wasm_bindgen]
+pub fn compressed_byte_length(data: &[u8], endian: Endianness) -> usize {
+    let data_as_biguint = match endian {
+        Endianness::Big => BigUint::from_bytes_be(data),
+        Endianness::Little => BigUint::from_bytes_le(data),
+    };
+    let data_as_zld = memoized_zeckendorf_list_descending_for_biguint(&data_as_biguint);
+    let Some(&max_fi) = data_as_zld.first() else {
+        // Zero compresses to a single skip bit
+        return 1;
+    };
+
+    let ezba_bit_count = fi_to_efi(max_fi) as usize + 2 - data_as_zld.len();
+    ezba_bit_count.div_ceil(8)
+}
+
+/// Returns a range of Fibonacci indices, estimated from the bit length of `target` with Binet's formula, that should
+/// contain the smallest index whose Fibonacci value is greater than or equal to `target`.
+///
+/// `target` is at least `2^(bits - 1)` and less than `2^bits`, so the index is between the estimates for those two
+/// powers of two, which are less than 2 indices apart. One index of margin on each side covers the rounding of the
+/// estimates, which only matters for the first few indices where PHI^i / sqrt(5) is not yet close to F(i).
+fn fibonacci_index_bracket(target: &BigUint) -> (u64, u64) {
+    let bits = target.bits();
+    if bits == 0 {
+        return (1, 1);
+    }
+    let low = (estimated_fibonacci_index((bits - 1) as f64).floor() as u64)
+        .saturating_sub(1)
+        .max(1);
+    let high = estimated_fibonacci_index(bits as f64).ceil() as u64 + 1;
+    (low, high)
+}
+
+/// Returns the smallest Fibonacci index whose Fibonacci value is greater than or equal to `target`.
+///
+/// Binary searches the range from [`fibonacci_index_bracket`], after widening it one index at a time if the estimate
+/// missed. An exponential search that doubles an upper bound from 1 would instead fill the Fibonacci cache up to twice
+/// the index it needs, which for large inputs is a lot of memory spent on Fibonacci numbers that are never used.
+fn smallest_fibonacci_index_at_least(target: &BigUint) -> u64 {
+    smallest_fibonacci_index_at_least_with(target, memoized_slow_fibonacci_biguint_iterative)
+}
+
+/// Implements [`smallest_fibonacci_index_at_least`], looking Fibonacci numbers up with `fibonacci` so tests can observe
+/// which indices the search needs.
+fn smallest_fibonacci_index_at_least_with(
+    target: &BigUint,
+    mut fibonacci: impl FnMut(u64) -> Arc<BigUint>,
+) -> u64 {
+    let (mut low, mut high) = fibonacci_index_bracket(target);
+    while low > 1 && *fibonacci(low - 1) >= *target {
+        low -= 1;
+    }
+    while *fibonacci(high) < *target {
+        high += 1;
+    }
+
+    // Binary search for the smallest index i such that F[i] >= target, knowing F[low - 1] < target <= F[high]
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if *fibonacci(mid) < *target {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Returns the same index as [`smallest_fibonacci_index_at_least`], narrowing the search by bit length first.
+///
+/// A Fibonacci number with fewer bits than `target` is smaller than it, and one with more bits is larger, so only the
+/// indices whose Fibonacci numbers have exactly `target`'s bit length, at most two since each Fibonacci number is less
+/// than twice the previous one, need an exact [`BigUint`] comparison. Their range is found with
+/// [`fibonacci_index_range_with_bit_length`].
+fn smallest_fibonacci_index_at_least_by_bit_length(target: &BigUint) -> u64 {
+    let (start, end) = fibonacci_index_range_with_bit_length(target.bits());
+
+    (start..end)
+        .find(|&fi| *memoized_slow_fibonacci_biguint_iterative(fi) >= *target)
+        .unwrap_or(end)
+}
+
+/// Returns the range of Fibonacci indices whose Fibonacci numbers have exactly `target_bits` bits, skipping index 0,
+/// by binary searching [`FIBONACCI_BIT_LENGTH_CACHE`].
+///
+/// The cache is only read when it already reaches past `target_bits`, which is the common case. Otherwise the missing
+/// bit lengths are computed without holding any lock, and the write lock is only taken to append the ones another
+/// thread has not appended in the meantime.
+fn fibonacci_index_range_with_bit_length(target_bits: u64) -> (u64, u64) {
+    let index_range = |bit_length_cache: &[(u64, u64)]| {
+        // The range is only known once some Fibonacci number has more bits than the target
+        let &(last_fi, last_bits) = bit_length_cache
+            .last()
+            .expect("The bit length cache holds the base cases");
+        if last_bits <= target_bits {
+            return Err(last_fi);
+        }
+        // Index 0 is skipped, like the exact search, so 0 and 1 both give index 1
+        let start = bit_length_cache
+            .partition_point(|&(_, bits)| bits < target_bits)
+            .max(1);
+        let end = bit_length_cache.partition_point(|&(_, bits)| bits <= target_bits);
+        Ok((bit_length_cache[start].0, bit_length_cache[end].0))
+    };
+
+    let last_fi = match index_range(
+        &FIBONACCI_BIT_LENGTH_CACHE
+            .read()
+            .expect("Failed to read Fibonacci bit length cache"),
+    ) {
+        Ok(range) => return range,
+        Err(last_fi) => last_fi,
+    };
+
+    // Compute the missing bit lengths until one exceeds the target, without holding the lock
+    let mut missing_bit_lengths = Vec::new();
+    let mut fi = last_fi;
+    loop {
+        fi += 1;
+        let bits = memoized_slow_fibonacci_biguint_iterative(fi).bits();
+        missing_bit_lengths.push((fi, bits));
+        if bits > target_bits {
+            break;
+        }
+    }
+
+    let mut bit_length_cache = FIBONACCI_BIT_LENGTH_CACHE
+        .write()
+        .expect("Failed to write Fibonacci bit length cache");
+    // Another thread may have extended the cache since it was read, so only append what is still missing
+    let cached_last_fi = bit_length_cache.last().map_or(0, |&(fi, _)| fi);
+    if cached_last_fi < last_fi {
+        // The cache was cleared in the meantime, so the computed bit lengths would leave a gap; start over
+        drop(bit_length_cache);
+        return fibonacci_index_range_with_bit_length(target_bits);
+    }
+    bit_length_cache.extend(
+        missing_bit_lengths
+            .into_iter()
+            .filter(|&(fi, _)| fi > cached_last_fi),
+    );
+    index_range(&bit_length_cache).expect("The bit length cache now reaches past the target")
+}
+
+/// Creates the lazy Effective Zeckendorf Bits Ascending (lazy EZBA) for the given number.
+///
+/// The lazy Fibonacci representation is the counterpart of the Zeckendorf (greedy) representation: instead of never using
+/// two consecutive Fibonacci numbers, it never skips two consecutive Fibonacci numbers below the largest one used.
+/// It is found by using every effective Fibonacci index up to the smallest one whose running sum reaches `n`,
+/// then removing the Zeckendorf representation of the excess, which never contains consecutive indices.
+///
+/// The bits mirror the greedy EZBA encoding: a [`USE_BIT`] uses the current EFI and advances by 1, while a [`SKIP_BIT`]
+/// skips the current EFI and implicitly uses the next one, advancing by 2. Because trailing [`SKIP_BIT`]s would be
+/// ambiguous with byte padding, the bits always end with a terminating [`USE_BIT`] that carries no value.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::lazy_ezba_from_biguint;
+/// # use num_bigint::BigUint;
+/// # use num_traits::Zero;
+/// assert_eq!(lazy_ezba_from_biguint(&BigUint::zero()), vec![1]); // Only the terminator
+/// assert_eq!(lazy_ezba_from_biguint(&BigUint::from(1u64)), vec![1, 1]); // 1
+/// assert_eq!(lazy_ezba_from_biguint(&BigUint::from(2u64)), vec![0, 1]); // 2
+/// assert_eq!(lazy_ezba_from_biguint(&BigUint::from(4u64)), vec![1, 0, 1]); // 1 + 3
+/// assert_eq!(lazy_ezba_from_biguint(&BigUint::from(6u64)), vec![1, 1, 1, 1]); // 1 + 2 + 3
+/// ```
+pub fn lazy_ezba_from_biguint(n: &BigUint) -> Vec<u8> {
+    if n.is_zero() {
+        return vec![USE_BIT];
+    }
+
+    // The sum of the Fibonacci numbers at EFIs 0..=k is F(k + 4) - 2, so find the smallest k where that sum reaches n.
+    let fi = smallest_fibonacci_index_at_least(&(n + 2u8));
+    let max_efi = fi - 4;
+    let excess = &*memoized_slow_fibonacci_biguint_iterative(fi) - 2u8 - n;
+    let removed_ezla: Vec<u64> =
+        zl_to_ezl(&memoized_zeckendorf_list_descending_for_biguint(&excess))
+            .into_iter()
+            .rev()
+            .collect();
+
+    let mut lazy_ezba = Vec::new();
+    let mut removed_index = 0;
+    let mut current_efi = 0;
+    while current_efi <= max_efi {
+        if removed_ezla.get(removed_index) == Some(&current_efi) {
+            lazy_ezba.push(SKIP_BIT);
+            current_efi += 2;
+            removed_index += 1;
+        } else {
+            lazy_ezba.push(USE_BIT);
+            current_efi += 1;
+        }
+    }
+    lazy_ezba.push(USE_BIT);
+
+    lazy_ezba
+}
+
+/// Converts a lazy Effective Zeckendorf Bits Ascending (lazy EZBA), as created by [`lazy_ezba_from_biguint`],
+/// into the Effective Zeckendorf List Ascending of the effective Fibonacci indices it uses.
+///
+/// Everything from the final [`USE_BIT`] (the terminator) onwards is ignored, so byte padding is harmless.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::lazy_ezba_to_ezla;
+/// assert_eq!(lazy_ezba_to_ezla(&[0, 0, 0, 0, 0, 0, 0, 0]), vec![] as Vec<u64>);
+/// assert_eq!(lazy_ezba_to_ezla(&[1, 0, 0, 0, 0, 0, 0, 0]), vec![] as Vec<u64>);
+/// assert_eq!(lazy_ezba_to_ezla(&[0, 1, 0, 0, 0, 0, 0, 0]), vec![1u64]);
+/// assert_eq!(lazy_ezba_to_ezla(&[1, 0, 1, 0, 0, 0, 0, 0]), vec![0u64, 2u64]);
+/// ```
+pub fn lazy_ezba_to_ezla(lazy_ezba_bits: &[u8]) -> Vec<u64> {
+    let Some(terminator_position) = lazy_ezba_bits.iter().rposition(|&bit| bit == USE_BIT) else {
+        return vec![];
+    };
+
+    let mut ezla = Vec::new();
+    let mut current_efi = 0;
+    for &bit in &lazy_ezba_bits[..terminator_position] {
+        if bit == USE_BIT {
+            ezla.push(current_efi);
+            current_efi += 1;
+        } else {
+            ezla.push(current_efi + 1);
+            current_efi += 2;
+        }
+    }
+    ezla
+}
+
+/// Converts a Zeckendorf List to a [`BigUint`].
+///
+/// The Zeckendorf List is a list of Fibonacci indices that sum to the given number.
+/// It does not matter if the ZL is ascending or descending. The sum operation is commutative.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zl_to_biguint;
+/// # use num_bigint::BigUint;
+/// # use num_traits::{One, Zero};
+/// assert_eq!(zl_to_biguint(&[]), BigUint::zero());
+/// assert_eq!(zl_to_biguint(&[0]), BigUint::zero());
+/// assert_eq!(zl_to_biguint(&[1]), BigUint::one());
+/// assert_eq!(zl_to_biguint(&[2]), BigUint::one());
+/// assert_eq!(zl_to_biguint(&[3]), BigUint::from(2u64));
+/// assert_eq!(zl_to_biguint(&[4]), BigUint::from(3u64));
+/// assert_eq!(zl_to_biguint(&[5]), BigUint::from(5u64));
+/// assert_eq!(zl_to_biguint(&[6]), BigUint::from(8u64));
+/// assert_eq!(zl_to_biguint(&[6, 2]), BigUint::from(9u64));
+/// assert_eq!(zl_to_biguint(&[6, 3]), BigUint::from(10u64));
+/// assert_eq!(zl_to_biguint(&[6, 4]), BigUint::from(11u64));
+/// assert_eq!(zl_to_biguint(&[6, 4, 2]), BigUint::from(12u64));
+/// ```
+///
+/// This function is lenient: indices 0 and 1 contribute their Fibonacci values of 0 and 1, and the list is not checked
+/// for duplicate or consecutive indices. Use [`zl_to_biguint_checked`] to reject lists that are not valid Zeckendorf representations.
+pub fn zl_to_biguint(zl: &[u64]) -> BigUint {
+    sum_fibonacci_biguint(|| zl.iter().copied())
+}
+
+/// Converts a Zeckendorf List to a [`BigUint`] like [`zl_to_biguint`], but sums the Fibonacci values in parallel on
+/// rayon's thread pool.
+///
+/// [`BigUint`] addition is associative, so each worker sums its share of the list and the partial sums are added in a
+/// balanced reduction. This only pays off for long lists, such as the tens of thousands of indices of all-ones data;
+/// the `zl_to_biguint` benchmark group compares it with the sequential sum.
+///
+/// The contiguous Fibonacci cache is warmed up to the largest index first, and the values up to it are copied out as
+/// [`Arc`]s under a short read lock. The workers index into that snapshot, so no lock is held while they sum, and a
+/// worker that needs the cache's write lock for something else cannot deadlock against the calling thread.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{zl_to_biguint, zl_to_biguint_parallel};
+/// # use num_bigint::BigUint;
+/// assert_eq!(zl_to_biguint_parallel(&[6, 4, 2]), BigUint::from(12u64));
+///
+/// let zl = (1..=500).map(|i| 4 * i).collect::<Vec<_>>();
+/// assert_eq!(zl_to_biguint_parallel(&zl), zl_to_biguint(&zl));
+/// ```
+#[cfg(feature = "parallel")]
+pub fn zl_to_biguint_parallel(zl: &[u64]) -> BigUint {
+    let Some(&max_fi) = zl.iter().max() else {
+        return BigUint::zero();
+    };
+
+    memoized_slow_fibonacci_biguint_iterative(max_fi);
+    let fibonacci_values: Vec<Arc<BigUint>> = {
+        let fibonacci_cache = FIBONACCI_BIGUINT_CACHE
+            .read()
+            .expect("Failed to read Fibonacci BigUint cache");
+        match fibonacci_cache.get(..=max_fi as usize) {
+            Some(fibonacci_values) => fibonacci_values.to_vec(),
+            // The cache was cleared by another thread in the meantime
+            None => {
+                drop(fibonacci_cache);
+                return zl_to_biguint(zl);
+            }
+        }
+    };
+
+    zl.par_iter()
+        .fold(BigUint::zero, |acc, &fi| {
+            acc + &*fibonacci_values[fi as usize]
+        })
+        .reduce(BigUint::zero, |left, right| left + right)
+}
+
+/// Sums the Fibonacci numbers at the Fibonacci indices yielded by `fis`, which is called once to find the largest index
+/// and once more to sum, so the indices never need to be collected.
+fn sum_fibonacci_biguint<I: Iterator<Item = u64>>(fis: impl Fn() -> I) -> BigUint {
+    let Some(max_fi) = fis().max() else {
+        return BigUint::zero();
+    };
+
+    // Warm the contiguous cache up to the largest index once, then sum by slice indexing under a single read lock
+    // instead of cloning an Arc for every index.
+    memoized_slow_fibonacci_biguint_iterative(max_fi);
+    {
+        let fibonacci_cache = FIBONACCI_BIGUINT_CACHE
+            .read()
+            .expect("Failed to read Fibonacci BigUint cache");
+        if (max_fi as usize) < fibonacci_cache.len() {
+            return fis().fold(BigUint::zero(), |acc, fi| {
+                acc + &*fibonacci_cache[fi as usize]
+            });
+        }
+    }
+
+    // The cache was cleared by another thread in the meantime, so look up each index on its own
+    fis().fold(BigUint::zero(), |acc, fi| {
+        acc + &*memoized_slow_fibonacci_biguint_iterative(fi)
+        // TODO: investigate ways we can get the lower memory usage of the cached fast doubling Fibonacci algorithm but the speed of the cached slow Fibonacci algorithm. As of now, the cached fast doubling Fibonacci algorithm is slower at decompression than the cached slow Fibonacci algorithm at large data inputs, on the order of > 10kB. See the comments in scripts/poll_rss.sh for more information.
+        // acc + &*fast_doubling_fibonacci_biguint(*fi)
+    })
+}
+
+/// Converts packed ezba bits to the [`BigUint`] they represent, visiting the use bits with [`ezla_indices`] instead of
+/// collecting an ezla and a zla.
+pub(crate) fn ezba_bits_to_biguint(ezba_bits: &EzbaBits) -> BigUint {
+    sum_fibonacci_biguint(|| ezla_indices(ezba_bits.iter()).map(efi_to_fi))
+}
+
+/// Checks that a list of Fibonacci indices is a valid descending Zeckendorf list: every index is at least 2, and the
+/// indices are strictly descending with no two consecutive indices.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{ZeckError, validate_zeckendorf_list_descending};
+/// assert_eq!(validate_zeckendorf_list_descending(&[]), Ok(()));
+/// assert_eq!(validate_zeckendorf_list_descending(&[6, 4, 2]), Ok(()));
+/// assert_eq!(validate_zeckendorf_list_descending(&[6, 1]), Err(ZeckError::InvalidFibonacciIndex { fi: 1 }));
+/// assert_eq!(validate_zeckendorf_list_descending(&[4, 6]), Err(ZeckError::NotDescending { previous: 4, next: 6 }));
+/// assert_eq!(validate_zeckendorf_list_descending(&[6, 5]), Err(ZeckError::ConsecutiveIndices { previous: 6, next: 5 }));
+/// ```
+pub fn validate_zeckendorf_list_descending(zld: &[u64]) -> Result<(), ZeckError> {
+    if let Some(&fi) = zld.iter().find(|&&fi| fi < 2) {
+        return Err(ZeckError::InvalidFibonacciIndex { fi });
+    }
+
+    for pair in zld.windows(2) {
+        let (previous, next) = (pair[0], pair[1]);
+        if next >= previous {
+            return Err(ZeckError::NotDescending { previous, next });
+        }
+        if next == previous - 1 {
+            return Err(ZeckError::ConsecutiveIndices { previous, next });
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a descending Zeckendorf List to a [`BigUint`], rejecting lists that are not valid Zeckendorf representations.
+///
+/// This is the strict counterpart of [`zl_to_biguint`], using [`validate_zeckendorf_list_descending`] to reject
+/// indices below 2 and lists that are not strictly descending or that contain consecutive indices.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{ZeckError, zl_to_biguint_checked};
+/// # use num_bigint::BigUint;
+/// assert_eq!(zl_to_biguint_checked(&[6, 4, 2]), Ok(BigUint::from(12u64)));
+/// assert_eq!(zl_to_biguint_checked(&[0]), Err(ZeckError::InvalidFibonacciIndex { fi: 0 }));
+/// ```
+pub fn zl_to_biguint_checked(zld: &[u64]) -> Result<BigUint, ZeckError> {
+    validate_zeckendorf_list_descending(zld)?;
+    Ok(zl_to_biguint(zld))
+}
+
+/// Normalizes any list of Fibonacci indices into the canonical descending Zeckendorf list with the same sum.
+///
+/// The list may be in any order and may contain duplicate or consecutive indices. Carries are applied with the
+/// Fibonacci recurrence until no index repeats and no two indices are consecutive:
+///
+/// - `F(k) + F(k+1) = F(k+2)`
+/// - `2F(k) = F(k+1) + F(k-2)` for `k >= 4`, with `2F(2) = F(3)` and `2F(3) = F(4) + F(2)` at the bottom
+///
+/// Index 0 is dropped because `F(0) = 0`, and index 1 is treated as index 2 because `F(1) = F(2) = 1`.
+/// This makes it possible to do arithmetic directly on Zeckendorf lists, e.g. adding two numbers by concatenating their
+/// lists and canonicalizing the result.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::canonicalize_zeckendorf_list;
+/// assert_eq!(canonicalize_zeckendorf_list(&[3, 4]), vec![5]); // 2 + 3 = 5
+/// assert_eq!(canonicalize_zeckendorf_list(&[2, 2]), vec![3]); // 1 + 1 = 2
+/// assert_eq!(canonicalize_zeckendorf_list(&[6, 6]), vec![7, 4]); // 8 + 8 = 13 + 3
+/// assert_eq!(canonicalize_zeckendorf_list(&[2, 4, 6]), vec![6, 4, 2]);
+/// assert_eq!(canonicalize_zeckendorf_list(&[0, 1]), vec![2]);
+/// ```
+#[wasm_bindgen]
+pub fn canonicalize_zeckendorf_list(zl: &[u64]) -> Vec<u64> {
+    // Counts per Fibonacci index, kept sparse because indices can be very large.
+    let mut counts: BTreeMap<u64, u64> = BTreeMap::new();
+    for &fi in zl {
+        match fi {
+            0 => {}
+            1 => add_fibonacci_index(&mut counts, 2),
+            _ => add_fibonacci_index(&mut counts, fi),
+        }
+    }
+
+    let mut scan_from = 2;
+    while let Some(fi) = first_non_canonical_index(&counts, scan_from) {
+        if counts[&fi] >= 2 {
+            remove_fibonacci_index(&mut counts, fi);
+            remove_fibonacci_index(&mut counts, fi);
+            match fi {
+                2 => add_fibonacci_index(&mut counts, 3),
+                3 => {
+                    add_fibonacci_index(&mut counts, 4);
+                    add_fibonacci_index(&mut counts, 2);
+                }
+                _ => {
+                    add_fibonacci_index(&mut counts, fi + 1);
+                    add_fibonacci_index(&mut counts, fi - 2);
+                }
+            }
+        } else {
+            remove_fibonacci_index(&mut counts, fi);
+            remove_fibonacci_index(&mut counts, fi + 1);
+            add_fibonacci_index(&mut counts, fi + 2);
+        }
+        // A carry at fi only touches indices from fi - 2 to fi + 2, so only pairs starting at fi - 3 can have changed.
+        scan_from = fi.saturating_sub(3).max(2);
+    }
+
+    counts.into_keys().rev().collect()
+}
+
+/// Adds two Zeckendorf lists directly, without converting them to [`BigUint`]s and back.
+///
+/// The lists are merged and normalized with [`canonicalize_zeckendorf_list`], which avoids the Fibonacci summation of
+/// [`zl_to_biguint`] and the greedy decomposition of [`memoized_zeckendorf_list_descending_for_biguint`]. Like
+/// [`canonicalize_zeckendorf_list`], the inputs do not have to be canonical, and the result is a canonical descending
+/// Zeckendorf list.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeckendorf_add;
+/// assert_eq!(zeckendorf_add(&[6, 4, 2], &[]), vec![6, 4, 2]); // 12 + 0 = 12
+/// assert_eq!(zeckendorf_add(&[6, 4, 2], &[5]), vec![7, 4, 2]); // 12 + 5 = 17 = 13 + 3 + 1
+/// assert_eq!(zeckendorf_add(&[4], &[5]), vec![6]); // 3 + 5 = 8
+/// ```
+#[wasm_bindgen]
+pub fn zeckendorf_add(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let merged: Vec<u64> = a.iter().chain(b).copied().collect();
+    canonicalize_zeckendorf_list(&merged)
+}
+
+/// Returns the lowest index at or above `scan_from` that repeats or is followed by the next consecutive index.
+fn first_non_canonical_index(counts: &BTreeMap<u64, u64>, scan_from: u64) -> Option<u64> {
+    let mut entries = counts.range(scan_from..).peekable();
+    while let Some((&fi, &count)) = entries.next() {
+        let next_is_consecutive = entries.peek().is_some_and(|&(&next, _)| next == fi + 1);
+        if count >= 2 || next_is_consecutive {
+            return Some(fi);
+        }
+    }
+    None
+}
+
+fn add_fibonacci_index(counts: &mut BTreeMap<u64, u64>, fi: u64) {
+    *counts.entry(fi).or_insert(0) += 1;
+}
+
+fn remove_fibonacci_index(counts: &mut BTreeMap<u64, u64>, fi: u64) {
+    let count = counts
+        .get_mut(&fi)
+        .expect("Removed Fibonacci index must be present");
+    *count -= 1;
+    if *count == 0 {
+        counts.remove(&fi);
+    }
+}
+
+/// Creates an "all ones Zeckendorf number", or AOZN, by creating an Effective Zeckendorf Bits Ascending (EZBA)
+/// with `n` consecutive ones, then converting it to a [`BigUint`].
+///
+/// An AOZN is created by generating a Zeckendorf representation with `n`
+/// consecutive ones (in the Effective Zeckendorf Bits Ascending format), then converting that
+/// representation back to the actual number value. This is useful for understanding how Zeckendorf
+/// representations behave when they contain many ones.
+///
+/// # Arguments
+///
+/// * `n` - The number of consecutive ones in the EZBA representation
+///
+/// # Returns
+///
+/// Returns [`BigUint::zero()`] if `n` is 0, otherwise returns the [`BigUint`] value of the all-ones
+/// Zeckendorf representation.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::all_ones_zeckendorf_to_biguint;
+/// # use num_bigint::BigUint;
+/// # use num_traits::Zero;
+/// assert_eq!(all_ones_zeckendorf_to_biguint(0), BigUint::zero());
+/// assert_eq!(all_ones_zeckendorf_to_biguint(1), BigUint::from(1u64)); // 1
+/// assert_eq!(all_ones_zeckendorf_to_biguint(2), BigUint::from(4u64)); // 1 + 3
+/// assert_eq!(all_ones_zeckendorf_to_biguint(3), BigUint::from(12u64)); // 1 + 3 + 8
+/// assert_eq!(all_ones_zeckendorf_to_biguint(4), BigUint::from(33u64)); // 1 + 3 + 8 + 21
+/// ```
+pub fn all_ones_zeckendorf_to_biguint(n: usize) -> BigUint {
+    if n == 0 {
+        return BigUint::zero();
     }
     let ezba = vec![1u8; n];
     let ezla = ezba_to_ezla(&ezba);
@@ -1211,6 +2709,45 @@ pub fn all_ones_zeckendorf_to_biguint(n: usize) -> BigUint {
     zl_to_biguint(&zla)
 }
 
+/// Creates the same "all ones Zeckendorf number" as [`all_ones_zeckendorf_to_biguint`] without building its EZBA.
+///
+/// The `n` use bits of an AOZN stand for the even-indexed Fibonacci numbers F(2), F(4), ..., F(2n), and the sum of
+/// those is F(2n + 1) - 1, so this takes a single [`fast_doubling_fibonacci_biguint`] call however large `n` is.
+///
+/// # Panics
+///
+/// Panics if `2n + 1` does not fit in a [`u64`] Fibonacci index, which only happens for `n` of 2^63 and above, far
+/// beyond any AOZN that fits in memory.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{all_ones_zeckendorf_to_biguint, all_ones_zeckendorf_to_biguint_fast};
+/// # use num_bigint::BigUint;
+/// assert_eq!(all_ones_zeckendorf_to_biguint_fast(4), BigUint::from(33u64)); // 1 + 3 + 8 + 21
+/// assert_eq!(
+///     all_ones_zeckendorf_to_biguint_fast(100),
+///     all_ones_zeckendorf_to_biguint(100)
+/// );
+/// ```
+pub fn all_ones_zeckendorf_to_biguint_fast(n: usize) -> BigUint {
+    all_ones_zeckendorf_to_biguint_fast_with(n, fast_doubling_fibonacci_biguint)
+}
+
+/// Implements [`all_ones_zeckendorf_to_biguint_fast`], computing Fibonacci numbers with `fibonacci` so tests can count
+/// the calls.
+fn all_ones_zeckendorf_to_biguint_fast_with(
+    n: usize,
+    mut fibonacci: impl FnMut(u64) -> Arc<BigUint>,
+) -> BigUint {
+    let fi = u64::try_from(n)
+        .ok()
+        .and_then(|n| n.checked_mul(2))
+        .and_then(|twice_n| twice_n.checked_add(1))
+        .expect("The Fibonacci index 2n + 1 of the AOZN overflows u64");
+    fibonacci(fi).as_ref() - 1u32
+}
+
 /// Decompresses a slice of bytes compressed using the Zeckendorf algorithm, assuming the original data was compressed using the big endian bytes interpretation.
 ///
 /// Assume the original input data was interpreted as a big endian integer, for now. See the TODO in the [`padless_zeckendorf_compress_be_dangerous`] function for more information.
@@ -1220,6 +2757,8 @@ pub fn all_ones_zeckendorf_to_biguint(n: usize) -> BigUint {
 /// **This function does not pad leading zero bytes.** If the original data had leading zeros, they will not be restored.
 /// The decompressed output will be the minimal representation of the number (without leading zeros).
 ///
+/// If you retained the original size, use [`zeckendorf_decompress_be_with_size`] to restore the leading zeros.
+///
 /// For a format that automatically handles size preservation and padding, use [`crate::zeck_file_format::file::deserialize_zeck_file`]
 /// and [`crate::zeck_file_format::decompress::decompress_zeck_file`] instead, which includes a header with the original size information and restores leading zeros.
 ///
@@ -1240,17 +2779,11 @@ pub fn all_ones_zeckendorf_to_biguint(n: usize) -> BigUint {
 /// ```
 #[wasm_bindgen]
 pub fn padless_zeckendorf_decompress_be_dangerous(compressed_data: &[u8]) -> Vec<u8> {
-    // Unpack the compressed data into bits
-    let compressed_data_as_bits = unpack_bytes_to_ezba_bits(compressed_data);
+    // Wrap the compressed data as packed bits
+    let compressed_data_as_bits = EzbaBits::from_packed_bytes(compressed_data);
     // println!("Compressed data as bits: {:?}", compressed_data_as_bits);
-    // Unpack the bits into an ezla (Effective Zeckendorf List Ascending)
-    let compressed_data_as_ezla = ezba_to_ezla(&compressed_data_as_bits);
-    // println!("Compressed data as ezla: {:?}", compressed_data_as_ezla);
-    // Convert the ezla to a zla (Zeckendorf List Ascending)
-    let compressed_data_as_zla = ezl_to_zl(&compressed_data_as_ezla);
-    // println!("Compressed data as zla: {:?}", compressed_data_as_zla);
-    // Convert the zla to a biguint
-    let compressed_data_as_biguint = zl_to_biguint(&compressed_data_as_zla);
+    // Sum the Fibonacci numbers of the used effective Fibonacci indices, without collecting them into a list
+    let compressed_data_as_biguint = ezba_bits_to_biguint(&compressed_data_as_bits);
     // println!("Compressed data as biguint: {:?}", compressed_data_as_biguint);
     compressed_data_as_biguint.to_bytes_be()
 }
@@ -1262,6 +2795,8 @@ pub fn padless_zeckendorf_decompress_be_dangerous(compressed_data: &[u8]) -> Vec
 /// **This function does not pad leading zero bytes.** If the original data had leading zeros, they will not be restored.
 /// The decompressed output will be the minimal representation of the number (without leading zeros).
 ///
+/// If you retained the original size, use [`zeckendorf_decompress_le_with_size`] to restore the trailing zeros.
+///
 /// For a format that automatically handles size preservation and padding, use [`crate::zeck_file_format::file::deserialize_zeck_file`]
 /// and [`crate::zeck_file_format::decompress::decompress_zeck_file`] instead, which includes a header with the original size information and restores leading zeros.
 ///
@@ -1282,21 +2817,78 @@ pub fn padless_zeckendorf_decompress_be_dangerous(compressed_data: &[u8]) -> Vec
 /// ```
 #[wasm_bindgen]
 pub fn padless_zeckendorf_decompress_le_dangerous(compressed_data: &[u8]) -> Vec<u8> {
-    // Unpack the compressed data into bits
-    let compressed_data_as_bits = unpack_bytes_to_ezba_bits(compressed_data);
+    // Wrap the compressed data as packed bits
+    let compressed_data_as_bits = EzbaBits::from_packed_bytes(compressed_data);
     // println!("Compressed data as bits: {:?}", compressed_data_as_bits);
-    // Unpack the bits into an ezla (Effective Zeckendorf List Ascending)
-    let compressed_data_as_ezla = ezba_to_ezla(&compressed_data_as_bits);
-    // println!("Compressed data as ezla: {:?}", compressed_data_as_ezla);
-    // Convert the ezla to a zla (Zeckendorf List Ascending)
-    let compressed_data_as_zla = ezl_to_zl(&compressed_data_as_ezla);
-    // println!("Compressed data as zla: {:?}", compressed_data_as_zla);
-    // Convert the zla to a biguint
-    let compressed_data_as_biguint = zl_to_biguint(&compressed_data_as_zla);
+    // Sum the Fibonacci numbers of the used effective Fibonacci indices, without collecting them into a list
+    let compressed_data_as_biguint = ezba_bits_to_biguint(&compressed_data_as_bits);
     // println!("Compressed data as biguint: {:?}", compressed_data_as_biguint);
     compressed_data_as_biguint.to_bytes_le()
 }
 
+/// Decompresses a slice of bytes compressed using the Zeckendorf algorithm, assuming the original data was compressed using the big endian bytes interpretation,
+/// and restores any leading zero bytes by padding the output to `original_size`.
+///
+/// This is the size-aware counterpart of [`padless_zeckendorf_decompress_be_dangerous`]. The padless compression strips leading
+/// zero bytes, so the caller must retain the original size (e.g., `data.len()`) and pass it here to recover the exact original data.
+///
+/// If the decompressed data is already `original_size` bytes or longer, it is returned unchanged.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{padless_zeckendorf_compress_be_dangerous, zeckendorf_decompress_be_with_size};
+/// let data = vec![0, 5];
+/// let compressed = padless_zeckendorf_compress_be_dangerous(&data);
+/// assert_eq!(zeckendorf_decompress_be_with_size(&compressed, data.len()), data);
+/// ```
+#[wasm_bindgen]
+pub fn zeckendorf_decompress_be_with_size(compressed_data: &[u8], original_size: usize) -> Vec<u8> {
+    let decompressed = padless_zeckendorf_decompress_be_dangerous(compressed_data);
+    if decompressed.len() >= original_size {
+        return decompressed;
+    }
+
+    let mut padded = vec![0u8; original_size - decompressed.len()];
+    padded.extend_from_slice(&decompressed);
+    padded
+}
+
+/// Decompresses a slice of bytes compressed using the Zeckendorf algorithm, assuming the original data was compressed using the little endian bytes interpretation,
+/// and restores any trailing zero bytes by padding the output to `original_size`.
+///
+/// This is the size-aware counterpart of [`padless_zeckendorf_decompress_le_dangerous`]. In the little endian interpretation, the most significant
+/// bytes are at the end of the data, so the stripped zero bytes are restored at the end of the output.
+///
+/// If the decompressed data is already `original_size` bytes or longer, it is returned unchanged.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{padless_zeckendorf_compress_le_dangerous, zeckendorf_decompress_le_with_size};
+/// let data = vec![5, 0];
+/// let compressed = padless_zeckendorf_compress_le_dangerous(&data);
+/// assert_eq!(zeckendorf_decompress_le_with_size(&compressed, data.len()), data);
+/// ```
+#[wasm_bindgen]
+pub fn zeckendorf_decompress_le_with_size(compressed_data: &[u8], original_size: usize) -> Vec<u8> {
+    let mut decompressed = padless_zeckendorf_decompress_le_dangerous(compressed_data);
+    if decompressed.len() < original_size {
+        decompressed.resize(original_size, 0u8);
+    }
+    decompressed
+}
+
 /// Attempts to compress the input data using both big endian and little endian interpretations,
 /// and returns the best result.
 ///
@@ -1311,6 +2903,9 @@ pub fn padless_zeckendorf_decompress_le_dangerous(compressed_data: &[u8]) -> Vec
 /// decompressed data with leading zeros to restore the exact original data. Without the original
 /// size, information will be lost during decompression.
 ///
+/// All-zero input of any length, including empty input, is the integer zero, which compresses to a single skip bit
+/// (`[0]`) and decompresses to a single zero byte, so only the original size can restore its length.
+///
 /// For a format that automatically handles size preservation, use [`crate::zeck_file_format::compress::compress_zeck_be`]
 /// instead, which includes a header with the original size information.
 ///
@@ -1364,3 +2959,973 @@ pub fn padless_zeckendorf_compress_best_dangerous(data: &[u8]) -> PadlessCompres
         PadlessCompressionResult::Neither { be_size, le_size }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress_if_fibonacci_matches_general_compression_for_fibonacci_numbers() {
+        for fi in [2, 3, 4, 10, 11, 12, 93, 94, 500, 1_001] {
+            // Arrange
+            let fibonacci = fast_doubling_fibonacci_biguint(fi);
+
+            // Act
+            let compressed = compress_if_fibonacci(&fibonacci);
+
+            // Assert
+            assert_eq!(
+                compressed,
+                Some(padless_zeckendorf_compress_be_dangerous(
+                    &fibonacci.to_bytes_be()
+                )),
+                "fi: {fi}"
+            );
+        }
+    }
+
+    #[test]
+    fn compress_if_fibonacci_returns_none_for_other_integers() {
+        for fi in [5, 12, 94, 500] {
+            // Arrange
+            let fibonacci = fast_doubling_fibonacci_biguint(fi);
+            let non_fibonacci_numbers = [&*fibonacci - 1u64, &*fibonacci + 1u64];
+
+            for n in non_fibonacci_numbers {
+                // Act
+                let compressed = compress_if_fibonacci(&n);
+
+                // Assert
+                assert_eq!(compressed, None, "n: {n}");
+            }
+        }
+        assert_eq!(compress_if_fibonacci(&BigUint::zero()), None);
+    }
+
+    #[test]
+    fn fibonacci_iter_yields_the_fibonacci_sequence() {
+        // Arrange
+        let expected: [u64; 20] = [
+            0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181,
+        ];
+
+        // Act
+        let fibonacci_numbers: Vec<BigUint> = fibonacci_iter()
+            .take(20)
+            .map(|fibonacci| (*fibonacci).clone())
+            .collect();
+
+        // Assert
+        assert_eq!(fibonacci_numbers, expected.map(BigUint::from));
+    }
+
+    #[test]
+    fn u64_zeckendorf_lists_near_the_largest_fibonacci_index() {
+        let f92 = memoized_fibonacci_u64(92);
+        let f93 = memoized_fibonacci_u64(MAX_U64_FIBONACCI_INDEX);
+        assert_eq!(f93, 12_200_160_415_121_876_738);
+
+        let f64_value = memoized_fibonacci_u64(64);
+        for n in [
+            f64_value + 1,
+            f92 - 1,
+            f92,
+            f92 + 1,
+            f93 - 1,
+            f93,
+            f93 + 1,
+            u64::MAX,
+        ] {
+            // Act
+            let zeckendorf_list = memoized_zeckendorf_list_descending_for_integer(n);
+
+            // Assert
+            assert_eq!(zl_to_biguint(&zeckendorf_list), BigUint::from(n), "n: {n}");
+            assert_eq!(
+                validate_zeckendorf_list_descending(&zeckendorf_list),
+                Ok(()),
+                "n: {n}"
+            );
+            assert!(zeckendorf_list[0] <= MAX_U64_FIBONACCI_INDEX, "n: {n}");
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn memoized_fibonacci_u64_computes_index_93_without_overflow() {
+        // Act
+        let f93 = memoized_fibonacci_u64(MAX_U64_FIBONACCI_INDEX);
+
+        // Assert
+        assert_eq!(f93, 12_200_160_415_121_876_738);
+        assert_eq!(
+            BigUint::from(f93),
+            *fast_doubling_fibonacci_biguint(MAX_U64_FIBONACCI_INDEX)
+        );
+        assert_eq!(
+            memoized_slow_fibonacci_recursive(MAX_U64_FIBONACCI_INDEX),
+            f93
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Fibonacci index 94 overflows u64")]
+    fn fibonacci_index_94_overflows_u64() {
+        memoized_fibonacci_u64(MAX_U64_FIBONACCI_INDEX + 1);
+    }
+
+    #[test]
+    fn fibonacci_matches_fast_doubling_for_sequential_and_sparse_indices() {
+        // Arrange
+        let sequential_indices = 0..=200;
+        let sparse_indices = [
+            1_000,
+            FIBONACCI_CONTIGUOUS_CACHE_LIMIT - 1,
+            FIBONACCI_CONTIGUOUS_CACHE_LIMIT,
+            20_000,
+        ];
+
+        for fi in sequential_indices.chain(sparse_indices) {
+            // Act
+            let fibonacci_value = fibonacci(fi);
+
+            // Assert
+            assert_eq!(
+                fibonacci_value,
+                fast_doubling_fibonacci_biguint(fi),
+                "fi: {fi}"
+            );
+        }
+    }
+
+    #[test]
+    fn packing_the_ezba_matches_the_padless_compression() {
+        for seed in 0..200 {
+            // Arrange
+            let data = test_data::generate_random_data((seed % 48) as usize, seed);
+
+            // Act
+            let ezba = zeckendorf_ezba_be(&data);
+
+            // Assert
+            assert!(ezba.iter().all(|&bit| bit <= 1), "seed: {seed}");
+            assert_eq!(
+                pack_ezba_bits_to_bytes(&ezba),
+                padless_zeckendorf_compress_be_dangerous(&data),
+                "seed: {seed}"
+            );
+            assert_eq!(
+                decompress_from_ezba(&ezba),
+                padless_zeckendorf_decompress_be_dangerous(&pack_ezba_bits_to_bytes(&ezba)),
+                "seed: {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn fibonacci_index_bracket_is_tight_for_a_large_input() {
+        // Arrange
+        let n = BigUint::one() << 5000u32;
+
+        let mut max_looked_up_fi = 0;
+
+        // Act
+        let (low, high) = fibonacci_index_bracket(&n);
+        let fi = smallest_fibonacci_index_at_least_with(&n, |fi| {
+            max_looked_up_fi = max_looked_up_fi.max(fi);
+            fast_doubling_fibonacci_biguint(fi)
+        });
+
+        // Assert
+        // The search only looks up Fibonacci numbers up to high, so the cache grows to just past the needed index
+        assert!(low <= fi && fi <= high, "fi: {fi}, bracket: {low}..={high}");
+        assert!(high - fi <= 3, "fi: {fi}, high: {high}");
+        assert!(
+            max_looked_up_fi <= high,
+            "max looked up fi: {max_looked_up_fi}, high: {high}"
+        );
+        assert_eq!(smallest_fibonacci_index_at_least(&n), fi);
+        assert!(*memoized_slow_fibonacci_biguint_iterative(fi - 1) < n);
+        assert!(*memoized_slow_fibonacci_biguint_iterative(fi) >= n);
+        assert_eq!(smallest_fibonacci_index_at_least_by_bit_length(&n), fi);
+        assert_eq!(
+            memoized_zeckendorf_list_descending_for_biguint(&n)[0],
+            fi - 1
+        );
+    }
+
+    #[test]
+    fn fibonacci_index_bracket_contains_the_answer_for_small_values() {
+        for value in 0u64..=10_000 {
+            // Arrange
+            let value = BigUint::from(value);
+
+            // Act
+            let (low, high) = fibonacci_index_bracket(&value);
+
+            // Assert
+            let fi = smallest_fibonacci_index_at_least(&value);
+            assert!(
+                low <= fi && fi <= high,
+                "value: {value}, fi: {fi}, bracket: {low}..={high}"
+            );
+        }
+    }
+
+    #[test]
+    fn bit_length_search_matches_the_exact_search_for_many_values() {
+        // Arrange
+        let small_values = (0u64..=1_000).map(BigUint::from);
+        let values_around_fibonacci_numbers = (1u64..=500).flat_map(|fi| {
+            let fibonacci_value = memoized_slow_fibonacci_biguint_iterative(fi);
+            [
+                fibonacci_value.as_ref() - 1u8,
+                fibonacci_value.as_ref().clone(),
+                fibonacci_value.as_ref() + 1u8,
+            ]
+        });
+        let random_values = (0..500).map(|seed| {
+            BigUint::from_bytes_be(&test_data::generate_random_data((seed % 64) as usize, seed))
+        });
+
+        for value in small_values
+            .chain(values_around_fibonacci_numbers)
+            .chain(random_values)
+        {
+            // Act
+            let fi = smallest_fibonacci_index_at_least_by_bit_length(&value);
+
+            // Assert
+            assert_eq!(
+                fi,
+                smallest_fibonacci_index_at_least(&value),
+                "value: {value}"
+            );
+        }
+    }
+
+    #[test]
+    fn bit_length_search_agrees_across_threads_extending_the_cache() {
+        // Arrange
+        let targets: Vec<BigUint> = (0..8u32)
+            .map(|thread| (BigUint::one() << (3_000 + 97 * thread)) + thread)
+            .collect();
+
+        // Act
+        let results: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = targets
+                .iter()
+                .map(|target| {
+                    scope.spawn(move || smallest_fibonacci_index_at_least_by_bit_length(target))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        // Assert
+        for (target, fi) in targets.iter().zip(results) {
+            assert_eq!(fi, smallest_fibonacci_index_at_least(target));
+        }
+    }
+
+    #[test]
+    fn all_ones_zeckendorf_numbers_have_density_one() {
+        for n in 1..=64 {
+            assert_eq!(
+                zeckendorf_density(&all_ones_zeckendorf_to_biguint(n)),
+                1.0,
+                "n: {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn all_ones_zeckendorf_to_biguint_fast_matches_the_ezba_construction() {
+        for n in 0..=46 {
+            assert_eq!(
+                all_ones_zeckendorf_to_biguint_fast(n),
+                all_ones_zeckendorf_to_biguint(n),
+                "n: {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn all_ones_zeckendorf_to_biguint_fast_computes_a_single_fibonacci_number() {
+        for n in [0, 1, 46, 10_000] {
+            // Arrange
+            let mut looked_up_fis = Vec::new();
+
+            // Act
+            let aozn = all_ones_zeckendorf_to_biguint_fast_with(n, |fi| {
+                looked_up_fis.push(fi);
+                fast_doubling_fibonacci_biguint(fi)
+            });
+
+            // Assert
+            assert_eq!(looked_up_fis, vec![2 * n as u64 + 1], "n: {n}");
+            assert_eq!(aozn, all_ones_zeckendorf_to_biguint_fast(n), "n: {n}");
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    #[should_panic(expected = "overflows u64")]
+    fn all_ones_zeckendorf_to_biguint_fast_panics_when_the_fibonacci_index_overflows() {
+        // Arrange
+        let n = usize::MAX;
+
+        // Act
+        all_ones_zeckendorf_to_biguint_fast_with(n, |_| unreachable!());
+    }
+
+    #[test]
+    fn single_fibonacci_numbers_have_low_density() {
+        // Arrange
+        let n = memoized_slow_fibonacci_biguint_iterative(200u64);
+
+        // Act
+        let density = zeckendorf_density(&n);
+
+        // Assert
+        // F(200) is EFI 198, so its EZBA is 198 skip bits followed by one use bit
+        assert_eq!(density, 1.0 / 199.0);
+        assert!(density < zeckendorf_density(&(n.as_ref() - 1u64)));
+    }
+
+    #[test]
+    fn zeckendorf_add_matches_biguint_addition_for_random_pairs() {
+        for seed in 0..200 {
+            // Arrange
+            let a = zeckendorf_list_descending_for_bytes_be(&test_data::generate_random_data(
+                (seed % 24) as usize,
+                seed,
+            ));
+            let b = zeckendorf_list_descending_for_bytes_be(&test_data::generate_random_data(
+                (seed % 17) as usize,
+                seed + 1_000,
+            ));
+
+            // Act
+            let sum = zeckendorf_add(&a, &b);
+
+            // Assert
+            assert_eq!(
+                zl_to_biguint(&sum),
+                zl_to_biguint(&a) + zl_to_biguint(&b),
+                "seed: {seed}"
+            );
+            assert_eq!(
+                validate_zeckendorf_list_descending(&sum),
+                Ok(()),
+                "seed: {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalize_zeckendorf_list_merges_consecutive_indices() {
+        // Arrange
+        let zl = [3, 4];
+
+        // Act
+        let canonical = canonicalize_zeckendorf_list(&zl);
+
+        // Assert
+        assert_eq!(canonical, vec![5]);
+        assert_eq!(zl_to_biguint(&canonical), zl_to_biguint(&zl));
+    }
+
+    #[test]
+    fn canonicalize_zeckendorf_list_matches_zeckendorf_list_of_sum() {
+        for n in 0u64..300 {
+            // Arrange
+            let zld = memoized_zeckendorf_list_descending_for_integer(n);
+            // Every index doubled, plus the noncanonical pair F(1) + F(2) for another 2
+            let zl: Vec<u64> = zld.iter().chain(&zld).copied().chain([1, 2]).collect();
+
+            // Act
+            let canonical = canonicalize_zeckendorf_list(&zl);
+
+            // Assert
+            assert_eq!(
+                canonical,
+                memoized_zeckendorf_list_descending_for_integer(2 * n + 2),
+                "n: {n}"
+            );
+            assert_eq!(
+                validate_zeckendorf_list_descending(&canonical),
+                Ok(()),
+                "n: {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn for_each_ezla_index_matches_ezba_to_ezla() {
+        // Arrange
+        let ezba = unpack_bytes_to_ezba_bits(&padless_zeckendorf_compress_be_dangerous(&[
+            0x12, 0x34, 0x56, 0x78, 0x9A,
+        ]));
+
+        // Act
+        let mut indices = Vec::new();
+        for_each_ezla_index(&ezba, |efi| indices.push(efi));
+
+        // Assert
+        assert_eq!(indices, ezba_to_ezla(&ezba));
+    }
+
+    #[test]
+    fn try_fi_to_efi_is_none_below_two() {
+        for fi in [0u64, 1] {
+            // Act
+            let efi = try_fi_to_efi(fi);
+            let efi_ref = try_fi_to_efi_ref(&fi);
+            let efi_biguint = try_fi_to_efi_biguint(BigUint::from(fi));
+
+            // Assert
+            assert_eq!(efi, None, "fi: {fi}");
+            assert_eq!(efi_ref, None, "fi: {fi}");
+            assert_eq!(efi_biguint, None, "fi: {fi}");
+        }
+    }
+
+    #[test]
+    fn unpack_bytes_to_ezba_bits_matches_scalar_for_every_byte() {
+        // Arrange
+        let bytes: Vec<u8> = (0..=255u8).collect();
+
+        // Act
+        let fast = unpack_bytes_to_ezba_bits(&bytes);
+        let scalar = unpack_bytes_to_ezba_bits_scalar(&bytes);
+
+        // Assert
+        assert_eq!(fast, scalar);
+    }
+
+    #[test]
+    fn pack_ezba_bits_to_bytes_matches_scalar() {
+        // Arrange
+        let ezba: Vec<u8> = (0..1_003u32).map(|i| ((i * 7 + i / 3) % 2) as u8).collect();
+
+        for len in [0, 1, 7, 8, 9, 63, 64, 65, 1_003] {
+            // Act
+            let fast = pack_ezba_bits_to_bytes(&ezba[..len]);
+            let scalar = pack_ezba_bits_to_bytes_scalar(&ezba[..len]);
+
+            // Assert
+            assert_eq!(fast, scalar, "len: {len}");
+        }
+    }
+
+    #[test]
+    fn pack_ezba_bits_to_bytes_matches_scalar_for_compressed_data() {
+        // Arrange
+        let data: Vec<u8> = (0..256u32).map(|i| (i * 31 % 256) as u8).collect();
+        let ezld = zl_to_ezl(&memoized_zeckendorf_list_descending_for_biguint(
+            &BigUint::from_bytes_be(&data),
+        ));
+        let ezba = ezba_from_ezld(&ezld);
+
+        // Act
+        let fast = pack_ezba_bits_to_bytes(&ezba);
+        let scalar = pack_ezba_bits_to_bytes_scalar(&ezba);
+
+        // Assert
+        assert_eq!(fast, scalar);
+    }
+
+    #[test]
+    fn ezba_bits_from_ezld_matches_hand_computed_ezbas() {
+        // Each use bit skips the bit of the next effective Fibonacci index, which is never written
+        let cases: [(&[u64], &[u8]); 7] = [
+            (&[], &[0]),
+            (&[0], &[1]),
+            (&[1], &[0, 1]),
+            (&[3], &[0, 0, 0, 1]),
+            // 12 = 8 + 3 + 1
+            (&[4, 2, 0], &[1, 1, 1]),
+            // 10 = 8 + 2
+            (&[4, 1], &[0, 1, 0, 1]),
+            // 27 = 21 + 5 + 1
+            (&[6, 3, 0], &[1, 0, 1, 0, 1]),
+        ];
+
+        for (ezld, expected) in cases {
+            // Arrange
+            let ezba_bits = EzbaBits::from_ezld(ezld);
+
+            // Act
+            let ezba = ezba_bits.to_vec();
+
+            // Assert
+            assert_eq!(ezba, expected, "ezld: {ezld:?}");
+        }
+    }
+
+    #[test]
+    fn ezba_bits_packed_bytes_match_pack_ezba_bits_to_bytes() {
+        // Arrange
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 37 + 11) as u8).collect();
+        let ezld = zl_to_ezl(&memoized_zeckendorf_list_descending_for_biguint(
+            &BigUint::from_bytes_be(&data),
+        ));
+
+        // Act
+        let packed = EzbaBits::from_ezld(&ezld).into_packed_bytes();
+
+        // Assert
+        assert_eq!(packed, pack_ezba_bits_to_bytes(&ezba_from_ezld(&ezld)));
+    }
+
+    #[test]
+    fn incompressible_data_is_borrowed_not_copied() {
+        // Arrange
+        let data = [0xFF; 64];
+        let result = padless_zeckendorf_compress_best_dangerous(&data);
+
+        // Act
+        let stored = result.into_compressed_or_original(&data);
+
+        // Assert
+        assert!(matches!(stored, Cow::Borrowed(_)));
+        assert!(std::ptr::eq(stored.as_ref(), data.as_slice()));
+    }
+
+    #[test]
+    fn compressible_data_returns_owned_compressed_data() {
+        // Arrange
+        let data = [0, 0, 0, 1];
+        let result = padless_zeckendorf_compress_best_dangerous(&data);
+        let compressed_len = result.compressed_len();
+
+        // Act
+        let stored = result.into_compressed_or_original(&data);
+
+        // Assert
+        assert!(matches!(stored, Cow::Owned(_)));
+        assert_eq!(Some(stored.len()), compressed_len);
+    }
+
+    #[test]
+    fn compress_single_byte_matches_general_path_for_all_bytes() {
+        for byte in 0..=u8::MAX {
+            // Arrange
+            // A leading zero byte keeps the slice longer than one byte, so this takes the general BigUint path
+            let general = padless_zeckendorf_compress_be_dangerous(&[0, byte]);
+
+            // Act
+            let table = compress_single_byte(byte);
+
+            // Assert
+            assert_eq!(table, general, "byte: {byte}");
+            assert_eq!(
+                padless_zeckendorf_compress_le_dangerous(&[byte]),
+                general,
+                "byte: {byte}"
+            );
+        }
+    }
+
+    #[test]
+    fn compressed_value_bits_equal_ezba_length() {
+        for n in 1..5_000u64 {
+            // Arrange
+            let data = n.to_be_bytes();
+            let ezba = ezba_from_ezld(&zl_to_ezl(&zeckendorf_list_descending_for_bytes_be(&data)));
+
+            // Act
+            let value = compressed_value(&data);
+
+            // Assert
+            assert_eq!(value.bits(), ezba.len() as u64, "n: {n}");
+        }
+        assert_eq!(compressed_value(&[0]).bits(), 0);
+        assert_eq!(ezba_from_ezld(&[]), vec![SKIP_BIT]);
+        assert_eq!(compressed_value(&[4]), BigUint::from(3u8));
+    }
+
+    #[test]
+    fn ezba_from_ezld_accepts_compressed_data_ezlds() {
+        // Arrange
+        let data: Vec<u8> = (0..1_000u32).map(|i| (i * 91 + 7) as u8).collect();
+        let ezld = zl_to_ezl(&memoized_zeckendorf_list_descending_for_biguint(
+            &BigUint::from_bytes_le(&data),
+        ));
+
+        // Act
+        let ezba = ezba_from_ezld(&ezld);
+
+        // Assert
+        assert_eq!(
+            ezba.iter().filter(|&&bit| bit == USE_BIT).count(),
+            ezld.len()
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "The EZLD uses consecutive effective Fibonacci indices 3 and 2")]
+    fn ezba_from_ezld_rejects_consecutive_indices_in_debug_builds() {
+        ezba_from_ezld(&[3, 2]);
+    }
+
+    #[test]
+    fn ezba_bits_from_packed_bytes_matches_unpacked_ezla() {
+        // Arrange
+        let bytes: Vec<u8> = (0..=255u8).rev().collect();
+
+        // Act
+        let ezba_bits = EzbaBits::from_packed_bytes(&bytes);
+
+        // Assert
+        assert_eq!(ezba_bits.to_vec(), unpack_bytes_to_ezba_bits(&bytes));
+        assert_eq!(
+            ezba_bits.to_ezla(),
+            ezba_to_ezla(&unpack_bytes_to_ezba_bits(&bytes))
+        );
+    }
+
+    #[test]
+    fn ezba_bits_get_is_none_past_len() {
+        // Arrange
+        let mut ezba_bits = EzbaBits::new();
+        ezba_bits.push_skip();
+        ezba_bits.push_use();
+
+        // Act
+        let bits = [ezba_bits.get(0), ezba_bits.get(1), ezba_bits.get(2)];
+
+        // Assert
+        assert_eq!(bits, [Some(SKIP_BIT), Some(USE_BIT), None]);
+    }
+
+    #[test]
+    fn load_fibonacci_cache_rejects_truncated_file() {
+        // Arrange
+        let path = std::env::temp_dir().join(format!(
+            "zeck_fibonacci_cache_truncated_{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, [0, 0, 0, 0, 1, 0, 0, 0]).unwrap();
+
+        // Act
+        let result = load_fibonacci_cache(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        // Assert
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn fast_doubling_isolated_count_matches_bit_length_plus_popcount() {
+        for fi in [1u64, 2, 3, 7, 8, 100, 1_000, 65_535, 1 << 20] {
+            // Arrange
+            let expected = (64 - fi.leading_zeros() + fi.count_ones()) as usize;
+
+            // Act
+            let (fibonacci, cached_count) = fast_doubling_fibonacci_biguint_isolated(fi);
+
+            // Assert
+            assert_eq!(cached_count, expected, "fi: {fi}");
+            assert_eq!(fibonacci, fast_doubling_fibonacci_biguint(fi), "fi: {fi}");
+        }
+    }
+
+    #[test]
+    fn fast_doubling_isolated_count_for_zero_is_base_cases() {
+        // Act
+        let (fibonacci, cached_count) = fast_doubling_fibonacci_biguint_isolated(0);
+
+        // Assert
+        assert!(fibonacci.is_zero());
+        assert_eq!(cached_count, 2);
+    }
+
+    #[test]
+    fn fast_doubling_cache_snapshot_is_sorted_by_index() {
+        // Arrange
+        memoized_fast_doubling_fibonacci_biguint(1_000);
+
+        // Act
+        let snapshot = fast_doubling_cache_snapshot();
+
+        // Assert
+        assert!(snapshot.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(snapshot[0], (0, Arc::new(BigUint::zero())));
+        for (fi, value) in &snapshot {
+            assert_eq!(*value, fast_doubling_fibonacci_biguint(*fi), "fi: {fi}");
+        }
+    }
+
+    #[test]
+    fn compressed_byte_length_matches_compressed_output() {
+        for i in 0..2_000u32 {
+            // Arrange
+            let data: Vec<u8> = (0..(i % 40)).map(|j| (i * 31 + j * 17) as u8).collect();
+
+            // Act
+            let be_len = compressed_byte_length(&data, Endianness::Big);
+            let le_len = compressed_byte_length(&data, Endianness::Little);
+
+            // Assert
+            assert_eq!(
+                be_len,
+                padless_zeckendorf_compress_be_dangerous(&data).len(),
+                "data: {data:?}"
+            );
+            assert_eq!(
+                le_len,
+                padless_zeckendorf_compress_le_dangerous(&data).len(),
+                "data: {data:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn zl_to_biguint_checked_rejects_index_below_two() {
+        for zld in [&[0u64][..], &[1], &[5, 1], &[9, 0]] {
+            // Act
+            let result = zl_to_biguint_checked(zld);
+
+            // Assert
+            assert!(
+                matches!(result, Err(ZeckError::InvalidFibonacciIndex { .. })),
+                "zld: {zld:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn zl_to_biguint_checked_rejects_ascending_and_duplicate_indices() {
+        for (zld, previous, next) in [(&[2u64, 4][..], 2, 4), (&[6, 6], 6, 6), (&[9, 4, 7], 4, 7)] {
+            // Act
+            let result = zl_to_biguint_checked(zld);
+
+            // Assert
+            assert_eq!(result, Err(ZeckError::NotDescending { previous, next }));
+        }
+    }
+
+    #[test]
+    fn zl_to_biguint_checked_rejects_consecutive_indices() {
+        // Act
+        let result = zl_to_biguint_checked(&[10, 7, 6]);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckError::ConsecutiveIndices {
+                previous: 7,
+                next: 6
+            })
+        );
+    }
+
+    #[test]
+    fn zl_to_biguint_checked_matches_lenient_for_valid_lists() {
+        for n in 0..1_000u64 {
+            // Arrange
+            let zld = memoized_zeckendorf_list_descending_for_integer(n);
+
+            // Act
+            let result = zl_to_biguint_checked(&zld);
+
+            // Assert
+            assert_eq!(result, Ok(BigUint::from(n)));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn zl_to_biguint_parallel_matches_sequential_sum() {
+        for zl in [
+            vec![],
+            vec![0, 1, 1, 2],
+            zeckendorf_list_descending_for_bytes_be(&test_data::generate_ones_data(512)),
+            zeckendorf_list_descending_for_bytes_be(&test_data::generate_random_data(
+                512,
+                test_data::DEFAULT_TEST_DATA_SEED,
+            )),
+        ] {
+            // Act
+            let parallel_sum = zl_to_biguint_parallel(&zl);
+
+            // Assert
+            assert_eq!(parallel_sum, zl_to_biguint(&zl), "zl length: {}", zl.len());
+        }
+    }
+
+    #[test]
+    fn one_bit_biguint_positions_of_zero_are_none() {
+        // Arrange
+        let zero = BigUint::zero();
+
+        // Act
+        let highest = highest_one_bit_biguint(&zero);
+        let lowest = lowest_one_bit_biguint(&zero);
+
+        // Assert
+        assert_eq!(highest, None);
+        assert_eq!(lowest, None);
+    }
+
+    #[test]
+    fn one_bit_biguint_positions_of_powers_of_two_are_the_exponent() {
+        for exponent in [0, 1, 31, 63, 64, 65, 127, 128, 1_000] {
+            // Arrange
+            let power_of_two = BigUint::one() << exponent;
+
+            // Act
+            let highest = highest_one_bit_biguint(&power_of_two);
+            let lowest = lowest_one_bit_biguint(&power_of_two);
+
+            // Assert
+            assert_eq!(highest, Some(exponent), "exponent: {exponent}");
+            assert_eq!(lowest, Some(exponent), "exponent: {exponent}");
+        }
+    }
+
+    #[test]
+    fn one_bit_biguint_positions_span_multiple_limbs() {
+        // Arrange
+        let n = (BigUint::one() << 200u32) | (BigUint::one() << 70u32) | (BigUint::one() << 5u32);
+
+        // Act
+        let highest = highest_one_bit_biguint(&n);
+        let lowest = lowest_one_bit_biguint(&n);
+
+        // Assert
+        assert_eq!(highest, Some(200));
+        assert_eq!(lowest, Some(5));
+        assert_eq!(highest_one_bit_biguint(&BigUint::from(u64::MAX)), Some(63));
+    }
+
+    #[test]
+    fn best_of_selects_smallest_compressed_result() {
+        // Arrange
+        let results = [
+            PadlessCompressionResult::Neither {
+                be_size: 1,
+                le_size: 1,
+            },
+            PadlessCompressionResult::BigEndianBest {
+                compressed_data: vec![0; 4],
+                le_size: 5,
+            },
+            PadlessCompressionResult::LittleEndianBest {
+                compressed_data: vec![0; 3],
+                be_size: 6,
+            },
+        ];
+
+        // Act
+        let maybe_best = PadlessCompressionResult::best_of(&results);
+
+        // Assert
+        assert_eq!(maybe_best, Some(&results[2]));
+    }
+
+    #[test]
+    fn best_of_returns_neither_only_when_nothing_compressed() {
+        // Arrange
+        let results = [PadlessCompressionResult::Neither {
+            be_size: 1,
+            le_size: 1,
+        }];
+
+        // Act
+        let maybe_best = PadlessCompressionResult::best_of(&results);
+
+        // Assert
+        assert_eq!(maybe_best, Some(&results[0]));
+        assert_eq!(PadlessCompressionResult::best_of(&[]), None);
+    }
+
+    #[test]
+    fn fibonacci_index_histogram_sums_to_set_bits() {
+        // Arrange
+        let corpus = vec![
+            vec![0],
+            vec![1, 2, 3, 4],
+            vec![0xFF; 16],
+            (0..=255).collect::<Vec<u8>>(),
+        ];
+        let set_bit_count: u64 = corpus
+            .iter()
+            .map(|input| {
+                padless_zeckendorf_compress_be_dangerous(input)
+                    .iter()
+                    .map(|byte| u64::from(byte.count_ones()))
+                    .sum::<u64>()
+            })
+            .sum();
+
+        // Act
+        let histogram = fibonacci_index_histogram(&corpus);
+
+        // Assert
+        assert_eq!(histogram.values().sum::<u64>(), set_bit_count);
+        assert!(
+            histogram
+                .keys()
+                .all(|&fibonacci_index| fibonacci_index >= 2)
+        );
+    }
+
+    #[test]
+    fn decompress_be_with_size_restores_single_leading_zero() {
+        // Arrange
+        let data = vec![0u8, 5];
+        let compressed = padless_zeckendorf_compress_be_dangerous(&data);
+
+        // Act
+        let decompressed = zeckendorf_decompress_be_with_size(&compressed, data.len());
+
+        // Assert
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn decompress_be_with_size_restores_multiple_leading_zeros() {
+        // Arrange
+        let data = vec![0u8, 0, 0, 0x42, 0xFF];
+        let compressed = padless_zeckendorf_compress_be_dangerous(&data);
+
+        // Act
+        let decompressed = zeckendorf_decompress_be_with_size(&compressed, data.len());
+
+        // Assert
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn decompress_be_with_size_restores_all_zero_input() {
+        // Arrange
+        let data = vec![0u8; 4];
+        let compressed = padless_zeckendorf_compress_be_dangerous(&data);
+
+        // Act
+        let decompressed = zeckendorf_decompress_be_with_size(&compressed, data.len());
+
+        // Assert
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn decompress_le_with_size_restores_multiple_trailing_zeros() {
+        // Arrange
+        let data = vec![0xFFu8, 0x42, 0, 0, 0];
+        let compressed = padless_zeckendorf_compress_le_dangerous(&data);
+
+        // Act
+        let decompressed = zeckendorf_decompress_le_with_size(&compressed, data.len());
+
+        // Assert
+        assert_eq!(decompressed, data);
+    }
+}
@@ -0,0 +1,249 @@
+//! Experimental Zeckendorf-style compression with Lucas numbers instead of Fibonacci numbers
+//!
+//! The Lucas numbers follow the Fibonacci recurrence from different starting values, L(0) = 2 and L(1) = 1, so
+//! L(n) = F(n - 1) + F(n + 1) grows at the same rate as F(n) but is about sqrt(5) times larger. Every integer is a sum of
+//! non-consecutive Lucas numbers, and the greedy choice of the largest Lucas number that fits gives such a sum.
+//!
+//! The representation is stored exactly like an EZBA (see [`crate::EzbaBits`]), with the Lucas numbers sorted by
+//! value taking the place of the effective Fibonacci indices. We call these effective Lucas positions:
+//! - position 0 is L(1) = 1
+//! - position 1 is L(0) = 2
+//! - every position `p >= 2` is L(p)
+//!
+//! The greedy choice never uses two adjacent positions, so the [`crate::USE_BIT`] that skips the next position is
+//! valid here too. Whether Lucas or Fibonacci numbers compress some data better is an open question; the
+//! `generate-statistics` example compares the two across several data distributions.
+
+use crate::{EzbaBits, PHI, highest_one_bit};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+/// lucas(x) is equal to 2 if x is 0; 1 if x is 1; else return lucas(x - 1) + lucas(x - 2)
+/// li stands for Lucas Index
+///
+/// Uses the same fast doubling loop as [`crate::fast_doubling_fibonacci_biguint`] to compute F(li) and F(li + 1),
+/// and returns L(li) = 2 * F(li + 1) - F(li).
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::fast_doubling_lucas_biguint;
+/// # use num_bigint::BigUint;
+/// assert_eq!(*fast_doubling_lucas_biguint(0), BigUint::from(2u64));
+/// assert_eq!(*fast_doubling_lucas_biguint(1), BigUint::from(1u64));
+/// assert_eq!(*fast_doubling_lucas_biguint(2), BigUint::from(3u64));
+/// assert_eq!(*fast_doubling_lucas_biguint(9), BigUint::from(76u64));
+/// ```
+pub fn fast_doubling_lucas_biguint(li: u64) -> Arc<BigUint> {
+    // (a, b) represents (F(m), F(m+1)), see fast_doubling_fibonacci_biguint for the identities
+    let mut a = BigUint::zero();
+    let mut b = BigUint::one();
+    let mut li_msb = highest_one_bit(li);
+    while li_msb != 0 {
+        let d = a.clone() * ((b.clone() << 1) - &a);
+        let e = a.pow(2) + b.pow(2);
+        a = d;
+        b = e;
+
+        if li & li_msb != 0 {
+            let tmp = a + &b;
+            a = b;
+            b = tmp;
+        }
+
+        li_msb >>= 1;
+    }
+
+    Arc::new((b << 1) - a)
+}
+
+/// Returns the effective Lucas positions of the greedy Lucas representation of `n`, in descending order.
+///
+/// See the [module documentation](self) for how positions map to Lucas numbers.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::lucas::lucas_list_descending_for_biguint;
+/// # use num_bigint::BigUint;
+/// assert_eq!(lucas_list_descending_for_biguint(&BigUint::from(0u64)), Vec::<u64>::new());
+/// assert_eq!(lucas_list_descending_for_biguint(&BigUint::from(2u64)), vec![1]);
+/// assert_eq!(lucas_list_descending_for_biguint(&BigUint::from(6u64)), vec![3, 1]); // 4 + 2
+/// assert_eq!(lucas_list_descending_for_biguint(&BigUint::from(100u64)), vec![9, 6, 3, 1]); // 76 + 18 + 4 + 2
+/// ```
+pub fn lucas_list_descending_for_biguint(n: &BigUint) -> Vec<u64> {
+    let mut positions = Vec::new();
+    let mut remainder = n.clone();
+
+    if remainder >= BigUint::from(3u8) {
+        // L(li) is the nearest integer to PHI^li, so li is about log2(n) / log2(PHI). Adjust the estimate until
+        // L(li) <= n < L(li + 1).
+        let mut li = (((remainder.bits() - 1) as f64 / PHI.log2()) as u64).max(2);
+        let mut lower = fast_doubling_lucas_biguint(li).as_ref().clone();
+        let mut upper = fast_doubling_lucas_biguint(li + 1).as_ref().clone();
+        while lower > remainder && li > 2 {
+            let below = &upper - &lower;
+            upper = lower;
+            lower = below;
+            li -= 1;
+        }
+        while upper <= remainder {
+            let above = &lower + &upper;
+            lower = upper;
+            upper = above;
+            li += 1;
+        }
+
+        // Walk down with L(li - 1) = L(li + 1) - L(li), taking every Lucas number that still fits
+        loop {
+            if lower <= remainder {
+                remainder -= &lower;
+                positions.push(li);
+            }
+            if li == 2 {
+                break;
+            }
+            let below = &upper - &lower;
+            upper = lower;
+            lower = below;
+            li -= 1;
+        }
+    }
+
+    // Below L(2) = 3, the remainder is L(0) = 2 at position 1, L(1) = 1 at position 0, or nothing
+    if remainder == BigUint::from(2u8) {
+        positions.push(1);
+    } else if remainder.is_one() {
+        positions.push(0);
+    }
+
+    positions
+}
+
+/// Sums the Lucas numbers of a list of effective Lucas positions in ascending order, the inverse of
+/// [`lucas_list_descending_for_biguint`].
+fn lucas_list_ascending_to_biguint(positions: &[u64]) -> BigUint {
+    let mut sum = BigUint::zero();
+    // (current, next) are the Lucas numbers at positions (position, position + 1), starting from position 2
+    let mut position = 2;
+    let mut current = BigUint::from(3u8);
+    let mut next = BigUint::from(4u8);
+    for &target in positions {
+        match target {
+            0 => sum += 1u8,
+            1 => sum += 2u8,
+            _ => {
+                while position < target {
+                    let after = &current + &next;
+                    current = std::mem::replace(&mut next, after);
+                    position += 1;
+                }
+                sum += &current;
+            }
+        }
+    }
+    sum
+}
+
+/// Compresses the integer as packed Lucas bits, the Lucas counterpart of the packed EZBA.
+fn lucas_compress_biguint(n: &BigUint) -> Vec<u8> {
+    EzbaBits::from_ezld(&lucas_list_descending_for_biguint(n)).into_packed_bytes()
+}
+
+/// Decodes packed Lucas bits back to the integer they represent.
+pub(crate) fn lucas_bits_to_biguint(compressed_data: &[u8]) -> BigUint {
+    lucas_list_ascending_to_biguint(&EzbaBits::from_packed_bytes(compressed_data).to_ezla())
+}
+
+/// Compresses data with the greedy Lucas representation instead of the Zeckendorf representation, interpreting the
+/// data as a big endian integer.
+///
+/// This is the Lucas counterpart of [`crate::padless_zeckendorf_compress_be_dangerous`], with the same caveats: leading
+/// zero bytes are stripped, so the caller must retain the original size. For a .zeck file, use
+/// [`crate::zeck_file_format::compress::compress_zeck_lucas`].
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{lucas_compress_be, lucas_decompress_be};
+/// assert_eq!(lucas_compress_be(&[0]), vec![0]);
+/// assert_eq!(lucas_compress_be(&[1]), vec![1]);
+/// assert_eq!(lucas_compress_be(&[6]), vec![0b110]); // 4 + 2, positions 3 and 1
+/// assert_eq!(lucas_decompress_be(&lucas_compress_be(&[1, 0])), vec![1, 0]);
+/// ```
+#[wasm_bindgen]
+pub fn lucas_compress_be(data: &[u8]) -> Vec<u8> {
+    lucas_compress_biguint(&BigUint::from_bytes_be(data))
+}
+
+/// Compresses data with the greedy Lucas representation instead of the Zeckendorf representation, interpreting the
+/// data as a little endian integer.
+///
+/// This is the Lucas counterpart of [`crate::padless_zeckendorf_compress_le_dangerous`], with the same caveats: trailing
+/// zero bytes are stripped, so the caller must retain the original size.
+#[wasm_bindgen]
+pub fn lucas_compress_le(data: &[u8]) -> Vec<u8> {
+    lucas_compress_biguint(&BigUint::from_bytes_le(data))
+}
+
+/// Decompresses data compressed with [`lucas_compress_be`].
+///
+/// Like [`crate::padless_zeckendorf_decompress_be_dangerous`], leading zero bytes of the original data are not restored.
+#[wasm_bindgen]
+pub fn lucas_decompress_be(compressed_data: &[u8]) -> Vec<u8> {
+    lucas_bits_to_biguint(compressed_data).to_bytes_be()
+}
+
+/// Decompresses data compressed with [`lucas_compress_le`].
+///
+/// Like [`crate::padless_zeckendorf_decompress_le_dangerous`], trailing zero bytes of the original data are not
+/// restored.
+#[wasm_bindgen]
+pub fn lucas_decompress_le(compressed_data: &[u8]) -> Vec<u8> {
+    lucas_bits_to_biguint(compressed_data).to_bytes_le()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lucas_list_sums_to_n_without_adjacent_positions() {
+        for n in 0..5_000u64 {
+            // Arrange
+            let n = BigUint::from(n);
+
+            // Act
+            let positions = lucas_list_descending_for_biguint(&n);
+
+            // Assert
+            assert!(
+                positions.windows(2).all(|pair| pair[0] >= pair[1] + 2),
+                "n: {n}, positions: {positions:?}"
+            );
+            let ascending = positions.iter().rev().copied().collect::<Vec<_>>();
+            assert_eq!(lucas_list_ascending_to_biguint(&ascending), n);
+        }
+    }
+
+    #[test]
+    fn lucas_round_trips_both_endiannesses() {
+        for data in [
+            vec![1],
+            vec![255],
+            vec![1, 2, 3, 4, 5, 6, 7, 8],
+            vec![0xFF; 64],
+            (1..=255).collect::<Vec<u8>>(),
+        ] {
+            // Act
+            let big_endian = lucas_decompress_be(&lucas_compress_be(&data));
+            let little_endian = lucas_decompress_le(&lucas_compress_le(&data));
+
+            // Assert
+            assert_eq!(big_endian, data);
+            assert_eq!(little_endian, data);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Compression ratio measurements shared by the statistics and plotting examples
+//!
+//! The ratios compare bit lengths rather than byte lengths, using [`compressed_value`] to count the bits the
+//! compressed data really needs, so they are not rounded up to whole bytes like the sizes of a [`crate::ZeckFile`].
+
+use crate::{bit_count_for_u64, compressed_value};
+use num_bigint::BigUint;
+
+/// Calculates the compression ratio for a given input value, converting the input to a bigint as big endian bytes and then
+/// compressing it.
+///
+/// Returns:
+/// - Some(f64) if the compression is possible. The compression ratio as a normalized value where 1.0 = 100% of original size.
+///   Values < 1.0 indicate favorable compression (compressed is smaller), values > 1.0 indicate unfavorable compression (compressed is larger).
+/// - None if the compression is not possible (e.g. if the input is 0)
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::stats::compression_amount_percent;
+/// assert_eq!(compression_amount_percent(0), None);
+/// assert_eq!(compression_amount_percent(12), Some(0.75)); // 4 bits compress to 0b111
+/// ```
+pub fn compression_amount_percent(value: u64) -> Option<f64> {
+    let original_bit_size = bit_count_for_u64(value);
+
+    if original_bit_size == 0 {
+        return None;
+    }
+
+    let data_bytes = BigUint::from(value).to_bytes_be();
+    let compressed_bit_size = compressed_value(&data_bytes).bits();
+
+    let ratio = compressed_bit_size as f64 / original_bit_size as f64;
+    Some(ratio)
+}
+
+/// Calculates the compression ratio for a given data in bytes.
+///
+/// Returns:
+/// - Some(f64) if the compression is possible. The compression ratio as a normalized value where 1.0 = 100% of original size.
+///   Values < 1.0 indicate favorable compression (compressed is smaller), values > 1.0 indicate unfavorable compression (compressed is larger).
+/// - None if the compression is not possible (e.g. if the input is an empty bytes array)
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::stats::compression_amount_percent_bytes;
+/// assert_eq!(compression_amount_percent_bytes(&[]), None);
+/// assert_eq!(compression_amount_percent_bytes(&[12]), Some(3.0 / 8.0));
+/// ```
+pub fn compression_amount_percent_bytes(data: &[u8]) -> Option<f64> {
+    let original_bit_size = data.len() * 8;
+
+    if original_bit_size == 0 {
+        return None;
+    }
+
+    let compressed_bit_size = compressed_value(data).bits();
+
+    let ratio = compressed_bit_size as f64 / original_bit_size as f64;
+    Some(ratio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn favorable_input_has_ratio_below_one() {
+        // Arrange
+        let data = [0, 0, 0, 1];
+
+        // Act
+        let ratio = compression_amount_percent_bytes(&data);
+
+        // Assert
+        assert_eq!(ratio, Some(1.0 / 32.0));
+    }
+
+    #[test]
+    fn unfavorable_input_has_ratio_above_one() {
+        // Arrange
+        let data = [0xFF; 8];
+
+        // Act
+        let ratio = compression_amount_percent_bytes(&data).unwrap();
+
+        // Assert
+        assert!(ratio > 1.0, "ratio: {ratio}");
+    }
+
+    #[test]
+    fn empty_input_has_no_ratio() {
+        // Act
+        let ratio = compression_amount_percent_bytes(&[]);
+
+        // Assert
+        assert_eq!(ratio, None);
+    }
+}
@@ -0,0 +1,360 @@
+//! Deterministic input data generators for benchmarks and tests
+//!
+//! Each generator covers a different data distribution, since the compression ratio depends heavily on the input:
+//! all-zero data compresses well, all-ones data is the worst case, and ramp, stripe, random, and sparse data fall in
+//! between. [`TestDataPattern`] names each generator, so tools can choose one from a string.
+//!
+//! The random generators take a seed and use a small built-in SplitMix64 generator, so the same seed always yields
+//! the same bytes on every platform without depending on the `rand` crate.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The default seed used by the benchmarks for the seeded generators.
+pub const DEFAULT_TEST_DATA_SEED: u64 = 0x5EED_F1B0_2024_0001;
+
+/// One out of this many bytes is non-zero in [`generate_sparse_data`], on average.
+pub const SPARSE_DATA_NONZERO_ONE_IN: u64 = 32;
+
+/// The length of each run of `0x00` or `0xFF` bytes in [`generate_stripe_data`].
+pub const STRIPE_DATA_RUN_LENGTH: usize = 16;
+
+/// A named data distribution produced by one of the generators in this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TestDataPattern {
+    /// Every byte is `0x00`, see [`generate_zeros_data`].
+    Zeros,
+    /// Every byte is `0xFF`, see [`generate_ones_data`].
+    Ones,
+    /// Each byte is its index modulo 256, see [`generate_ramp_data`].
+    Ramp,
+    /// Alternating runs of `0x00` and `0xFF`, see [`generate_stripe_data`].
+    Stripe,
+    /// Mostly-zero bytes from a seed, see [`generate_sparse_data`].
+    Sparse,
+    /// Uniformly random bytes from a seed, see [`generate_random_data`].
+    #[default]
+    Random,
+}
+
+impl TestDataPattern {
+    /// Every pattern, in the order they are listed in usage messages.
+    pub const ALL: [TestDataPattern; 6] = [
+        TestDataPattern::Zeros,
+        TestDataPattern::Ones,
+        TestDataPattern::Ramp,
+        TestDataPattern::Stripe,
+        TestDataPattern::Sparse,
+        TestDataPattern::Random,
+    ];
+
+    /// Generates `size` bytes of this pattern. The seed is only used by [`TestDataPattern::Sparse`] and
+    /// [`TestDataPattern::Random`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::test_data::TestDataPattern;
+    /// assert_eq!(TestDataPattern::Ones.generate(2, 0), vec![0xFF, 0xFF]);
+    /// assert_eq!(TestDataPattern::Random.generate(8, 42), TestDataPattern::Random.generate(8, 42));
+    /// ```
+    pub fn generate(self, size: usize, seed: u64) -> Vec<u8> {
+        match self {
+            TestDataPattern::Zeros => generate_zeros_data(size),
+            TestDataPattern::Ones => generate_ones_data(size),
+            TestDataPattern::Ramp => generate_ramp_data(size),
+            TestDataPattern::Stripe => generate_stripe_data(size),
+            TestDataPattern::Sparse => generate_sparse_data(size, seed),
+            TestDataPattern::Random => generate_random_data(size, seed),
+        }
+    }
+}
+
+/// Parses `"zeros"`, `"ones"`, `"ramp"`, `"stripe"`, `"sparse"`, or `"random"`, ignoring ASCII case.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::test_data::TestDataPattern;
+/// assert_eq!("stripe".parse(), Ok(TestDataPattern::Stripe));
+/// assert_eq!("Zeros".parse(), Ok(TestDataPattern::Zeros));
+/// assert!("noise".parse::<TestDataPattern>().is_err());
+/// ```
+impl FromStr for TestDataPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "zeros" => Ok(TestDataPattern::Zeros),
+            "ones" => Ok(TestDataPattern::Ones),
+            "ramp" => Ok(TestDataPattern::Ramp),
+            "stripe" => Ok(TestDataPattern::Stripe),
+            "sparse" => Ok(TestDataPattern::Sparse),
+            "random" => Ok(TestDataPattern::Random),
+            _ => Err(format!(
+                "Invalid pattern '{s}'. Expected 'zeros', 'ones', 'ramp', 'stripe', 'sparse', or 'random'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TestDataPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestDataPattern::Zeros => write!(f, "zeros"),
+            TestDataPattern::Ones => write!(f, "ones"),
+            TestDataPattern::Ramp => write!(f, "ramp"),
+            TestDataPattern::Stripe => write!(f, "stripe"),
+            TestDataPattern::Sparse => write!(f, "sparse"),
+            TestDataPattern::Random => write!(f, "random"),
+        }
+    }
+}
+
+/// Generates ramp data, where each byte is its index modulo 256.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::test_data::generate_ramp_data;
+/// assert_eq!(generate_ramp_data(5), vec![0, 1, 2, 3, 4]);
+/// assert_eq!(generate_ramp_data(258)[256..], [0, 1]);
+/// ```
+pub fn generate_ramp_data(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 256) as u8).collect()
+}
+
+/// Generates data where every byte is `0x00`.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::test_data::generate_zeros_data;
+/// assert_eq!(generate_zeros_data(3), vec![0, 0, 0]);
+/// ```
+pub fn generate_zeros_data(size: usize) -> Vec<u8> {
+    vec![0x00; size]
+}
+
+/// Generates data where every byte is `0xFF`, the worst case for Zeckendorf compression.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::test_data::generate_ones_data;
+/// assert_eq!(generate_ones_data(3), vec![0xFF, 0xFF, 0xFF]);
+/// ```
+pub fn generate_ones_data(size: usize) -> Vec<u8> {
+    vec![0xFF; size]
+}
+
+/// Generates alternating runs of [`STRIPE_DATA_RUN_LENGTH`] `0x00` bytes and [`STRIPE_DATA_RUN_LENGTH`] `0xFF` bytes,
+/// starting with `0x00`.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::test_data::{generate_stripe_data, STRIPE_DATA_RUN_LENGTH};
+/// let data = generate_stripe_data(3 * STRIPE_DATA_RUN_LENGTH);
+/// assert_eq!(data[0], 0x00);
+/// assert_eq!(data[STRIPE_DATA_RUN_LENGTH], 0xFF);
+/// assert_eq!(data[2 * STRIPE_DATA_RUN_LENGTH], 0x00);
+/// ```
+pub fn generate_stripe_data(size: usize) -> Vec<u8> {
+    (0..size)
+        .map(|i| {
+            if (i / STRIPE_DATA_RUN_LENGTH).is_multiple_of(2) {
+                0x00
+            } else {
+                0xFF
+            }
+        })
+        .collect()
+}
+
+/// Generates uniformly random bytes from the given seed.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::test_data::generate_random_data;
+/// let data = generate_random_data(64, 42);
+/// assert_eq!(data.len(), 64);
+/// assert_eq!(data, generate_random_data(64, 42));
+/// assert_ne!(data, generate_random_data(64, 43));
+/// ```
+pub fn generate_random_data(size: usize, seed: u64) -> Vec<u8> {
+    let mut rng = SplitMix64::new(seed);
+    let mut data = Vec::with_capacity(size);
+    while data.len() < size {
+        let remaining = size - data.len();
+        let bytes = rng.next_u64().to_le_bytes();
+        data.extend_from_slice(&bytes[..remaining.min(bytes.len())]);
+    }
+    data
+}
+
+/// Generates mostly-zero data from the given seed, where about one in [`SPARSE_DATA_NONZERO_ONE_IN`] bytes is a
+/// random non-zero value.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::test_data::generate_sparse_data;
+/// let data = generate_sparse_data(1024, 42);
+/// let zero_count = data.iter().filter(|&&byte| byte == 0).count();
+/// assert!(zero_count > 900);
+/// assert!(zero_count < 1024);
+/// ```
+pub fn generate_sparse_data(size: usize, seed: u64) -> Vec<u8> {
+    let mut rng = SplitMix64::new(seed);
+    (0..size)
+        .map(|_| {
+            let value = rng.next_u64();
+            if !value.is_multiple_of(SPARSE_DATA_NONZERO_ONE_IN) {
+                return 0;
+            }
+            // Use the high byte so the value is independent of the modulo check, and map it into 1..=255.
+            ((value >> 56) as u8).max(1)
+        })
+        .collect()
+}
+
+/// A minimal SplitMix64 pseudo-random number generator.
+///
+/// This is not cryptographically secure; it only needs to be fast, deterministic, and well distributed.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators_return_requested_size() {
+        for size in [0, 1, 7, 8, 9, 1000] {
+            // Act
+            let outputs = [
+                generate_ramp_data(size),
+                generate_zeros_data(size),
+                generate_ones_data(size),
+                generate_stripe_data(size),
+                generate_random_data(size, DEFAULT_TEST_DATA_SEED),
+                generate_sparse_data(size, DEFAULT_TEST_DATA_SEED),
+            ];
+
+            // Assert
+            for output in outputs {
+                assert_eq!(output.len(), size);
+            }
+        }
+    }
+
+    #[test]
+    fn each_pattern_generates_its_byte_content() {
+        // Arrange
+        let size = 100;
+        let seed = DEFAULT_TEST_DATA_SEED;
+
+        for pattern in TestDataPattern::ALL {
+            // Act
+            let data = pattern.generate(size, seed);
+
+            // Assert
+            assert_eq!(data.len(), size, "pattern: {pattern}");
+            for (i, &byte) in data.iter().enumerate() {
+                let expected_byte = match pattern {
+                    TestDataPattern::Zeros => 0x00,
+                    TestDataPattern::Ones => 0xFF,
+                    TestDataPattern::Ramp => i as u8,
+                    TestDataPattern::Stripe if (i / STRIPE_DATA_RUN_LENGTH).is_multiple_of(2) => {
+                        0x00
+                    }
+                    TestDataPattern::Stripe => 0xFF,
+                    TestDataPattern::Sparse => generate_sparse_data(size, seed)[i],
+                    TestDataPattern::Random => generate_random_data(size, seed)[i],
+                };
+                assert_eq!(byte, expected_byte, "pattern: {pattern}, index: {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn pattern_names_round_trip_and_unknown_names_are_rejected() {
+        for pattern in TestDataPattern::ALL {
+            // Act
+            let parsed = pattern.to_string().parse::<TestDataPattern>();
+
+            // Assert
+            assert_eq!(parsed, Ok(pattern));
+        }
+        assert_eq!(
+            "noise".parse::<TestDataPattern>(),
+            Err(
+                "Invalid pattern 'noise'. Expected 'zeros', 'ones', 'ramp', 'stripe', 'sparse', or 'random'"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn same_seed_yields_identical_bytes() {
+        for seed in [0, 1, DEFAULT_TEST_DATA_SEED, u64::MAX] {
+            // Act
+            let first = generate_random_data(256, seed);
+            let second = generate_random_data(256, seed);
+
+            // Assert
+            assert_eq!(first, second, "seed: {seed}");
+            assert_eq!(
+                generate_sparse_data(256, seed),
+                generate_sparse_data(256, seed),
+                "seed: {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn random_data_prefix_is_stable_across_sizes() {
+        // Arrange
+        let longer = generate_random_data(20, 7);
+
+        // Act
+        let shorter = generate_random_data(13, 7);
+
+        // Assert
+        assert_eq!(shorter, longer[..13]);
+    }
+
+    #[test]
+    fn generated_data_round_trips_through_compression() {
+        for data in [
+            generate_zeros_data(64),
+            generate_ones_data(64),
+            generate_random_data(64, DEFAULT_TEST_DATA_SEED),
+            generate_sparse_data(64, DEFAULT_TEST_DATA_SEED),
+        ] {
+            // Act
+            let compressed = crate::padless_zeckendorf_compress_be_dangerous(&data);
+            let decompressed = crate::zeckendorf_decompress_be_with_size(&compressed, data.len());
+
+            // Assert
+            assert_eq!(decompressed, data);
+        }
+    }
+}
@@ -1,18 +1,67 @@
 //! .zeck file format module
 //!
 //! This module provides functionality for compressing and decompressing data using the .zeck file format,
-//! which includes a header containing format version, original file size, and endianness information.
+//! which includes a header containing format version, original file size, and representation flags (endianness, lazy representation, polarity, and padding bits),
+//! plus optional header fields for a checksum of the original data, the original filename, and a rotation offset.
+//!
+//! Version 1 files only record the endianness, and their compressed data runs to the end of the input. Version 2 files
+//! record the length of their compressed data, so they can be concatenated.
+//! Every header field added since then needs a newer version, so older
+//! readers reject a file they cannot read with [`ZeckFormatError::UnsupportedVersion`] instead of misreading it.
 
+pub mod archive;
+#[cfg(feature = "tokio")]
+pub mod async_tasks;
+pub mod builder;
+pub mod checksum;
 pub mod compress;
 pub mod decompress;
 pub mod error;
 pub mod file;
+pub mod flags;
+pub mod sparse;
+pub mod view;
 
+pub use archive::ZeckArchive;
+pub use builder::ZeckFileBuilder;
 pub use error::ZeckFormatError;
 pub use file::ZeckFile;
+pub use flags::ZeckFlags;
+pub use view::ZeckFileView;
+
+/// Current .zeck file format version, written by every compression function except the multi-block archives.
+///
+/// Version 2 files have the representation, optional field, and extended flags described below.
+pub const ZECK_FORMAT_VERSION: u8 = 2;
+
+/// The original .zeck file format version, which is still read.
+///
+/// A version 1 file is the header followed by the greedy EZBA bits. Its flags byte only records the endianness, and
+/// every other bit is reserved (see [`ZECK_FLAG_RESERVED_MASK`]).
+pub const ZECK_LEGACY_FORMAT_VERSION: u8 = 1;
+
+/// .zeck file format version for multi-block archives that split one stream into blocks (see [`archive`]).
+pub const ZECK_ARCHIVE_FORMAT_VERSION: u8 = 3;
 
-/// Current .zeck file format version.
-pub const ZECK_FORMAT_VERSION: u8 = 1;
+/// .zeck file format version for multi-block archives of length-delimited records (see
+/// [`archive::compress_records`]).
+///
+/// The layout is the same as a version 3 archive, with one block per record. The version tells the two apart, because
+/// records have boundaries that concatenating them would lose, so each kind is only decompressed by its own functions.
+/// This is also the newest version that can be read.
+pub const ZECK_RECORDS_FORMAT_VERSION: u8 = 4;
+
+/// Size of the block count that follows the header in a multi-block archive, in bytes.
+pub const ZECK_ARCHIVE_BLOCK_COUNT_SIZE: usize = 4;
+
+/// Size of each block index entry in a multi-block archive, in bytes:
+/// offset (8), compressed length (8), original length (8), and CRC-32 (4).
+pub const ZECK_ARCHIVE_INDEX_ENTRY_SIZE: usize = 28;
+
+/// Largest original length of a single block in a multi-block archive, in bytes. Blocks are decompressed whole, so
+/// the block index is rejected with [`ZeckFormatError::InvalidBlockSize`] if it declares a larger block, instead of
+/// attempting a huge allocation.
+pub const ZECK_ARCHIVE_MAX_BLOCK_SIZE: u64 = u32::MAX as u64;
 
 /// Size of the .zeck file format header in bytes.
 pub const ZECK_HEADER_SIZE: usize = 10;
@@ -22,5 +71,118 @@ pub const ZECK_HEADER_SIZE: usize = 10;
 /// If this bit is clear (0), the data was compressed using little endian interpretation.
 pub const ZECK_FLAG_BIG_ENDIAN: u8 = 0b0000_0001;
 
-/// Reserved flags mask. Bits 1-7 are reserved for future use.
+/// Bit flag in the flags byte indicating the lazy Fibonacci representation.
+/// If this bit is set (1), the data was encoded as a lazy EZBA (see [`crate::lazy_ezba_from_biguint`]).
+/// If this bit is clear (0), the data was encoded as a regular (greedy) Zeckendorf EZBA.
+pub const ZECK_FLAG_LAZY: u8 = 0b0000_0010;
+
+/// Bit flag in the flags byte indicating inverted polarity.
+/// If this bit is set (1), every input byte was bitwise inverted before being interpreted as an integer,
+/// and decompression inverts the bytes back.
+pub const ZECK_FLAG_INVERTED: u8 = 0b0000_0100;
+
+/// Bit flag in the flags byte indicating that a padding bits byte follows the header.
+/// If this bit is set (1), the header is followed by a byte holding the number of padding bits (0-7) in the last byte
+/// of the compressed data, which pins the exact EZBA bit length so decompression can reject non-canonical data.
+/// If this bit is clear (0), the bit length is implied by the compressed data, as in files written before this flag existed.
+pub const ZECK_FLAG_PADDING_BITS: u8 = 0b0000_1000;
+
+/// Size of the padding bits byte that follows the header when [`ZECK_FLAG_PADDING_BITS`] is set, in bytes.
+pub const ZECK_PADDING_BITS_SIZE: usize = 1;
+
+/// Bit flag in the flags byte indicating that a checksum of the original data follows the header.
+/// If this bit is set (1), a CRC-32 (see [`checksum::crc32`]) of the uncompressed data is stored as a little endian u32
+/// after the padding bits byte, and decompression verifies it.
+pub const ZECK_FLAG_CHECKSUM: u8 = 0b0001_0000;
+
+/// Size of the checksum that follows the header when [`ZECK_FLAG_CHECKSUM`] is set, in bytes.
+pub const ZECK_CHECKSUM_SIZE: usize = 4;
+
+/// Bit flag in the flags byte indicating that the original filename follows the header.
+/// If this bit is set (1), the filename is stored after the checksum as a little endian u32 byte length followed by
+/// that many bytes of UTF-8.
+pub const ZECK_FLAG_FILENAME: u8 = 0b0010_0000;
+
+/// Size of the filename length that precedes the filename bytes when [`ZECK_FLAG_FILENAME`] is set, in bytes.
+pub const ZECK_FILENAME_LENGTH_SIZE: usize = 4;
+
+/// Bit flag in the flags byte indicating that the EZBA bits were entropy coded in a second stage.
+/// If this bit is set (1), the compressed data is the output of [`crate::entropy_encode_ezba`] applied to the packed
+/// EZBA bits, and decompression decodes it with [`crate::entropy_decode_ezba`] first. The entropy coded stream is
+/// self-delimiting, so no padding bits byte is recorded.
+pub const ZECK_FLAG_ENTROPY_CODED: u8 = 0b0100_0000;
+
+/// Bit flag in the flags byte indicating that an extended flags byte follows the header.
+/// If this bit is set (1), the header is followed by a second flags byte (see [`ZECK_EXTENDED_FLAG_ROTATION`]) before
+/// any other optional header field. If this bit is clear (0), every extended flag is clear.
+pub const ZECK_FLAG_EXTENDED: u8 = 0b1000_0000;
+
+/// Reserved flags mask of version 1 files (see [`ZECK_LEGACY_FORMAT_VERSION`]), whose flags byte only has
+/// [`ZECK_FLAG_BIG_ENDIAN`]. A version 1 file with any of these bits set fails with
+/// [`ZeckFormatError::ReservedFlagsSet`]. Version 2 assigns every bit of the flags byte.
 pub const ZECK_FLAG_RESERVED_MASK: u8 = 0b1111_1110;
+
+/// Size of the extended flags byte that follows the header when [`ZECK_FLAG_EXTENDED`] is set, in bytes.
+pub const ZECK_EXTENDED_FLAGS_SIZE: usize = 1;
+
+/// Bit flag in the extended flags byte indicating that the input was rotated before compression.
+/// If this bit is set (1), a rotation offset is stored after the filename as a little endian u64. The input was
+/// rotated left by that many bytes, treating it as a ring, and decompression rotates it back.
+pub const ZECK_EXTENDED_FLAG_ROTATION: u8 = 0b0000_0001;
+
+/// Size of the rotation offset that follows the header when [`ZECK_EXTENDED_FLAG_ROTATION`] is set, in bytes.
+pub const ZECK_ROTATION_SIZE: usize = 8;
+
+/// Bit flag in the extended flags byte indicating the delta encoding pre-transform.
+/// If this bit is set (1), every input byte after the first was replaced by its wrapping difference from the previous
+/// byte (`data[i] - data[i - 1]`) before compression, after any rotation, and decompression adds the bytes back up.
+pub const ZECK_EXTENDED_FLAG_DELTA: u8 = 0b0000_0010;
+
+/// Bit flag in the extended flags byte indicating the sparse index list encoding.
+/// If this bit is set (1), the compressed data is the Zeckendorf representation as a sparse index list (see
+/// [`sparse`]) instead of EZBA bits. It is only used with the greedy representation and without entropy coding or
+/// padding bits.
+pub const ZECK_EXTENDED_FLAG_SPARSE: u8 = 0b0000_0100;
+
+/// Bit flag in the extended flags byte indicating that a CRC-32 of the compressed data is recorded.
+/// If this bit is set (1), the CRC-32 of the compressed data follows the rotation offset as a little endian u32, and
+/// decompression checks it before decompressing, so a corrupted payload is rejected without the expensive work.
+pub const ZECK_EXTENDED_FLAG_PAYLOAD_CRC: u8 = 0b0000_1000;
+
+/// Size of the payload CRC-32 that follows the header when [`ZECK_EXTENDED_FLAG_PAYLOAD_CRC`] is set, in bytes.
+pub const ZECK_PAYLOAD_CRC_SIZE: usize = 4;
+
+/// Bit flag in the extended flags byte selecting the experimental Lucas codec.
+/// If this bit is set (1), the compressed data is the greedy Lucas representation (see [`crate::lucas`]) instead of the
+/// Zeckendorf representation, stored as bits the same way as an EZBA. If it is clear (0), the base sequence is the
+/// Fibonacci numbers. It is only used with the greedy representation and without entropy coding or a sparse index list.
+pub const ZECK_EXTENDED_FLAG_LUCAS: u8 = 0b0001_0000;
+
+/// Bit flag in the extended flags byte indicating the uncompressed fallback.
+/// If this bit is set (1), the compressed data is the input itself, exactly the original size, because compressing it
+/// did not help. It is read as an integer in the file's endianness like decompressed data, so the inversion, delta, and
+/// rotation pre-transforms still apply, but it cannot be combined with the lazy representation, entropy coding, padding
+/// bits, a sparse index list, or the Lucas codec.
+pub const ZECK_EXTENDED_FLAG_UNCOMPRESSED: u8 = 0b0010_0000;
+
+/// Bit flag in the extended flags byte indicating that the modification time of the original file is recorded.
+/// If this bit is set (1), the modification time follows the payload CRC-32 as a little endian u64 of seconds since the
+/// Unix epoch. It is metadata only, so decompression does not read it.
+pub const ZECK_EXTENDED_FLAG_MTIME: u8 = 0b0100_0000;
+
+/// Size of the modification time that follows the header when [`ZECK_EXTENDED_FLAG_MTIME`] is set, in bytes.
+pub const ZECK_MTIME_SIZE: usize = 8;
+
+/// Size of the compressed data length that follows the optional header fields of a version 2 file, in bytes.
+/// The compressed data is exactly that many bytes (a little endian u64), so a version 2 file can be followed by more
+/// data, such as another .zeck file.
+pub const ZECK_PAYLOAD_LENGTH_SIZE: usize = 8;
+
+/// Bit flag in the extended flags byte indicating that the original data is a sequence of fixed width elements.
+/// If this bit is set (1), the width of one element in bytes follows the modification time as a single byte, and the
+/// original size is a whole number of elements, so the element count is the original size divided by the width.
+/// [`decompress::decompress_to_u64_vec`] only reads files with an element width of 8.
+pub const ZECK_EXTENDED_FLAG_ELEMENT_WIDTH: u8 = 0b1000_0000;
+
+/// Size of the element width that follows the header when [`ZECK_EXTENDED_FLAG_ELEMENT_WIDTH`] is set, in bytes.
+pub const ZECK_ELEMENT_WIDTH_SIZE: usize = 1;
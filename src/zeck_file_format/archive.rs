@@ -0,0 +1,739 @@
+//! Multi-block .zeck archives with per-block checksums and random access
+//!
+//! Version 3 of the .zeck file format splits the input into fixed-size blocks that are compressed independently.
+//! Version 4 has the same layout, with one block per record of [`compress_records`].
+//! The regular header is followed by a block index and then the block payloads:
+//!
+//! ```text
+//! [version(1)] [original_size(8 LE u64)] [flags(1)]
+//! [block_count(4 LE u32)]
+//! [offset(8 LE u64) compressed_len(8 LE u64) original_len(8 LE u64) crc32(4 LE u32)] × block_count
+//! [block payloads...]
+//! ```
+//!
+//! Offsets are relative to the start of the block payloads, and each CRC-32 is computed over the block's original
+//! data, so any block can be located, decompressed, and verified without touching the others.
+//! The flags byte applies to every block.
+
+use crate::zeck_file_format::checksum::crc32;
+use crate::zeck_file_format::compress::compress_with_flags;
+use crate::zeck_file_format::decompress::decompress_zeck_payload;
+use crate::zeck_file_format::{
+    ZECK_ARCHIVE_BLOCK_COUNT_SIZE, ZECK_ARCHIVE_FORMAT_VERSION, ZECK_ARCHIVE_INDEX_ENTRY_SIZE,
+    ZECK_ARCHIVE_MAX_BLOCK_SIZE, ZECK_FLAG_BIG_ENDIAN, ZECK_FLAG_CHECKSUM, ZECK_FLAG_ENTROPY_CODED,
+    ZECK_FLAG_EXTENDED, ZECK_FLAG_FILENAME, ZECK_FLAG_PADDING_BITS, ZECK_HEADER_SIZE,
+    ZECK_RECORDS_FORMAT_VERSION, error::ZeckFormatError, file::ZeckFile,
+};
+use std::convert::TryFrom;
+use std::ops::Range;
+use wasm_bindgen::prelude::*;
+
+/// An entry in the block index of a multi-block archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeckArchiveBlockEntry {
+    /// Offset of the block's compressed payload, relative to the start of the block payloads
+    pub offset: u64,
+    /// Length of the block's compressed payload in bytes
+    pub compressed_len: u64,
+    /// Length of the block's original data in bytes
+    pub original_len: u64,
+    /// CRC-32 of the block's original data
+    pub crc32: u32,
+}
+
+/// A parsed view over a serialized multi-block .zeck archive that gives random access to its blocks.
+///
+/// Opening an archive only parses the header and block index; block payloads are borrowed from the input bytes
+/// and decompressed on demand by [`ZeckArchive::block`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZeckArchive<'a> {
+    /// Original uncompressed size of all blocks combined, in bytes
+    pub original_size: u64,
+    /// Flags byte shared by every block
+    pub flags: u8,
+    entries: Vec<ZeckArchiveBlockEntry>,
+    payloads: &'a [u8],
+}
+
+impl<'a> ZeckArchive<'a> {
+    /// Parses the header and block index of a serialized multi-block archive, of blocks or of records.
+    ///
+    /// Every index entry is checked to lie within the block payloads, so [`ZeckArchive::block`] never reads out of bounds.
+    /// A block that extends past the end of the data fails with [`ZeckFormatError::Truncated`]. Every block's original
+    /// length is checked too, failing with [`ZeckFormatError::InvalidBlockSize`] if it is above
+    /// [`ZECK_ARCHIVE_MAX_BLOCK_SIZE`] or the blocks add up to more than the archive's original size, so a corrupted
+    /// index cannot trigger a huge allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::archive::{compress_zeck_archive, ZeckArchive};
+    /// let data: Vec<u8> = (0..12).collect();
+    /// let bytes = compress_zeck_archive(&data, 4, true).unwrap().to_bytes();
+    /// let archive = ZeckArchive::open(&bytes).unwrap();
+    /// assert_eq!(archive.block_count(), 3);
+    /// assert_eq!(archive.block(1).unwrap(), vec![4, 5, 6, 7]);
+    /// ```
+    pub fn open(zeck_file_data: &'a [u8]) -> Result<Self, ZeckFormatError> {
+        if zeck_file_data.len() < ZECK_HEADER_SIZE {
+            return Err(ZeckFormatError::HeaderTooShort {
+                actual_length: zeck_file_data.len(),
+                required_length: ZECK_HEADER_SIZE,
+            });
+        }
+
+        let version = zeck_file_data[0];
+        if !matches!(
+            version,
+            ZECK_ARCHIVE_FORMAT_VERSION | ZECK_RECORDS_FORMAT_VERSION
+        ) {
+            return Err(ZeckFormatError::UnsupportedVersion {
+                found_version: version,
+                supported_version: ZECK_RECORDS_FORMAT_VERSION,
+            });
+        }
+        let original_size = u64::from_le_bytes(read_array(zeck_file_data, 1));
+        let flags = zeck_file_data[9];
+
+        Self::from_parts(original_size, flags, &zeck_file_data[ZECK_HEADER_SIZE..])
+    }
+
+    /// Parses the block index from the data following the header of a multi-block archive.
+    pub(crate) fn from_parts(
+        original_size: u64,
+        flags: u8,
+        compressed_data: &'a [u8],
+    ) -> Result<Self, ZeckFormatError> {
+        // Archives have no optional header fields or second stage, so their flags are reserved here too
+        let archive_reserved_mask = ZECK_FLAG_EXTENDED
+            | ZECK_FLAG_PADDING_BITS
+            | ZECK_FLAG_CHECKSUM
+            | ZECK_FLAG_FILENAME
+            | ZECK_FLAG_ENTROPY_CODED;
+        if flags & archive_reserved_mask != 0 {
+            return Err(ZeckFormatError::ReservedFlagsSet { flags });
+        }
+
+        if compressed_data.len() < ZECK_ARCHIVE_BLOCK_COUNT_SIZE {
+            return Err(ZeckFormatError::HeaderTooShort {
+                actual_length: ZECK_HEADER_SIZE + compressed_data.len(),
+                required_length: ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE,
+            });
+        }
+        let block_count = u32::from_le_bytes(read_array(compressed_data, 0)) as usize;
+
+        let index_size = ZECK_ARCHIVE_BLOCK_COUNT_SIZE
+            .saturating_add(block_count.saturating_mul(ZECK_ARCHIVE_INDEX_ENTRY_SIZE));
+        if compressed_data.len() < index_size {
+            return Err(ZeckFormatError::HeaderTooShort {
+                actual_length: ZECK_HEADER_SIZE + compressed_data.len(),
+                required_length: ZECK_HEADER_SIZE + index_size,
+            });
+        }
+
+        let (index, payloads) = compressed_data.split_at(index_size);
+        let entries = index[ZECK_ARCHIVE_BLOCK_COUNT_SIZE..]
+            .chunks_exact(ZECK_ARCHIVE_INDEX_ENTRY_SIZE)
+            .map(|entry| ZeckArchiveBlockEntry {
+                offset: u64::from_le_bytes(read_array(entry, 0)),
+                compressed_len: u64::from_le_bytes(read_array(entry, 8)),
+                original_len: u64::from_le_bytes(read_array(entry, 16)),
+                crc32: u32::from_le_bytes(read_array(entry, 24)),
+            })
+            .collect::<Vec<_>>();
+
+        // Empty blocks are legal (an archive of records can hold an empty record), so only the upper bound is checked
+        let mut total_original_len = 0u64;
+        for (block_index, entry) in entries.iter().enumerate() {
+            total_original_len = total_original_len.saturating_add(entry.original_len);
+            if entry.original_len > ZECK_ARCHIVE_MAX_BLOCK_SIZE
+                || total_original_len > original_size
+            {
+                return Err(ZeckFormatError::InvalidBlockSize {
+                    block_index,
+                    value: entry.original_len,
+                });
+            }
+
+            block_payload_range(entry, block_index, payloads.len())?;
+        }
+
+        Ok(Self {
+            original_size,
+            flags,
+            entries,
+            payloads,
+        })
+    }
+
+    /// Returns the number of blocks in the archive.
+    pub fn block_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the block index entries of the archive.
+    pub fn entries(&self) -> &[ZeckArchiveBlockEntry] {
+        &self.entries
+    }
+
+    /// Returns the size of the data [`ZeckArchive::decompress_all`] produces, the sum of the blocks' original lengths.
+    ///
+    /// The block lengths were checked when the archive was opened, unlike the original size in the header.
+    pub fn decompressed_size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.original_len).sum()
+    }
+
+    /// Returns the compressed payload of a block without decompressing it, or [`None`] if the block does not exist.
+    pub(crate) fn block_payload(&self, index: usize) -> Option<&'a [u8]> {
+        let entry = self.entries.get(index)?;
+        let range = block_payload_range(entry, index, self.payloads.len()).ok()?;
+        Some(&self.payloads[range])
+    }
+
+    /// Decompresses a single block and verifies it against the CRC-32 stored in the block index.
+    ///
+    /// Only the requested block's payload is read, so the cost does not depend on the position of the block.
+    pub fn block(&self, index: usize) -> Result<Vec<u8>, ZeckFormatError> {
+        let Some(entry) = self.entries.get(index) else {
+            return Err(ZeckFormatError::BlockIndexOutOfRange {
+                index,
+                block_count: self.entries.len(),
+            });
+        };
+
+        let range = block_payload_range(entry, index, self.payloads.len())?;
+        let block = decompress_zeck_payload(
+            &self.payloads[range],
+            entry.original_len,
+            self.flags,
+            0,
+            None,
+        )?;
+
+        let actual = crc32(&block);
+        if actual != entry.crc32 {
+            return Err(ZeckFormatError::BlockChecksumMismatch {
+                block_index: index,
+                expected: entry.crc32,
+                actual,
+            });
+        }
+
+        Ok(block)
+    }
+
+    /// Decompresses and verifies every block, returning the concatenated original data.
+    ///
+    /// The output grows as blocks decompress rather than reserving [`ZeckArchive::decompressed_size`] up front, since
+    /// the block index can declare gigabytes of blocks that fail to decompress.
+    pub fn decompress_all(&self) -> Result<Vec<u8>, ZeckFormatError> {
+        let mut output = Vec::new();
+        for index in 0..self.entries.len() {
+            output.extend_from_slice(&self.block(index)?);
+        }
+        Ok(output)
+    }
+}
+
+/// Compresses data into a multi-block archive, splitting it into blocks of `block_size` bytes (the last block may be shorter),
+/// and stores the result in a version 3 [`ZeckFile`] struct.
+///
+/// Every block is compressed independently with the same endianness, so blocks can later be decompressed in any order
+/// with [`ZeckArchive::block`]. Compressing in smaller blocks also keeps each block well below the sizes where
+/// Zeckendorf compression becomes slow.
+///
+/// Returns [`ZeckFormatError::ZeroBlockSize`] if `block_size` is 0.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::archive::compress_zeck_archive;
+/// # use zeck::zeck_file_format::decompress::decompress_zeck_file;
+/// let data: Vec<u8> = (0..100).collect();
+/// let zeck_file = compress_zeck_archive(&data, 32, false).unwrap();
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+/// ```
+#[wasm_bindgen]
+pub fn compress_zeck_archive(
+    data: &[u8],
+    block_size: usize,
+    is_big_endian: bool,
+) -> Result<ZeckFile, ZeckFormatError> {
+    if block_size == 0 {
+        return Err(ZeckFormatError::ZeroBlockSize);
+    }
+    let original_size = u64::try_from(data.len())
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
+    let flags = if is_big_endian {
+        ZECK_FLAG_BIG_ENDIAN
+    } else {
+        0
+    };
+
+    let blocks = data.chunks(block_size).collect::<Vec<_>>();
+    compress_blocks(&blocks, original_size, flags, ZECK_ARCHIVE_FORMAT_VERSION)
+}
+
+/// Compresses a list of length-delimited records into a multi-block archive with one block per record,
+/// and stores the result in a version 4 [`ZeckFile`] struct.
+///
+/// Unlike [`compress_zeck_archive`], which splits a single stream into fixed-size blocks, the block boundaries here are
+/// the record boundaries, so [`decompress_records`] restores the exact list of records, including empty ones.
+/// The block index already stores the record count and every record's original size, and the version marks the blocks
+/// as records, so [`crate::zeck_file_format::decompress::decompress_zeck_file`] does not concatenate them.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::archive::{compress_records, decompress_records};
+/// let records: [&[u8]; 3] = [b"first", b"", b"third"];
+/// let zeck_file = compress_records(&records, false).unwrap();
+/// assert_eq!(decompress_records(&zeck_file).unwrap(), records);
+/// ```
+pub fn compress_records(
+    records: &[&[u8]],
+    is_big_endian: bool,
+) -> Result<ZeckFile, ZeckFormatError> {
+    let total_size = records.iter().map(|record| record.len()).sum::<usize>();
+    let original_size = u64::try_from(total_size)
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: total_size })?;
+    let flags = if is_big_endian {
+        ZECK_FLAG_BIG_ENDIAN
+    } else {
+        0
+    };
+
+    compress_blocks(records, original_size, flags, ZECK_RECORDS_FORMAT_VERSION)
+}
+
+/// Decompresses a multi-block archive created by [`compress_records`] back into its list of records.
+///
+/// Every block is verified against its CRC-32. Single files and block archives from [`compress_zeck_archive`] have no
+/// record boundaries and fail with [`ZeckFormatError::UnexpectedVersion`].
+pub fn decompress_records(zeck_file: &ZeckFile) -> Result<Vec<Vec<u8>>, ZeckFormatError> {
+    if zeck_file.version != ZECK_RECORDS_FORMAT_VERSION {
+        return Err(ZeckFormatError::UnexpectedVersion {
+            found_version: zeck_file.version,
+            expected_version: ZECK_RECORDS_FORMAT_VERSION,
+        });
+    }
+
+    let archive = ZeckArchive::from_parts(
+        zeck_file.original_size,
+        zeck_file.flags,
+        &zeck_file.compressed_data,
+    )?;
+    (0..archive.block_count())
+        .map(|index| archive.block(index))
+        .collect()
+}
+
+/// Compresses each block independently and lays out the block count, block index, and block payloads in an archive
+/// of the given version.
+fn compress_blocks(
+    blocks: &[&[u8]],
+    original_size: u64,
+    flags: u8,
+    version: u8,
+) -> Result<ZeckFile, ZeckFormatError> {
+    let block_count =
+        u32::try_from(blocks.len()).map_err(|_| ZeckFormatError::DataSizeTooLarge {
+            size: original_size as usize,
+        })?;
+
+    let mut index = Vec::with_capacity(
+        ZECK_ARCHIVE_BLOCK_COUNT_SIZE + blocks.len() * ZECK_ARCHIVE_INDEX_ENTRY_SIZE,
+    );
+    index.extend_from_slice(&block_count.to_le_bytes());
+    let mut payloads = Vec::new();
+    for block in blocks {
+        let compressed_block = compress_with_flags(block, flags);
+        index.extend_from_slice(&(payloads.len() as u64).to_le_bytes());
+        index.extend_from_slice(&(compressed_block.len() as u64).to_le_bytes());
+        index.extend_from_slice(&(block.len() as u64).to_le_bytes());
+        index.extend_from_slice(&crc32(block).to_le_bytes());
+        payloads.extend_from_slice(&compressed_block);
+    }
+    index.extend_from_slice(&payloads);
+
+    Ok(ZeckFile {
+        version,
+        original_size,
+        flags,
+        extended_flags: 0,
+        // Every block has its own compressed length in the block index, so no padding bits are recorded
+        maybe_padding_bits: None,
+        // Every block has its own checksum in the block index instead
+        maybe_checksum: None,
+        maybe_filename: None,
+        maybe_rotation: None,
+        maybe_payload_crc: None,
+        maybe_mtime: None,
+        maybe_element_width: None,
+        compressed_data: index,
+    })
+}
+
+/// Returns the range of a block's compressed payload within the block payloads of an archive.
+///
+/// Fails with [`ZeckFormatError::BlockOutOfBounds`] if the entry's offset and length overflow, and with
+/// [`ZeckFormatError::Truncated`] if the block extends past the end of the payloads, including an offset that does not
+/// fit in a `usize` on 32-bit targets.
+fn block_payload_range(
+    entry: &ZeckArchiveBlockEntry,
+    block_index: usize,
+    payloads_len: usize,
+) -> Result<Range<usize>, ZeckFormatError> {
+    let Some(end) = entry.offset.checked_add(entry.compressed_len) else {
+        return Err(ZeckFormatError::BlockOutOfBounds { block_index });
+    };
+    let maybe_start = usize::try_from(entry.offset).ok();
+    let truncated = || ZeckFormatError::Truncated {
+        expected: usize::try_from(entry.compressed_len).unwrap_or(usize::MAX),
+        actual: maybe_start.map_or(0, |start| payloads_len.saturating_sub(start)),
+    };
+    let (Some(start), Ok(end)) = (maybe_start, usize::try_from(end)) else {
+        return Err(truncated());
+    };
+    if end > payloads_len {
+        return Err(truncated());
+    }
+    Ok(start..end)
+}
+
+/// Reads a fixed-size little endian field starting at `offset`. The caller must have checked the length.
+fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    bytes[offset..offset + N]
+        .try_into()
+        .expect("Length was checked by the caller")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zeck_file_format::compress::compress_zeck_be;
+    use crate::zeck_file_format::decompress::decompress_zeck_file;
+
+    fn three_block_archive_bytes() -> (Vec<u8>, Vec<u8>) {
+        let data: Vec<u8> = (0..24u8).map(|i| i.wrapping_mul(37)).collect();
+        let bytes = compress_zeck_archive(&data, 8, true).unwrap().to_bytes();
+        (data, bytes)
+    }
+
+    #[test]
+    fn opens_three_block_archive() {
+        // Arrange
+        let (_, bytes) = three_block_archive_bytes();
+
+        // Act
+        let archive = ZeckArchive::open(&bytes).unwrap();
+
+        // Assert
+        assert_eq!(archive.block_count(), 3);
+    }
+
+    #[test]
+    fn decompresses_block_one_without_touching_block_zero() {
+        // Arrange
+        let (data, mut bytes) = three_block_archive_bytes();
+        let payloads_start =
+            ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE + 3 * ZECK_ARCHIVE_INDEX_ENTRY_SIZE;
+        // Corrupt the first payload byte, which belongs to block 0
+        bytes[payloads_start] ^= 0xFF;
+        let archive = ZeckArchive::open(&bytes).unwrap();
+
+        // Act
+        let block = archive.block(1).unwrap();
+
+        // Assert
+        assert_eq!(block, data[8..16]);
+    }
+
+    #[test]
+    fn corrupted_block_fails_checksum() {
+        // Arrange
+        let (_, mut bytes) = three_block_archive_bytes();
+        let payloads_start =
+            ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE + 3 * ZECK_ARCHIVE_INDEX_ENTRY_SIZE;
+        bytes[payloads_start] ^= 0b0000_0100;
+        let archive = ZeckArchive::open(&bytes).unwrap();
+
+        // Act
+        let result = archive.block(0);
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ZeckFormatError::BlockChecksumMismatch { block_index: 0, .. })
+        ));
+    }
+
+    /// Returns the position of the original length of block `block_index` in serialized archive bytes.
+    fn original_len_position(block_index: usize) -> usize {
+        ZECK_HEADER_SIZE
+            + ZECK_ARCHIVE_BLOCK_COUNT_SIZE
+            + block_index * ZECK_ARCHIVE_INDEX_ENTRY_SIZE
+            + 16
+    }
+
+    #[test]
+    fn huge_block_size_is_rejected_on_open() {
+        // Arrange
+        let (_, mut bytes) = three_block_archive_bytes();
+        let huge_block_size = 1u64 << 40;
+        let position = original_len_position(1);
+        bytes[position..position + 8].copy_from_slice(&huge_block_size.to_le_bytes());
+        bytes[1..9].copy_from_slice(&(huge_block_size + 16).to_le_bytes());
+
+        // Act
+        let result = ZeckArchive::open(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::InvalidBlockSize {
+                block_index: 1,
+                value: huge_block_size,
+            })
+        );
+    }
+
+    #[test]
+    fn decompress_all_does_not_reserve_the_declared_size_up_front() {
+        // Arrange
+        // Every block shares one payload byte. The first block declares it empty, so it fails to decompress, and the
+        // others declare the largest allowed length, hundreds of GiB together.
+        let block_count = 64u32;
+        let mut compressed_data = block_count.to_le_bytes().to_vec();
+        for block_index in 0..block_count {
+            let original_len = if block_index == 0 {
+                0
+            } else {
+                ZECK_ARCHIVE_MAX_BLOCK_SIZE
+            };
+            compressed_data.extend(0u64.to_le_bytes());
+            compressed_data.extend(1u64.to_le_bytes());
+            compressed_data.extend(original_len.to_le_bytes());
+            compressed_data.extend(0u32.to_le_bytes());
+        }
+        compressed_data.push(1);
+        let original_size = u64::from(block_count - 1) * ZECK_ARCHIVE_MAX_BLOCK_SIZE;
+        let archive = ZeckArchive::from_parts(original_size, 0, &compressed_data).unwrap();
+
+        // Act
+        let result = archive.decompress_all();
+
+        // Assert
+        assert_eq!(archive.decompressed_size(), original_size);
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::DecompressedTooLarge {
+                expected_size: 0,
+                actual_size: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn blocks_larger_than_the_original_size_are_rejected() {
+        // Arrange
+        let (_, mut bytes) = three_block_archive_bytes();
+        let position = original_len_position(2);
+        bytes[position..position + 8].copy_from_slice(&9u64.to_le_bytes());
+
+        // Act
+        let result = ZeckArchive::open(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::InvalidBlockSize {
+                block_index: 2,
+                value: 9,
+            })
+        );
+    }
+
+    #[test]
+    fn decompress_all_round_trips() {
+        // Arrange
+        let (data, bytes) = three_block_archive_bytes();
+        let archive = ZeckArchive::open(&bytes).unwrap();
+
+        // Act
+        let decompressed = archive.decompress_all().unwrap();
+
+        // Assert
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn block_index_out_of_range_errors() {
+        let (_, bytes) = three_block_archive_bytes();
+        let archive = ZeckArchive::open(&bytes).unwrap();
+
+        let result = archive.block(3);
+
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::BlockIndexOutOfRange {
+                index: 3,
+                block_count: 3
+            })
+        );
+    }
+
+    #[test]
+    fn entry_past_payloads_errors() {
+        // Arrange
+        let (_, bytes) = three_block_archive_bytes();
+        // Drop the last payload byte, which belongs to block 2
+        let truncated = &bytes[..bytes.len() - 1];
+        let archive = ZeckArchive::open(&bytes).unwrap();
+        let block_len = archive.entries()[2].compressed_len as usize;
+
+        // Act
+        let result = ZeckArchive::open(truncated);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::Truncated {
+                expected: block_len,
+                actual: block_len - 1
+            })
+        );
+    }
+
+    #[test]
+    fn entry_with_offset_beyond_four_gib_errors() {
+        // Arrange
+        let (_, mut bytes) = three_block_archive_bytes();
+        let archive = ZeckArchive::open(&bytes).unwrap();
+        let block_len = archive.entries()[1].compressed_len as usize;
+        let block_one_offset_start =
+            ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE + ZECK_ARCHIVE_INDEX_ENTRY_SIZE;
+        // On 32-bit targets this offset would truncate to 0 if it were cast, pointing at block 0
+        bytes[block_one_offset_start..block_one_offset_start + 8]
+            .copy_from_slice(&(1u64 << 32).to_le_bytes());
+
+        // Act
+        let result = ZeckArchive::open(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::Truncated {
+                expected: block_len,
+                actual: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn entry_with_overflowing_end_errors() {
+        // Arrange
+        let (_, mut bytes) = three_block_archive_bytes();
+        let block_one_offset_start =
+            ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE + ZECK_ARCHIVE_INDEX_ENTRY_SIZE;
+        bytes[block_one_offset_start..block_one_offset_start + 8]
+            .copy_from_slice(&u64::MAX.to_le_bytes());
+
+        // Act
+        let result = ZeckArchive::open(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::BlockOutOfBounds { block_index: 1 })
+        );
+    }
+
+    #[test]
+    fn zero_block_size_is_an_error() {
+        // Arrange
+        let data = [1, 2, 3];
+
+        // Act
+        let result = compress_zeck_archive(&data, 0, false);
+
+        // Assert
+        assert_eq!(result, Err(ZeckFormatError::ZeroBlockSize));
+    }
+
+    #[test]
+    fn empty_input_has_no_blocks() {
+        let bytes = compress_zeck_archive(&[], 8, false).unwrap().to_bytes();
+
+        let archive = ZeckArchive::open(&bytes).unwrap();
+
+        assert_eq!(archive.block_count(), 0);
+        assert_eq!(archive.decompress_all().unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn records_of_varying_lengths_round_trip() {
+        // Arrange
+        let long_record: Vec<u8> = (0..200u8).collect();
+        let records: [&[u8]; 6] = [&[], b"a", &[0, 0, 0], &[], &long_record, &[]];
+        let zeck_file = compress_records(&records, true).unwrap();
+        let bytes = zeck_file.to_bytes();
+
+        // Act
+        let decompressed = decompress_records(&zeck_file).unwrap();
+        let archive = ZeckArchive::open(&bytes).unwrap();
+
+        // Assert
+        assert_eq!(decompressed, records);
+        assert_eq!(archive.block_count(), 6);
+        assert_eq!(zeck_file.original_size, 204);
+    }
+
+    #[test]
+    fn decompress_records_rejects_single_files_and_block_archives() {
+        for zeck_file in [
+            compress_zeck_be(b"record").unwrap(),
+            compress_zeck_archive(b"record", 3, true).unwrap(),
+        ] {
+            // Act
+            let result = decompress_records(&zeck_file);
+
+            // Assert
+            assert_eq!(
+                result,
+                Err(ZeckFormatError::UnexpectedVersion {
+                    found_version: zeck_file.version,
+                    expected_version: ZECK_RECORDS_FORMAT_VERSION
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn records_are_not_decompressed_as_one_stream() {
+        // Arrange
+        let records: [&[u8]; 2] = [b"first", b"second"];
+        let zeck_file = compress_records(&records, false).unwrap();
+        let bytes = zeck_file.to_bytes();
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+        let read_back = ZeckFile::read_from(&mut bytes.as_slice()).unwrap().unwrap();
+
+        // Assert
+        assert_eq!(zeck_file.version, ZECK_RECORDS_FORMAT_VERSION);
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::UnexpectedVersion {
+                found_version: ZECK_RECORDS_FORMAT_VERSION,
+                expected_version: ZECK_ARCHIVE_FORMAT_VERSION
+            })
+        );
+        assert_eq!(zeck_file.validate(), Ok(()));
+        assert_eq!(decompress_records(&read_back).unwrap(), records);
+        assert_eq!(
+            ZeckArchive::open(&bytes).unwrap().block(1).unwrap(),
+            b"second"
+        );
+    }
+}
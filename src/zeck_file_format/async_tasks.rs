@@ -0,0 +1,78 @@
+//! Async wrappers that run .zeck compression and decompression on tokio's blocking thread pool
+//!
+//! Compression and decompression are CPU-bound and can take a long time on larger inputs, so calling them directly
+//! from an async task stalls every other task on the same executor thread. These wrappers move the work onto
+//! [`tokio::task::spawn_blocking`] and await its result. They are only available with the `tokio` feature.
+
+use crate::zeck_file_format::compress::compress_zeck_be;
+use crate::zeck_file_format::decompress::decompress_zeck_file;
+use crate::zeck_file_format::{ZeckFile, ZeckFormatError};
+use std::io;
+use tokio::task::{JoinError, spawn_blocking};
+
+/// Compresses `data` with [`compress_zeck_be`] on tokio's blocking thread pool.
+///
+/// The data is taken by value because the blocking task must own it.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::async_tasks::compress_zeck_be_async;
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let zeck_file = compress_zeck_be_async(vec![12, 34]).await.unwrap();
+/// assert_eq!(zeck_file.original_size, 2);
+/// # });
+/// ```
+pub async fn compress_zeck_be_async(data: Vec<u8>) -> Result<ZeckFile, ZeckFormatError> {
+    spawn_blocking(move || compress_zeck_be(&data))
+        .await
+        .unwrap_or_else(join_error_to_result)
+}
+
+/// Decompresses `zeck_file` with [`decompress_zeck_file`] on tokio's blocking thread pool.
+///
+/// The file is taken by value because the blocking task must own it.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::async_tasks::decompress_zeck_file_async;
+/// # use zeck::zeck_file_format::compress::compress_zeck_le;
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let zeck_file = compress_zeck_le(&[12, 34]).unwrap();
+/// let decompressed = decompress_zeck_file_async(zeck_file).await.unwrap();
+/// assert_eq!(decompressed, vec![12, 34]);
+/// # });
+/// ```
+pub async fn decompress_zeck_file_async(zeck_file: ZeckFile) -> Result<Vec<u8>, ZeckFormatError> {
+    spawn_blocking(move || decompress_zeck_file(&zeck_file))
+        .await
+        .unwrap_or_else(join_error_to_result)
+}
+
+/// Resumes the panic if the blocking task panicked, the same as calling the function directly would have.
+/// Otherwise the task was cancelled because the runtime is shutting down, which is reported as an I/O error.
+fn join_error_to_result<T>(error: JoinError) -> Result<T, ZeckFormatError> {
+    if error.is_panic() {
+        std::panic::resume_unwind(error.into_panic());
+    }
+    Err(io::Error::other(error).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn round_trips_through_blocking_pool() {
+        // Arrange
+        let original = vec![0, 1, 2, 3, 255, 128];
+
+        // Act
+        let zeck_file = compress_zeck_be_async(original.clone()).await.unwrap();
+        let decompressed = decompress_zeck_file_async(zeck_file).await.unwrap();
+
+        // Assert
+        assert_eq!(decompressed, original);
+    }
+}
@@ -0,0 +1,303 @@
+//! Builder for constructing a [`ZeckFile`] from already compressed data
+
+use crate::Endianness;
+use crate::zeck_file_format::{
+    ZECK_EXTENDED_FLAG_DELTA, ZECK_EXTENDED_FLAG_MTIME, ZECK_EXTENDED_FLAG_UNCOMPRESSED,
+    file::ZeckFile, flags::ZeckFlags,
+};
+
+/// Builds a [`ZeckFile`] of the current version field by field, for tooling that repackages compressed data.
+///
+/// The padding bits are always computed from the compressed data, like the files produced by the compression functions,
+/// except for an uncompressed fallback, whose data is not EZBA bits. Setters that are not called keep their defaults: an
+/// original size of 0, empty compressed data, little endian, greedy representation, normal polarity, no delta encoding,
+/// compressed rather than uncompressed data, and no checksum, filename, or modification time.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::{checksum::crc32, compress::compress_zeck_be, decompress::decompress_zeck_file, file::deserialize_zeck_file, ZeckFile};
+/// let original = b"hi".to_vec();
+/// let compressed = compress_zeck_be(&original).unwrap();
+///
+/// let zeck_file = ZeckFile::builder()
+///     .original_size(compressed.original_size)
+///     .compressed_data(compressed.compressed_data)
+///     .big_endian(true)
+///     .filename(Some("hi.txt".to_string()))
+///     .checksum(Some(crc32(&original)))
+///     .build();
+///
+/// let deserialized = deserialize_zeck_file(&zeck_file.to_bytes()).unwrap();
+/// assert_eq!(deserialized.maybe_filename.as_deref(), Some("hi.txt"));
+/// assert_eq!(decompress_zeck_file(&deserialized).unwrap(), original);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZeckFileBuilder {
+    original_size: u64,
+    compressed_data: Vec<u8>,
+    flags: ZeckFlags,
+    extended_flags: u8,
+    maybe_checksum: Option<u32>,
+    maybe_filename: Option<String>,
+    maybe_mtime: Option<u64>,
+}
+
+impl ZeckFileBuilder {
+    /// Creates a builder with every field at its default. Equivalent to [`ZeckFile::builder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the original uncompressed size in bytes.
+    pub fn original_size(mut self, original_size: u64) -> Self {
+        self.original_size = original_size;
+        self
+    }
+
+    /// Sets the compressed data, without any header.
+    pub fn compressed_data(mut self, compressed_data: Vec<u8>) -> Self {
+        self.compressed_data = compressed_data;
+        self
+    }
+
+    /// Sets whether the data was compressed using big endian interpretation.
+    pub fn big_endian(mut self, is_big_endian: bool) -> Self {
+        self.flags.endianness = if is_big_endian {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        };
+        self
+    }
+
+    /// Sets whether the data was encoded using the lazy Fibonacci representation.
+    pub fn lazy(mut self, is_lazy: bool) -> Self {
+        self.flags.is_lazy = is_lazy;
+        self
+    }
+
+    /// Sets whether the input bytes were bitwise inverted before compression.
+    pub fn inverted(mut self, is_inverted: bool) -> Self {
+        self.flags.is_inverted = is_inverted;
+        self
+    }
+
+    /// Sets whether the input bytes were delta encoded before compression.
+    pub fn delta_encoded(mut self, is_delta_encoded: bool) -> Self {
+        if is_delta_encoded {
+            self.extended_flags |= ZECK_EXTENDED_FLAG_DELTA;
+        } else {
+            self.extended_flags &= !ZECK_EXTENDED_FLAG_DELTA;
+        }
+        self
+    }
+
+    /// Sets whether the compressed data is the raw input stored as is (see
+    /// [`crate::zeck_file_format::ZECK_EXTENDED_FLAG_UNCOMPRESSED`]). Such a file records no padding bits.
+    pub fn uncompressed_fallback(mut self, is_uncompressed_fallback: bool) -> Self {
+        if is_uncompressed_fallback {
+            self.extended_flags |= ZECK_EXTENDED_FLAG_UNCOMPRESSED;
+        } else {
+            self.extended_flags &= !ZECK_EXTENDED_FLAG_UNCOMPRESSED;
+        }
+        self
+    }
+
+    /// Sets the name of the original file, or clears it with [`None`].
+    pub fn filename(mut self, maybe_filename: Option<String>) -> Self {
+        self.maybe_filename = maybe_filename;
+        self
+    }
+
+    /// Sets the CRC-32 (see [`crate::zeck_file_format::checksum::crc32`]) of the original data, or clears it with [`None`].
+    pub fn checksum(mut self, maybe_checksum: Option<u32>) -> Self {
+        self.maybe_checksum = maybe_checksum;
+        self
+    }
+
+    /// Sets the modification time of the original file in seconds since the Unix epoch, or clears it with [`None`].
+    pub fn mtime(mut self, maybe_mtime: Option<u64>) -> Self {
+        self.maybe_mtime = maybe_mtime;
+        self
+    }
+
+    /// Builds the [`ZeckFile`], setting the flags of the optional header fields that are present.
+    pub fn build(self) -> ZeckFile {
+        let flags = ZeckFlags {
+            has_checksum: self.maybe_checksum.is_some(),
+            has_filename: self.maybe_filename.is_some(),
+            ..self.flags
+        };
+        let mut zeck_file =
+            ZeckFile::new_with_flags(self.original_size, self.compressed_data, flags.to_byte());
+        zeck_file.maybe_checksum = self.maybe_checksum;
+        zeck_file.maybe_filename = self.maybe_filename;
+        zeck_file.maybe_mtime = self.maybe_mtime;
+        if self.maybe_mtime.is_some() {
+            zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_MTIME);
+        }
+        if self.extended_flags != 0 {
+            zeck_file.insert_extended_flags(self.extended_flags);
+        }
+        if self.extended_flags & ZECK_EXTENDED_FLAG_UNCOMPRESSED != 0 {
+            zeck_file.update_flags(|flags| flags.has_padding_bits = false);
+            zeck_file.maybe_padding_bits = None;
+        }
+        zeck_file
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Endianness;
+    use crate::zeck_file_format::ZECK_PAYLOAD_LENGTH_SIZE;
+    use crate::zeck_file_format::checksum::crc32;
+    use crate::zeck_file_format::compress::{compress_zeck_delta, compress_zeck_le};
+    use crate::zeck_file_format::decompress::decompress_zeck_file;
+    use crate::zeck_file_format::error::ZeckFormatError;
+    use crate::zeck_file_format::file::deserialize_zeck_file;
+
+    #[test]
+    fn built_file_matches_compressed_file() {
+        // Arrange
+        let compressed = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap();
+
+        // Act
+        let built = ZeckFile::builder()
+            .original_size(compressed.original_size)
+            .compressed_data(compressed.compressed_data.clone())
+            .build();
+
+        // Assert
+        assert_eq!(built, compressed);
+    }
+
+    #[test]
+    fn built_file_round_trips_through_bytes() {
+        // Arrange
+        let original = vec![9, 8, 7, 6];
+        let compressed = compress_zeck_le(&original).unwrap();
+        let zeck_file = ZeckFile::builder()
+            .original_size(compressed.original_size)
+            .compressed_data(compressed.compressed_data)
+            .filename(Some("numbers.bin".to_string()))
+            .checksum(Some(crc32(&original)))
+            .build();
+
+        // Act
+        let bytes = zeck_file.to_bytes();
+        let deserialized = deserialize_zeck_file(&bytes).unwrap();
+
+        // Assert
+        assert_eq!(bytes.len(), zeck_file.total_size());
+        assert_eq!(deserialized, zeck_file);
+        assert_eq!(
+            ZeckFile::read_from(&mut bytes.as_slice()),
+            Ok(Some(zeck_file))
+        );
+        assert_eq!(decompress_zeck_file(&deserialized), Ok(original));
+    }
+
+    #[test]
+    fn built_delta_encoded_file_matches_compressed_file() {
+        // Arrange
+        let compressed = compress_zeck_delta(&[1, 2, 3, 4], Endianness::Big).unwrap();
+
+        // Act
+        let built = ZeckFile::builder()
+            .original_size(compressed.original_size)
+            .compressed_data(compressed.compressed_data.clone())
+            .big_endian(true)
+            .delta_encoded(true)
+            .build();
+
+        // Assert
+        assert_eq!(built, compressed);
+        assert_eq!(decompress_zeck_file(&built), Ok(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn flag_setters_can_be_cleared() {
+        // Act
+        let zeck_file = ZeckFile::builder()
+            .big_endian(true)
+            .lazy(true)
+            .inverted(true)
+            .lazy(false)
+            .build();
+
+        // Assert
+        assert!(zeck_file.is_big_endian());
+        assert!(!zeck_file.is_lazy());
+        assert!(zeck_file.is_inverted());
+    }
+
+    #[test]
+    fn wrong_checksum_fails_decompression() {
+        // Arrange
+        let original = vec![9, 8, 7, 6];
+        let compressed = compress_zeck_le(&original).unwrap();
+        let expected = crc32(&original) ^ 1;
+        let zeck_file = ZeckFile::builder()
+            .original_size(compressed.original_size)
+            .compressed_data(compressed.compressed_data)
+            .checksum(Some(expected))
+            .build();
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::ChecksumMismatch {
+                expected,
+                actual: crc32(&original),
+            })
+        );
+    }
+
+    #[test]
+    fn truncated_filename_is_header_too_short() {
+        // Arrange
+        let zeck_file = ZeckFile::builder()
+            .compressed_data(vec![1])
+            .filename(Some("name".to_string()))
+            .build();
+        let bytes = zeck_file.to_bytes();
+        let filename_end = bytes.len() - zeck_file.compressed_data.len();
+
+        // Act
+        let result = deserialize_zeck_file(&bytes[..filename_end - 1]);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::HeaderTooShort {
+                actual_length: filename_end - 1,
+                required_length: filename_end,
+            })
+        );
+    }
+
+    #[test]
+    fn non_utf8_filename_is_rejected() {
+        // Arrange
+        let zeck_file = ZeckFile::builder()
+            .compressed_data(vec![1])
+            .filename(Some("a".to_string()))
+            .build();
+        let mut bytes = zeck_file.to_bytes();
+        let filename_index =
+            bytes.len() - zeck_file.compressed_data.len() - ZECK_PAYLOAD_LENGTH_SIZE - 1;
+        bytes[filename_index] = 0xFF;
+
+        // Act
+        let result = deserialize_zeck_file(&bytes);
+
+        // Assert
+        assert_eq!(result, Err(ZeckFormatError::InvalidFilename));
+    }
+}
@@ -0,0 +1,46 @@
+//! CRC-32 checksums for the .zeck file format
+
+/// Lookup table for the reflected CRC-32 (IEEE 802.3) polynomial `0xEDB88320`, built at compile time.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the CRC-32 (IEEE 802.3) checksum of the given data, the same checksum used by zip, gzip, and PNG.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::checksum::crc32;
+/// assert_eq!(crc32(b""), 0);
+/// assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+/// ```
+pub fn crc32(data: &[u8]) -> u32 {
+    !crc32_update(CRC32_INITIAL_STATE, data)
+}
+
+/// The CRC-32 state before any data, which [`crc32_update`] starts from.
+pub(crate) const CRC32_INITIAL_STATE: u32 = 0xFFFF_FFFF;
+
+/// Feeds more data into a running CRC-32 state, so data that arrives in pieces can be checksummed without collecting
+/// it. The checksum is the bitwise NOT of the final state.
+pub(crate) fn crc32_update(state: u32, data: &[u8]) -> u32 {
+    data.iter().fold(state, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
@@ -1,11 +1,23 @@
 //! Compression functions for the .zeck file format
 
+use crate::lucas::{lucas_compress_be, lucas_compress_le};
 use crate::zeck_file_format::error::ZeckFormatError;
 use crate::zeck_file_format::file::ZeckFile;
+use crate::zeck_file_format::flags::ZeckFlags;
+use crate::zeck_file_format::sparse::encode_sparse_index_list;
+use crate::zeck_file_format::{
+    ZECK_EXTENDED_FLAG_DELTA, ZECK_EXTENDED_FLAG_ELEMENT_WIDTH, ZECK_EXTENDED_FLAG_LUCAS,
+    ZECK_EXTENDED_FLAG_ROTATION, ZECK_EXTENDED_FLAG_SPARSE, ZECK_FLAG_BIG_ENDIAN,
+    ZECK_FLAG_INVERTED, ZECK_FLAG_LAZY,
+};
 use crate::{
-    PadlessCompressionResult, padless_zeckendorf_compress_be_dangerous,
-    padless_zeckendorf_compress_best_dangerous, padless_zeckendorf_compress_le_dangerous,
+    EndianChoice, Endianness, EzbaBits, PadlessCompressionResult, compressed_byte_length,
+    compressed_len_sort_key, entropy_encode_ezba, lazy_ezba_from_biguint,
+    memoized_zeckendorf_list_descending_for_biguint, pack_ezba_bits_to_bytes,
+    padless_zeckendorf_compress_be_dangerous, padless_zeckendorf_compress_best_dangerous,
+    padless_zeckendorf_compress_le_dangerous, unpack_bytes_to_ezba_bits, zl_to_ezl,
 };
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 use tsify::Tsify;
@@ -41,11 +53,86 @@ pub enum BestCompressionResult {
     },
 }
 
+impl BestCompressionResult {
+    /// Returns the length of the best file's compressed data, or [`None`] if neither interpretation compressed the data.
+    pub fn compressed_len(&self) -> Option<usize> {
+        match self {
+            BestCompressionResult::BigEndianBest { zeck_file, .. }
+            | BestCompressionResult::LittleEndianBest { zeck_file, .. } => {
+                Some(zeck_file.compressed_data.len())
+            }
+            BestCompressionResult::Neither { .. } => None,
+        }
+    }
+
+    /// Returns the result with the shortest compressed data, where [`BestCompressionResult::Neither`] compares as
+    /// worse than any compressed result. Ties go to the earliest result. Returns [`None`] if `results` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::compress::{compress_zeck_best, BestCompressionResult};
+    /// let results = [
+    ///     compress_zeck_best(&[0xFF; 8]).unwrap(),
+    ///     compress_zeck_best(&[0, 0, 0, 1]).unwrap(),
+    /// ];
+    /// assert_eq!(BestCompressionResult::best_of(&results), Some(&results[1]));
+    /// ```
+    pub fn best_of(results: &[Self]) -> Option<&Self> {
+        results
+            .iter()
+            .min_by_key(|result| compressed_len_sort_key(result.compressed_len()))
+    }
+}
+
+/// Default input size in bytes for [`compress_zeck_best_with_threshold`], above which it compresses only once, with
+/// the endianness from [`predict_best_endianness`], instead of trying both endiannesses. `zeck-compress` uses it in
+/// best mode.
+///
+/// This matches the size above which compression becomes unstable, where a second attempt costs the most.
+pub const ZECK_BEST_EXHAUSTIVE_THRESHOLD: usize = 10_000;
+
+/// Predicts the endianness whose interpretation of `data` is the smaller integer, without compressing it.
+///
+/// The compressed size tracks the largest Fibonacci number in the Zeckendorf representation, so the smaller integer
+/// usually compresses better, and always does when it has fewer significant bytes, such as when one end of the data
+/// has more zero bytes. When both integers have the same number of significant bytes, the number of Fibonacci terms
+/// also matters and the prediction can miss by a few bytes; use [`exact_compression_preview`] for exact sizes. This
+/// compares the bytes from the most significant end of each interpretation, so it takes linear time and does not
+/// allocate. Ties go to little endian, like [`compress_zeck_best`].
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::compress::predict_best_endianness;
+/// assert_eq!(predict_best_endianness(&[0, 1]), Endianness::Big);
+/// assert_eq!(predict_best_endianness(&[1, 0]), Endianness::Little);
+/// assert_eq!(predict_best_endianness(&[7, 7]), Endianness::Little);
+/// ```
+#[wasm_bindgen]
+pub fn predict_best_endianness(data: &[u8]) -> Endianness {
+    let be_significant_len = data.len() - data.iter().take_while(|&&byte| byte == 0).count();
+    let le_significant_len = data.len() - data.iter().rev().take_while(|&&byte| byte == 0).count();
+    let be_ordering = be_significant_len.cmp(&le_significant_len).then_with(|| {
+        // The significant bytes of both interpretations have the same length, so compare them most significant first
+        data[data.len() - be_significant_len..]
+            .iter()
+            .cmp(data[..le_significant_len].iter().rev())
+    });
+    if be_ordering.is_lt() {
+        Endianness::Big
+    } else {
+        Endianness::Little
+    }
+}
+
 /// Compresses data using the Zeckendorf algorithm with automatic endianness selection,
 /// and stores the result in a [`BestCompressionResult`] struct.
 ///
 /// This function attempts compression with both big endian and little endian interpretations,
 /// and returns the best result, or if neither produced compression (both were larger than the original).
+/// To compress large inputs only once, with a predicted endianness, use [`compress_zeck_best_with_threshold`].
 ///
 /// # ⚠️ Warning
 ///
@@ -104,9 +191,8 @@ pub enum BestCompressionResult {
 pub fn compress_zeck_best(data: &[u8]) -> Result<BestCompressionResult, ZeckFormatError> {
     let original_size = u64::try_from(data.len())
         .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
-    let result = padless_zeckendorf_compress_best_dangerous(data);
 
-    match result {
+    match padless_zeckendorf_compress_best_dangerous(data) {
         PadlessCompressionResult::BigEndianBest {
             compressed_data,
             le_size,
@@ -127,6 +213,144 @@ pub fn compress_zeck_best(data: &[u8]) -> Result<BestCompressionResult, ZeckForm
     }
 }
 
+/// Result of [`compress_zeck_best_with_threshold`], which only tries both endiannesses for inputs up to its threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThresholdCompressionResult {
+    /// The input was at most the threshold, so both endiannesses were tried, as by [`compress_zeck_best`].
+    Compared(BestCompressionResult),
+    /// The input was larger than the threshold, so it was compressed only with the endianness from
+    /// [`predict_best_endianness`]. The compressed size for the other endianness was never computed.
+    Predicted {
+        /// The endianness the data was compressed with
+        endianness: Endianness,
+        /// The compressed file, or [`None`] if the predicted endianness did not make the data smaller
+        maybe_zeck_file: Option<ZeckFile>,
+        /// Compressed size using the predicted endianness
+        size: usize,
+    },
+}
+
+impl ThresholdCompressionResult {
+    /// Returns the length of the compressed data, or [`None`] if the data did not compress.
+    pub fn compressed_len(&self) -> Option<usize> {
+        match self {
+            ThresholdCompressionResult::Compared(result) => result.compressed_len(),
+            ThresholdCompressionResult::Predicted {
+                maybe_zeck_file, ..
+            } => maybe_zeck_file
+                .as_ref()
+                .map(|zeck_file| zeck_file.compressed_data.len()),
+        }
+    }
+}
+
+/// Like [`compress_zeck_best`], but compresses inputs larger than `exhaustive_threshold` bytes only once.
+///
+/// Inputs of at most `exhaustive_threshold` bytes are compressed with both endiannesses, exactly as by
+/// [`compress_zeck_best`]. Larger inputs are compressed only with the endianness from [`predict_best_endianness`],
+/// which halves the work but may miss a slightly smaller result with the other endianness, whose size is then not
+/// reported. [`ZECK_BEST_EXHAUSTIVE_THRESHOLD`] is a reasonable threshold.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::compress::{
+/// #     compress_zeck_best_with_threshold, BestCompressionResult, ThresholdCompressionResult,
+/// # };
+/// # use zeck::zeck_file_format::decompress::decompress_zeck_file;
+/// let data = vec![1, 0, 0];
+/// let ThresholdCompressionResult::Predicted { endianness, maybe_zeck_file: Some(zeck_file), .. } =
+///     compress_zeck_best_with_threshold(&data, 2).unwrap()
+/// else {
+///     panic!("little endian is the smaller integer");
+/// };
+/// assert_eq!(endianness, Endianness::Little);
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+///
+/// assert!(matches!(
+///     compress_zeck_best_with_threshold(&data, 3).unwrap(),
+///     ThresholdCompressionResult::Compared(BestCompressionResult::LittleEndianBest { .. })
+/// ));
+/// ```
+pub fn compress_zeck_best_with_threshold(
+    data: &[u8],
+    exhaustive_threshold: usize,
+) -> Result<ThresholdCompressionResult, ZeckFormatError> {
+    if data.len() <= exhaustive_threshold {
+        return compress_zeck_best(data).map(ThresholdCompressionResult::Compared);
+    }
+
+    let endianness = predict_best_endianness(data);
+    let zeck_file = match endianness {
+        Endianness::Big => compress_zeck_be(data)?,
+        Endianness::Little => compress_zeck_le(data)?,
+    };
+    let size = zeck_file.compressed_data.len();
+    Ok(ThresholdCompressionResult::Predicted {
+        endianness,
+        maybe_zeck_file: (size < data.len()).then_some(zeck_file),
+        size,
+    })
+}
+
+/// Compressed sizes for both endiannesses, as reported by [`exact_compression_preview`] without compressing the data.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionPreview {
+    /// Compressed size using big endian interpretation
+    pub be_size: usize,
+    /// Compressed size using little endian interpretation
+    pub le_size: usize,
+    /// The endianness with the smaller compressed size, preferring little endian on a tie like [`compress_zeck_best`]
+    pub best_endianness: Endianness,
+    /// Whether the compressed size for [`Self::best_endianness`] is smaller than the original data
+    pub is_favorable: bool,
+}
+
+/// Reports the compressed sizes [`compress_zeck_best`] would compare, without producing the compressed data.
+///
+/// The sizes come from [`compressed_byte_length`], which counts the EZBA bits from the Zeckendorf representation
+/// instead of allocating and packing them. The sizes are exact, so this is not cheap: finding the Zeckendorf
+/// representation of both integers is most of the work [`compress_zeck_best`] does, and only the EZBA bits are
+/// skipped. To guess the better endianness in linear time, use [`predict_best_endianness`] instead.
+/// The sizes are of the compressed data only, like the sizes in [`BestCompressionResult`], and do not include the
+/// .zeck header.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::compress::{compress_zeck_be, exact_compression_preview};
+/// let preview = exact_compression_preview(&[0, 1]);
+/// assert_eq!(preview.best_endianness, Endianness::Big);
+/// assert!(preview.is_favorable);
+/// assert_eq!(preview.be_size, compress_zeck_be(&[0, 1]).unwrap().compressed_data.len());
+///
+/// assert!(!exact_compression_preview(&[0xFF; 8]).is_favorable);
+/// ```
+#[wasm_bindgen]
+pub fn exact_compression_preview(data: &[u8]) -> CompressionPreview {
+    let be_size = compressed_byte_length(data, Endianness::Big);
+    let le_size = compressed_byte_length(data, Endianness::Little);
+    let (best_endianness, best_size) = if be_size < le_size {
+        (Endianness::Big, be_size)
+    } else {
+        (Endianness::Little, le_size)
+    };
+    CompressionPreview {
+        be_size,
+        le_size,
+        best_endianness,
+        is_favorable: best_size < data.len(),
+    }
+}
+
 /// Compresses data using the Zeckendorf algorithm with little endian interpretation,
 /// and stores the result in a [`ZeckFile`] struct.
 ///
@@ -194,3 +418,1008 @@ pub fn compress_zeck_be(data: &[u8]) -> Result<ZeckFile, ZeckFormatError> {
     let compressed_data = padless_zeckendorf_compress_be_dangerous(data);
     Ok(ZeckFile::new(original_size, compressed_data, true))
 }
+
+/// Compresses the bytes yielded by an iterator like [`compress_zeck_be`], for generators and streams that do not
+/// already hold the data in a slice.
+///
+/// The Zeckendorf representation is of the whole input as one integer, so the bytes are still collected internally;
+/// this only saves the caller from building the buffer.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::{compress::zeckendorf_compress_be_from_iter, decompress::decompress_zeck_file};
+/// let zeck_file = zeckendorf_compress_be_from_iter([0, 1, 2].into_iter()).unwrap();
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), vec![0, 1, 2]);
+/// ```
+pub fn zeckendorf_compress_be_from_iter(
+    iter: impl Iterator<Item = u8>,
+) -> Result<ZeckFile, ZeckFormatError> {
+    compress_zeck_be(&iter.collect::<Vec<u8>>())
+}
+
+/// Compresses the bytes yielded by an iterator like [`compress_zeck_le`], for generators and streams that do not
+/// already hold the data in a slice.
+///
+/// The Zeckendorf representation is of the whole input as one integer, so the bytes are still collected internally;
+/// this only saves the caller from building the buffer.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::compress::{compress_zeck_le, zeckendorf_compress_le_from_iter};
+/// let zeck_file = zeckendorf_compress_le_from_iter((1..=3).rev()).unwrap();
+/// assert_eq!(zeck_file, compress_zeck_le(&[3, 2, 1]).unwrap());
+/// ```
+pub fn zeckendorf_compress_le_from_iter(
+    iter: impl Iterator<Item = u8>,
+) -> Result<ZeckFile, ZeckFormatError> {
+    compress_zeck_le(&iter.collect::<Vec<u8>>())
+}
+
+/// Compresses data using the Zeckendorf algorithm with the given [`EndianChoice`], and stores the result in a [`ZeckFile`] struct.
+///
+/// This is the single entry point for [`compress_zeck_be`], [`compress_zeck_le`], and [`compress_zeck_best`].
+/// With [`EndianChoice::Best`], if neither endianness produces a smaller output than the original, this returns
+/// [`ZeckFormatError::CompressionFailed`] with the sizes of both attempts.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{EndianChoice, ZeckFormatError, compress_zeck, decompress_zeck_file};
+/// let data = vec![1, 0];
+/// let zeck_file = compress_zeck(&data, EndianChoice::Best).unwrap();
+/// assert!(!zeck_file.is_big_endian());
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+///
+/// assert!(compress_zeck(&data, EndianChoice::Big).unwrap().is_big_endian());
+///
+/// // 255 does not compress with either endianness
+/// assert!(matches!(
+///     compress_zeck(&[255], EndianChoice::Best),
+///     Err(ZeckFormatError::CompressionFailed { original_size: 1, be_size: 2, le_size: 2 })
+/// ));
+/// ```
+#[wasm_bindgen]
+pub fn compress_zeck(data: &[u8], choice: EndianChoice) -> Result<ZeckFile, ZeckFormatError> {
+    match choice {
+        EndianChoice::Big => compress_zeck_be(data),
+        EndianChoice::Little => compress_zeck_le(data),
+        EndianChoice::Best => match compress_zeck_best(data)? {
+            BestCompressionResult::BigEndianBest { zeck_file, .. }
+            | BestCompressionResult::LittleEndianBest { zeck_file, .. } => Ok(zeck_file),
+            BestCompressionResult::Neither { be_size, le_size } => {
+                Err(ZeckFormatError::CompressionFailed {
+                    original_size: data.len(),
+                    be_size,
+                    le_size,
+                })
+            }
+        },
+    }
+}
+
+/// Compresses data with whichever endianness compresses it better and returns the serialized .zeck file bytes,
+/// ready to be written to disk. This is the simplest way to compress data; see
+/// [`crate::zeck_file_format::decompress::decompress_from_zeck_bytes`] for the reverse.
+///
+/// Unlike [`compress_zeck_best`], this never fails because the data did not compress: incompressible data is still
+/// stored, and the result is then larger than the input.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{compress_to_zeck_bytes, decompress_from_zeck_bytes};
+/// let data = b"hello, zeck".to_vec();
+/// let zeck_bytes = compress_to_zeck_bytes(&data).unwrap();
+/// assert_eq!(decompress_from_zeck_bytes(&zeck_bytes).unwrap(), data);
+/// ```
+#[wasm_bindgen]
+pub fn compress_to_zeck_bytes(data: &[u8]) -> Result<Vec<u8>, ZeckFormatError> {
+    let zeck_file = match compress_zeck_best(data)? {
+        BestCompressionResult::BigEndianBest { zeck_file, .. }
+        | BestCompressionResult::LittleEndianBest { zeck_file, .. } => zeck_file,
+        // Only incompressible data is compressed a second time, preferring little endian on a tie like the other cases
+        BestCompressionResult::Neither { be_size, le_size } if be_size < le_size => {
+            compress_zeck_be(data)?
+        }
+        BestCompressionResult::Neither { .. } => compress_zeck_le(data)?,
+    };
+    Ok(zeck_file.to_bytes())
+}
+
+/// Compresses a slice of [`u64`] values using the Zeckendorf algorithm with the given [`Endianness`], and stores the
+/// result in a [`ZeckFile`] struct.
+///
+/// Each value is written as 8 bytes in the given byte order, so the whole slice is read as one integer in that order.
+/// The file records an element width of 8 (see [`ZeckFile::maybe_element_width`]), and the original size in the header
+/// is then `values.len() * 8`, so [`crate::zeck_file_format::decompress::decompress_to_u64_vec`] knows the values'
+/// width and count, and rejects files that were not compressed from [`u64`] values.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::{compress::zeckendorf_compress_u64_slice, decompress::decompress_to_u64_vec};
+/// let values = vec![0, 1, 1_000_000, u64::MAX];
+/// let zeck_file = zeckendorf_compress_u64_slice(&values, Endianness::Big).unwrap();
+/// assert_eq!(zeck_file.original_size, 32);
+/// assert_eq!(zeck_file.maybe_element_width, Some(8));
+/// assert_eq!(decompress_to_u64_vec(&zeck_file).unwrap(), values);
+/// ```
+#[wasm_bindgen]
+pub fn zeckendorf_compress_u64_slice(
+    values: &[u64],
+    endianness: Endianness,
+) -> Result<ZeckFile, ZeckFormatError> {
+    let data = values
+        .iter()
+        .flat_map(|value| match endianness {
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
+        })
+        .collect::<Vec<_>>();
+    let mut zeck_file = compress_zeck(&data, endianness.into())?;
+    zeck_file.maybe_element_width = Some(std::mem::size_of::<u64>() as u8);
+    zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_ELEMENT_WIDTH);
+    Ok(zeck_file)
+}
+
+/// Compresses data using the Zeckendorf algorithm with the given [`Endianness`], then entropy codes the EZBA bits in a
+/// second stage (see [`crate::ezba_entropy`]), and stores the result in a [`ZeckFile`] struct.
+///
+/// The second stage sets [`ZECK_FLAG_ENTROPY_CODED`](super::ZECK_FLAG_ENTROPY_CODED), so
+/// [`crate::zeck_file_format::decompress::decompress_zeck_file`] undoes it automatically. Whether it beats single-stage compression depends on the data; the
+/// `generate-statistics` example compares both across several data distributions.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{Endianness, compress_zeck_entropy_coded, decompress_zeck_file};
+/// let data = vec![0, 0, 0, 0, 0, 0, 0, 1];
+/// let zeck_file = compress_zeck_entropy_coded(&data, Endianness::Little).unwrap();
+/// assert!(zeck_file.is_entropy_coded());
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+/// ```
+#[wasm_bindgen]
+pub fn compress_zeck_entropy_coded(
+    data: &[u8],
+    endianness: Endianness,
+) -> Result<ZeckFile, ZeckFormatError> {
+    let original_size = u64::try_from(data.len())
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
+    let flags = ZeckFlags {
+        endianness,
+        is_entropy_coded: true,
+        ..ZeckFlags::NONE
+    }
+    .to_byte();
+    Ok(ZeckFile::new_with_flags(
+        original_size,
+        compress_with_flags(data, flags),
+        flags,
+    ))
+}
+
+/// Compresses data using the Zeckendorf algorithm with the given [`Endianness`] after the delta encoding
+/// pre-transform, and stores the result in a [`ZeckFile`] struct.
+///
+/// Every byte after the first is replaced by its wrapping difference from the previous byte, which clusters smooth
+/// data, such as ramps or slowly changing samples, toward zero and often shrinks the integer being compressed. The
+/// pre-transform sets [`ZECK_EXTENDED_FLAG_DELTA`], so [`crate::zeck_file_format::decompress::decompress_zeck_file`]
+/// undoes it automatically. The `generate-statistics` example compares it with no pre-transform on text and binary data.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::{compress::{compress_zeck_delta, compress_zeck_le}, decompress::decompress_zeck_file};
+/// let data = vec![200, 201, 202, 203, 204, 205]; // Delta encodes to [200, 1, 1, 1, 1, 1]
+/// let zeck_file = compress_zeck_delta(&data, Endianness::Little).unwrap();
+/// assert!(zeck_file.is_delta_encoded());
+/// assert!(zeck_file.compressed_data.len() < compress_zeck_le(&data).unwrap().compressed_data.len());
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+/// ```
+#[wasm_bindgen]
+pub fn compress_zeck_delta(
+    data: &[u8],
+    endianness: Endianness,
+) -> Result<ZeckFile, ZeckFormatError> {
+    let original_size = u64::try_from(data.len())
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
+    let flags = if endianness == Endianness::Big {
+        ZECK_FLAG_BIG_ENDIAN
+    } else {
+        0
+    };
+    let mut zeck_file = ZeckFile::new_with_flags(
+        original_size,
+        compress_with_flags(&delta_encode(data), flags),
+        flags,
+    );
+    zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_DELTA);
+    Ok(zeck_file)
+}
+
+/// Compresses data using the Zeckendorf algorithm with the given [`Endianness`], storing the Zeckendorf
+/// representation as a sparse index list (see [`crate::zeck_file_format::sparse`]) when that is smaller than the EZBA
+/// bits, and stores the result in a [`ZeckFile`] struct.
+///
+/// The EZBA spends a bit on every Fibonacci index below the largest one, so a number with a few terms and a huge top
+/// index, such as a single large Fibonacci number, compresses to far fewer bytes as a list of its indices. The sparse
+/// index list sets [`ZECK_EXTENDED_FLAG_SPARSE`] (see [`ZeckFile::is_sparse`]), so
+/// [`crate::zeck_file_format::decompress::decompress_zeck_file`] reads it automatically. Otherwise, the file is the same
+/// as the one from [`compress_zeck_be`] or [`compress_zeck_le`]. All-zero data has an empty index list, so it is
+/// always stored as one.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{Endianness, fast_doubling_fibonacci_biguint};
+/// # use zeck::zeck_file_format::{compress::compress_zeck_sparse, decompress::decompress_zeck_file};
+/// let data = fast_doubling_fibonacci_biguint(1_000).to_bytes_le(); // 87 bytes
+/// let zeck_file = compress_zeck_sparse(&data, Endianness::Little).unwrap();
+/// assert!(zeck_file.is_sparse());
+/// assert_eq!(zeck_file.compressed_data.len(), 2);
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+///
+/// let zeck_file = compress_zeck_sparse(&[0xAB, 0xCD], Endianness::Big).unwrap();
+/// assert!(!zeck_file.is_sparse());
+/// ```
+#[wasm_bindgen]
+pub fn compress_zeck_sparse(
+    data: &[u8],
+    endianness: Endianness,
+) -> Result<ZeckFile, ZeckFormatError> {
+    let original_size = u64::try_from(data.len())
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
+    let (flags, data_as_biguint) = match endianness {
+        Endianness::Big => (ZECK_FLAG_BIG_ENDIAN, BigUint::from_bytes_be(data)),
+        Endianness::Little => (0, BigUint::from_bytes_le(data)),
+    };
+    let data_as_ezld = zl_to_ezl(&memoized_zeckendorf_list_descending_for_biguint(
+        &data_as_biguint,
+    ));
+    let packed_ezba = EzbaBits::from_ezld(&data_as_ezld).into_packed_bytes();
+    let sparse_index_list = encode_sparse_index_list(&data_as_ezld);
+
+    if sparse_index_list.len() >= packed_ezba.len() {
+        return Ok(ZeckFile::new_with_flags(original_size, packed_ezba, flags));
+    }
+    let mut zeck_file = ZeckFile::new_with_flags(original_size, sparse_index_list, flags);
+    zeck_file.update_flags(|flags| flags.has_padding_bits = false);
+    zeck_file.maybe_padding_bits = None;
+    zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_SPARSE);
+    Ok(zeck_file)
+}
+
+/// Compresses data using the experimental Lucas codec (see [`crate::lucas`]) with the given [`Endianness`], and stores
+/// the result in a [`ZeckFile`] struct.
+///
+/// The data is stored as the greedy Lucas representation of the integer instead of its Zeckendorf representation.
+/// The codec sets [`ZECK_EXTENDED_FLAG_LUCAS`] (see [`ZeckFile::is_lucas`]), so
+/// [`crate::zeck_file_format::decompress::decompress_zeck_file`] decodes it automatically. The `generate-statistics`
+/// example compares the compressed sizes of the two codecs.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::{compress::compress_zeck_lucas, decompress::decompress_zeck_file};
+/// let data = vec![0, 1, 2, 3];
+/// let zeck_file = compress_zeck_lucas(&data, Endianness::Big).unwrap();
+/// assert!(zeck_file.is_lucas());
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+/// ```
+#[wasm_bindgen]
+pub fn compress_zeck_lucas(
+    data: &[u8],
+    endianness: Endianness,
+) -> Result<ZeckFile, ZeckFormatError> {
+    let original_size = u64::try_from(data.len())
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
+    let (flags, compressed_data) = match endianness {
+        Endianness::Big => (ZECK_FLAG_BIG_ENDIAN, lucas_compress_be(data)),
+        Endianness::Little => (0, lucas_compress_le(data)),
+    };
+    let mut zeck_file = ZeckFile::new_with_flags(original_size, compressed_data, flags);
+    zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_LUCAS);
+    Ok(zeck_file)
+}
+
+/// Replaces every byte after the first with its wrapping difference from the previous byte.
+pub(crate) fn delta_encode(data: &[u8]) -> Vec<u8> {
+    let mut previous = 0u8;
+    data.iter()
+        .map(|&byte| {
+            let delta = byte.wrapping_sub(previous);
+            previous = byte;
+            delta
+        })
+        .collect()
+}
+
+/// Every flags combination tried by [`compress_zeck_adaptive`], in order of preference when sizes tie.
+const ADAPTIVE_FLAG_COMBINATIONS: [u8; 8] = [
+    0,
+    ZECK_FLAG_BIG_ENDIAN,
+    ZECK_FLAG_INVERTED,
+    ZECK_FLAG_BIG_ENDIAN | ZECK_FLAG_INVERTED,
+    ZECK_FLAG_LAZY,
+    ZECK_FLAG_BIG_ENDIAN | ZECK_FLAG_LAZY,
+    ZECK_FLAG_LAZY | ZECK_FLAG_INVERTED,
+    ZECK_FLAG_BIG_ENDIAN | ZECK_FLAG_LAZY | ZECK_FLAG_INVERTED,
+];
+
+/// Compresses data using the Zeckendorf algorithm, trying every supported representation and keeping the smallest,
+/// and stores the result in a [`ZeckFile`] struct.
+///
+/// This function tries every combination of big or little endian interpretation, greedy (Zeckendorf) or lazy
+/// Fibonacci representation, and normal or inverted byte polarity, and records the winning combination in the
+/// header flags so [`crate::zeck_file_format::decompress::decompress_zeck_file`] can invert it. It is roughly
+/// eight times slower than [`compress_zeck_be`] or [`compress_zeck_le`], so prefer it when size matters more than speed.
+///
+/// Unlike [`compress_zeck_best`], this function always returns a [`ZeckFile`], even if the smallest result is
+/// larger than the original data.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::compress::compress_zeck_adaptive;
+/// # use zeck::zeck_file_format::decompress::decompress_zeck_file;
+/// let data = vec![255, 255, 254]; // Compresses best with inverted polarity
+/// let zeck_file = compress_zeck_adaptive(&data).unwrap();
+/// assert!(zeck_file.is_inverted());
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+/// ```
+#[wasm_bindgen]
+pub fn compress_zeck_adaptive(data: &[u8]) -> Result<ZeckFile, ZeckFormatError> {
+    let original_size = u64::try_from(data.len())
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
+    let (flags, compressed_data) = ADAPTIVE_FLAG_COMBINATIONS
+        .iter()
+        .map(|&flags| (flags, compress_with_flags(data, flags)))
+        .min_by_key(|(_, compressed_data)| compressed_data.len())
+        .expect("There is always at least one flags combination to try");
+    Ok(ZeckFile::new_with_flags(
+        original_size,
+        compressed_data,
+        flags,
+    ))
+}
+
+/// Number of rotation offsets tried by [`compress_zeck_rotations`], starting from 0.
+pub const ZECK_ROTATION_CANDIDATES: usize = 8;
+
+/// Compresses data using the Zeckendorf algorithm after rotating it left by each of the first
+/// [`ZECK_ROTATION_CANDIDATES`] byte offsets, treating it as a ring, and keeps the offset and endianness with the
+/// smallest compressed data. Returns the winning offset and the [`ZeckFile`].
+///
+/// Zeckendorf compression is sensitive to where the integer starts: rotating a few leading bytes to the other end can
+/// change which bytes become the most significant ones. A nonzero offset is recorded in the header (see
+/// [`ZeckFile::maybe_rotation`]) and undone by [`crate::zeck_file_format::decompress::decompress_zeck_file`]. It is
+/// only kept if it is strictly smaller than offset 0, so files that gain nothing stay readable by older versions.
+///
+/// Like [`compress_zeck_adaptive`], this function always returns a [`ZeckFile`], even if the smallest result is
+/// larger than the original data.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::compress::compress_zeck_rotations;
+/// # use zeck::zeck_file_format::decompress::decompress_zeck_file;
+/// let data = vec![255, 0, 255]; // [0, 255, 255] is a smaller big endian integer
+/// let (rotation, zeck_file) = compress_zeck_rotations(&data).unwrap();
+/// assert_eq!(rotation, 1);
+/// assert_eq!(zeck_file.maybe_rotation, Some(1));
+/// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+/// ```
+pub fn compress_zeck_rotations(data: &[u8]) -> Result<(usize, ZeckFile), ZeckFormatError> {
+    let original_size = u64::try_from(data.len())
+        .map_err(|_| ZeckFormatError::DataSizeTooLarge { size: data.len() })?;
+    let rotation_count = data.len().clamp(1, ZECK_ROTATION_CANDIDATES);
+    let (rotation, endianness, _) = (0..rotation_count)
+        .flat_map(|rotation| {
+            let rotated = rotate_left(data, rotation);
+            [Endianness::Big, Endianness::Little].map(|endianness| {
+                let size = compressed_byte_length(&rotated, endianness);
+                (rotation, endianness, size)
+            })
+        })
+        .min_by_key(|&(_, _, size)| size)
+        .expect("There is always at least one rotation to try");
+
+    let flags = if endianness == Endianness::Big {
+        ZECK_FLAG_BIG_ENDIAN
+    } else {
+        0
+    };
+    let rotated = rotate_left(data, rotation);
+    let mut zeck_file =
+        ZeckFile::new_with_flags(original_size, compress_with_flags(&rotated, flags), flags);
+    if rotation != 0 {
+        zeck_file.maybe_rotation = Some(rotation as u64);
+        zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_ROTATION);
+    }
+    Ok((rotation, zeck_file))
+}
+
+/// Returns a copy of `data` rotated left by `rotation` bytes, treating it as a ring.
+fn rotate_left(data: &[u8], rotation: usize) -> Vec<u8> {
+    let mut rotated = data.to_vec();
+    rotated.rotate_left(rotation);
+    rotated
+}
+
+/// Compresses data using the representation and second stage described by the given header flags.
+pub(crate) fn compress_with_flags(data: &[u8], flags: u8) -> Vec<u8> {
+    let flags = ZeckFlags::from_byte(flags);
+    let packed_ezba = compress_first_stage_with_flags(data, flags);
+    if flags.is_entropy_coded {
+        entropy_encode_ezba(&unpack_bytes_to_ezba_bits(&packed_ezba))
+    } else {
+        packed_ezba
+    }
+}
+
+/// Compresses data to packed EZBA bits using the representation described by the given header flags.
+fn compress_first_stage_with_flags(data: &[u8], flags: ZeckFlags) -> Vec<u8> {
+    let inverted_data: Vec<u8>;
+    let data = if flags.is_inverted {
+        inverted_data = data.iter().map(|byte| !byte).collect();
+        &inverted_data
+    } else {
+        data
+    };
+    let is_big_endian = flags.is_big_endian();
+
+    if !flags.is_lazy {
+        return if is_big_endian {
+            padless_zeckendorf_compress_be_dangerous(data)
+        } else {
+            padless_zeckendorf_compress_le_dangerous(data)
+        };
+    }
+
+    let data_as_biguint = if is_big_endian {
+        BigUint::from_bytes_be(data)
+    } else {
+        BigUint::from_bytes_le(data)
+    };
+    pack_ezba_bits_to_bytes(&lazy_ezba_from_biguint(&data_as_biguint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::generate_random_data;
+    use crate::zeck_file_format::decompress::decompress_zeck_file;
+    use crate::zeck_file_format::{ZECK_FLAG_ENTROPY_CODED, ZECK_FORMAT_VERSION};
+
+    #[test]
+    fn compression_writes_valid_files_of_the_current_version() {
+        for data in [vec![], vec![0, 1, 2, 0], generate_random_data(64, 1918)] {
+            // Arrange
+            let best = match compress_zeck_best(&data).unwrap() {
+                BestCompressionResult::BigEndianBest { zeck_file, .. }
+                | BestCompressionResult::LittleEndianBest { zeck_file, .. } => Some(zeck_file),
+                BestCompressionResult::Neither { .. } => None,
+            };
+            let mut compressed_files = vec![
+                ("be", compress_zeck_be(&data).unwrap()),
+                ("le", compress_zeck_le(&data).unwrap()),
+                (
+                    "be from iter",
+                    zeckendorf_compress_be_from_iter(data.iter().copied()).unwrap(),
+                ),
+                ("adaptive", compress_zeck_adaptive(&data).unwrap()),
+            ];
+            if data.len().is_multiple_of(8) {
+                let values: Vec<u64> = data
+                    .chunks_exact(8)
+                    .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+                    .collect();
+                compressed_files.push((
+                    "u64 slice",
+                    zeckendorf_compress_u64_slice(&values, Endianness::Little).unwrap(),
+                ));
+            }
+            for endianness in [Endianness::Big, Endianness::Little] {
+                compressed_files.extend([
+                    (
+                        "entropy coded",
+                        compress_zeck_entropy_coded(&data, endianness).unwrap(),
+                    ),
+                    ("delta", compress_zeck_delta(&data, endianness).unwrap()),
+                    ("sparse", compress_zeck_sparse(&data, endianness).unwrap()),
+                    ("lucas", compress_zeck_lucas(&data, endianness).unwrap()),
+                ]);
+            }
+            compressed_files.extend(best.map(|zeck_file| ("best", zeck_file)));
+
+            for (name, zeck_file) in compressed_files {
+                // Act
+                let bytes = zeck_file.to_bytes();
+
+                // Assert
+                let context = format!("{name}, data length: {}", data.len());
+                assert_eq!(zeck_file.version, ZECK_FORMAT_VERSION, "{context}");
+                assert_eq!(bytes[0], ZECK_FORMAT_VERSION, "{context}");
+                assert_eq!(zeck_file.validate(), Ok(()), "{context}");
+            }
+            assert_eq!(
+                compress_to_zeck_bytes(&data).unwrap()[0],
+                ZECK_FORMAT_VERSION
+            );
+        }
+    }
+
+    #[test]
+    fn compressing_from_an_iterator_matches_the_slice_version() {
+        // Arrange
+        let data: Vec<u8> = (0..100).map(|i| i as u8).collect();
+
+        // Act
+        let be_file = zeckendorf_compress_be_from_iter((0..100).map(|i| i as u8)).unwrap();
+        let le_file = zeckendorf_compress_le_from_iter((0..100).map(|i| i as u8)).unwrap();
+
+        // Assert
+        assert_eq!(be_file, compress_zeck_be(&data).unwrap());
+        assert_eq!(le_file, compress_zeck_le(&data).unwrap());
+        assert_eq!(decompress_zeck_file(&be_file).unwrap(), data);
+    }
+
+    #[test]
+    fn compression_preview_matches_compress_zeck_best() {
+        for seed in 0..100u64 {
+            // Arrange
+            let data = crate::test_data::generate_random_data((seed % 12) as usize, seed);
+            let best = compress_zeck_best(&data).unwrap();
+
+            // Act
+            let preview = exact_compression_preview(&data);
+
+            // Assert
+            assert_eq!(
+                preview.be_size,
+                compress_zeck_be(&data).unwrap().compressed_data.len()
+            );
+            assert_eq!(
+                preview.le_size,
+                compress_zeck_le(&data).unwrap().compressed_data.len()
+            );
+            match best {
+                BestCompressionResult::BigEndianBest { .. } => {
+                    assert!(preview.is_favorable);
+                    assert_eq!(preview.best_endianness, Endianness::Big);
+                }
+                BestCompressionResult::LittleEndianBest { .. } => {
+                    assert!(preview.is_favorable);
+                    assert_eq!(preview.best_endianness, Endianness::Little);
+                }
+                BestCompressionResult::Neither { .. } => assert!(!preview.is_favorable),
+            }
+        }
+    }
+
+    fn roundtrip_with_flags(data: &[u8], flags: u8) {
+        // Arrange
+        let zeck_file =
+            ZeckFile::new_with_flags(data.len() as u64, compress_with_flags(data, flags), flags);
+
+        // Act
+        let decompressed = decompress_zeck_file(&zeck_file).expect("decompress must succeed");
+
+        // Assert
+        assert_eq!(decompressed, data, "flags: {flags:#05b}");
+    }
+
+    #[test]
+    fn every_flags_combination_round_trips() {
+        let inputs: [&[u8]; 5] = [
+            &[1],
+            &[0, 0, 5],
+            &[5, 0, 0],
+            &[255, 255, 254],
+            &[0x42, 0x00, 0xFF, 0x13, 0x37, 0x00],
+        ];
+
+        for data in inputs {
+            for flags in ADAPTIVE_FLAG_COMBINATIONS {
+                roundtrip_with_flags(data, flags);
+            }
+        }
+    }
+
+    #[test]
+    fn entropy_coded_flags_combinations_round_trip() {
+        let inputs: [&[u8]; 4] = [
+            &[0],
+            &[1],
+            &[0, 0, 5],
+            &[0x42, 0x00, 0xFF, 0x13, 0x37, 0x00],
+        ];
+
+        for data in inputs {
+            for flags in ADAPTIVE_FLAG_COMBINATIONS {
+                roundtrip_with_flags(data, flags | ZECK_FLAG_ENTROPY_CODED);
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_round_trips_small_integers() {
+        for value in 0u16..=1024 {
+            roundtrip_with_flags(&value.to_be_bytes(), ZECK_FLAG_BIG_ENDIAN | ZECK_FLAG_LAZY);
+        }
+    }
+
+    #[test]
+    fn adaptive_is_never_larger_than_plain_best() {
+        // Arrange
+        let data: Vec<u8> = (0..64u32).map(|i| (i * 37 % 256) as u8).collect();
+        let be_size = compress_zeck_be(&data).unwrap().compressed_data.len();
+        let le_size = compress_zeck_le(&data).unwrap().compressed_data.len();
+
+        // Act
+        let zeck_file = compress_zeck_adaptive(&data).unwrap();
+
+        // Assert
+        assert!(zeck_file.compressed_data.len() <= be_size.min(le_size));
+    }
+
+    #[test]
+    fn adaptive_round_trips() {
+        // Arrange
+        let data: Vec<u8> = (0..64u32).map(|i| (i * 37 % 256) as u8).collect();
+
+        // Act
+        let zeck_file = compress_zeck_adaptive(&data).unwrap();
+        let decompressed = decompress_zeck_file(&zeck_file).unwrap();
+
+        // Assert
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn compress_zeck_rotations_round_trips_and_never_loses_to_offset_zero() {
+        for size in [0, 1, 2, 5, 9, 16] {
+            // Arrange
+            let data = generate_random_data(size, size as u64);
+            let unrotated = exact_compression_preview(&data);
+
+            // Act
+            let (rotation, zeck_file) = compress_zeck_rotations(&data).unwrap();
+            let bytes = zeck_file.to_bytes();
+            let deserialized = ZeckFile::try_from(bytes.as_slice()).unwrap();
+
+            // Assert
+            assert!(rotation < size.clamp(1, ZECK_ROTATION_CANDIDATES));
+            assert!(
+                zeck_file.compressed_data.len() <= unrotated.be_size.min(unrotated.le_size),
+                "size {size}"
+            );
+            assert_eq!(deserialized, zeck_file);
+            assert_eq!(decompress_zeck_file(&deserialized).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn empty_input_compresses_to_zero_length_file() {
+        // Arrange
+        let data: &[u8] = &[];
+
+        // Act
+        let zeck_files = [
+            compress_zeck_be(data).unwrap(),
+            compress_zeck_le(data).unwrap(),
+            compress_zeck_adaptive(data).unwrap(),
+            compress_zeck_rotations(data).unwrap().1,
+        ];
+        let best_result = compress_zeck_best(data);
+
+        // Assert
+        for zeck_file in zeck_files {
+            let deserialized = ZeckFile::try_from(zeck_file.to_bytes().as_slice()).unwrap();
+            assert_eq!(deserialized.original_size, 0);
+            assert_eq!(decompress_zeck_file(&deserialized), Ok(Vec::new()));
+        }
+        assert_eq!(
+            best_result,
+            Ok(BestCompressionResult::Neither {
+                be_size: 1,
+                le_size: 1
+            })
+        );
+    }
+
+    #[test]
+    fn sparse_index_list_wins_and_round_trips_for_very_sparse_values() {
+        for endianness in [Endianness::Big, Endianness::Little] {
+            // Arrange
+            let value = &*crate::fast_doubling_fibonacci_biguint(100_000)
+                + &*crate::fast_doubling_fibonacci_biguint(10);
+            let data = match endianness {
+                Endianness::Big => [vec![0, 0], value.to_bytes_be()].concat(),
+                Endianness::Little => [value.to_bytes_le(), vec![0, 0]].concat(),
+            };
+
+            // Act
+            let zeck_file = compress_zeck_sparse(&data, endianness).unwrap();
+            let deserialized = ZeckFile::try_from(zeck_file.to_bytes().as_slice()).unwrap();
+
+            // Assert
+            assert!(deserialized.is_sparse());
+            assert_eq!(deserialized, zeck_file);
+            assert_eq!(zeck_file.compressed_data.len(), 6);
+            let ezba_file = compress_zeck(&data, endianness.into()).unwrap();
+            assert!(ezba_file.compressed_data.len() > 8_000);
+            assert_eq!(decompress_zeck_file(&deserialized), Ok(data));
+        }
+    }
+
+    #[test]
+    fn sparse_compression_keeps_ezba_bits_when_they_are_smaller() {
+        for data in [&[1][..], &[0, 7, 0], b"Dense text"] {
+            // Arrange
+            let expected = compress_zeck_le(data).unwrap();
+
+            // Act
+            let zeck_file = compress_zeck_sparse(data, Endianness::Little).unwrap();
+
+            // Assert
+            assert_eq!(zeck_file, expected);
+        }
+    }
+
+    #[test]
+    fn lucas_codec_round_trips_through_bytes() {
+        let inputs: [&[u8]; 6] = [
+            &[],
+            &[0, 0, 0],
+            &[1],
+            &[0, 7, 0],
+            b"Lucas text",
+            &[0xFF; 100],
+        ];
+        for data in inputs {
+            for endianness in [Endianness::Big, Endianness::Little] {
+                // Arrange
+                let zeck_file = compress_zeck_lucas(data, endianness).unwrap();
+
+                // Act
+                let deserialized = ZeckFile::try_from(zeck_file.to_bytes().as_slice()).unwrap();
+                let decompressed = decompress_zeck_file(&deserialized);
+
+                // Assert
+                assert!(deserialized.is_lucas());
+                assert_eq!(deserialized, zeck_file);
+                assert_eq!(decompressed.as_deref(), Ok(data), "data: {data:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn delta_encoding_round_trips() {
+        let inputs: [&[u8]; 5] = [
+            &[],
+            &[0],
+            &[255, 0, 255, 0],
+            &[1, 2, 3, 4, 5],
+            b"Delta text",
+        ];
+        for data in inputs {
+            for endianness in [Endianness::Big, Endianness::Little] {
+                // Arrange
+                let zeck_file = compress_zeck_delta(data, endianness).unwrap();
+
+                // Act
+                let deserialized = ZeckFile::try_from(zeck_file.to_bytes().as_slice()).unwrap();
+                let decompressed = decompress_zeck_file(&deserialized);
+
+                // Assert
+                assert_eq!(deserialized, zeck_file);
+                assert!(deserialized.is_delta_encoded());
+                assert_eq!(decompressed, Ok(data.to_vec()), "{data:?} {endianness:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn compress_to_zeck_bytes_stores_incompressible_data() {
+        // Arrange
+        let data = [255];
+
+        // Act
+        let zeck_bytes = compress_to_zeck_bytes(&data).unwrap();
+
+        // Assert
+        assert_eq!(
+            crate::zeck_file_format::decompress::decompress_from_zeck_bytes(&zeck_bytes),
+            Ok(data.to_vec())
+        );
+    }
+
+    #[test]
+    fn rotation_out_of_range_is_rejected() {
+        // Arrange
+        let (_, mut zeck_file) = compress_zeck_rotations(&[255, 0, 255]).unwrap();
+        zeck_file.maybe_rotation = Some(3);
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::RotationOutOfRange {
+                rotation: 3,
+                original_size: 3
+            })
+        );
+    }
+
+    #[test]
+    fn compress_zeck_big_uses_big_endian() {
+        // Arrange
+        let data = [1, 0];
+
+        // Act
+        let zeck_file = compress_zeck(&data, EndianChoice::Big).unwrap();
+
+        // Assert
+        assert_eq!(zeck_file, compress_zeck_be(&data).unwrap());
+    }
+
+    #[test]
+    fn compress_zeck_little_uses_little_endian() {
+        // Arrange
+        let data = [1, 0];
+
+        // Act
+        let zeck_file = compress_zeck(&data, EndianChoice::Little).unwrap();
+
+        // Assert
+        assert_eq!(zeck_file, compress_zeck_le(&data).unwrap());
+    }
+
+    #[test]
+    fn compress_zeck_best_picks_smaller_endianness() {
+        // Arrange
+        let data = [0, 1];
+
+        // Act
+        let zeck_file = compress_zeck(&data, EndianChoice::Best).unwrap();
+
+        // Assert
+        assert_eq!(zeck_file, compress_zeck_be(&data).unwrap());
+    }
+
+    #[test]
+    fn compress_zeck_best_with_threshold_skips_second_attempt_above_threshold() {
+        const THRESHOLD: usize = 64;
+        for size in [THRESHOLD - 1, THRESHOLD, THRESHOLD + 1, THRESHOLD + 2] {
+            // Arrange
+            // Zero bytes at the end make the little endian integer clearly smaller
+            let mut data = generate_random_data(size, size as u64);
+            data[size - 8..].fill(0);
+            let exhaustive = compress_zeck_best(&data).unwrap();
+
+            // Act
+            let result = compress_zeck_best_with_threshold(&data, THRESHOLD).unwrap();
+
+            // Assert
+            assert_eq!(result.compressed_len(), exhaustive.compressed_len());
+            match result {
+                ThresholdCompressionResult::Compared(compared) => {
+                    assert!(size <= THRESHOLD);
+                    assert_eq!(compared, exhaustive);
+                }
+                ThresholdCompressionResult::Predicted {
+                    endianness,
+                    maybe_zeck_file: Some(zeck_file),
+                    size: compressed_size,
+                } => {
+                    assert!(size > THRESHOLD);
+                    assert_eq!(endianness, Endianness::Little);
+                    assert_eq!(compressed_size, zeck_file.compressed_data.len());
+                    assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), data);
+                }
+                other => panic!("expected little endian to compress, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn compress_zeck_best_with_threshold_reports_an_incompressible_prediction() {
+        // Arrange
+        let data = [0xFF; 65];
+
+        // Act
+        let result = compress_zeck_best_with_threshold(&data, 64).unwrap();
+
+        // Assert
+        assert_eq!(
+            result,
+            ThresholdCompressionResult::Predicted {
+                endianness: Endianness::Little,
+                maybe_zeck_file: None,
+                size: compress_zeck_le(&data).unwrap().compressed_data.len(),
+            }
+        );
+    }
+
+    #[test]
+    fn compress_zeck_best_errors_when_neither_compresses() {
+        // Arrange
+        let data = [255];
+
+        // Act
+        let result = compress_zeck(&data, EndianChoice::Best);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::CompressionFailed {
+                original_size: 1,
+                be_size: 2,
+                le_size: 2,
+            })
+        );
+    }
+}
@@ -1,19 +1,30 @@
 //! Decompression functions for the .zeck file format
 
+use crate::lucas::lucas_bits_to_biguint;
+use crate::zeck_file_format::checksum::{CRC32_INITIAL_STATE, crc32, crc32_update};
 use crate::zeck_file_format::error::ZeckFormatError;
 use crate::zeck_file_format::{
-    ZECK_FLAG_BIG_ENDIAN, ZECK_FLAG_RESERVED_MASK, ZECK_FORMAT_VERSION, file::ZeckFile,
+    ZECK_ARCHIVE_FORMAT_VERSION, ZECK_EXTENDED_FLAG_DELTA, ZECK_EXTENDED_FLAG_LUCAS,
+    ZECK_EXTENDED_FLAG_SPARSE, ZECK_EXTENDED_FLAG_UNCOMPRESSED, ZECK_FLAG_RESERVED_MASK,
+    ZECK_FORMAT_VERSION, ZECK_HEADER_SIZE, ZECK_LEGACY_FORMAT_VERSION, ZECK_RECORDS_FORMAT_VERSION,
+    archive::ZeckArchive, file::ZeckFile, flags::ZeckFlags, sparse::decode_sparse_index_list,
+    view::ZeckFileView,
 };
 use crate::{
-    padless_zeckendorf_decompress_be_dangerous, padless_zeckendorf_decompress_le_dangerous,
+    EzbaBits, SKIP_BIT, entropy_decode_ezba, ezba_bits_to_biguint, ezl_to_zl, lazy_ezba_to_ezla,
+    pack_ezba_bits_to_bytes, unpack_bytes_to_ezba_bits, zl_to_biguint,
 };
+use num_bigint::BigUint;
+use num_traits::Zero;
+use std::io::Read;
 use wasm_bindgen::prelude::*;
 
 /// Decompresses data from a [`ZeckFile`] struct.
 ///
 /// This function takes a [`ZeckFile`] directly and uses its header information to decompress
 /// the data. This is a convenience function that avoids the need to serialize and parse the
-/// file format when you already have a [`ZeckFile`] struct.
+/// file format when you already have a [`ZeckFile`] struct. The file is checked with [`ZeckFile::validate`] before
+/// anything is decompressed.
 ///
 /// # ⚠️ Warning
 ///
@@ -38,42 +49,600 @@ use wasm_bindgen::prelude::*;
 /// ```
 #[wasm_bindgen]
 pub fn decompress_zeck_file(zeck_file: &ZeckFile) -> Result<Vec<u8>, ZeckFormatError> {
-    // Check reserved flags
-    if zeck_file.flags & ZECK_FLAG_RESERVED_MASK != 0 {
-        return Err(ZeckFormatError::ReservedFlagsSet {
-            flags: zeck_file.flags,
+    zeck_file.validate()?;
+    decompress_validated_view(&zeck_file.as_view())
+}
+
+/// Decompresses data from a [`ZeckFileView`], reading the compressed data straight from the bytes the view borrows.
+///
+/// This behaves exactly like [`decompress_zeck_file`], but avoids copying the compressed data out of its buffer first,
+/// for example when the .zeck file is memory-mapped.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::{compress::compress_zeck_le, decompress::decompress_view, ZeckFileView};
+/// let original = vec![0, 1];
+/// let bytes = compress_zeck_le(&original).unwrap().to_bytes();
+/// let view = ZeckFileView::parse(&bytes).unwrap();
+/// assert_eq!(decompress_view(&view).unwrap(), original);
+/// ```
+pub fn decompress_view(view: &ZeckFileView) -> Result<Vec<u8>, ZeckFormatError> {
+    view.validate()?;
+    decompress_validated_view(view)
+}
+
+/// Implements [`decompress_view`] for a view that already passed [`ZeckFileView::validate`].
+fn decompress_validated_view(view: &ZeckFileView) -> Result<Vec<u8>, ZeckFormatError> {
+    // Route to version-specific decompression
+    match view.version {
+        // A version 1 file has no optional header fields, so it decompresses like a version 2 file without them
+        ZECK_LEGACY_FORMAT_VERSION | ZECK_FORMAT_VERSION => {
+            let mut decompressed = decompress_zeck_payload(
+                view.compressed_data,
+                view.original_size,
+                view.flags,
+                view.extended_flags,
+                view.maybe_padding_bits,
+            )?;
+            if view.extended_flags & ZECK_EXTENDED_FLAG_DELTA != 0 {
+                delta_decode(&mut decompressed);
+            }
+            if let Some(rotation) = view.maybe_rotation {
+                unrotate(&mut decompressed, rotation)?;
+            }
+            if let Some(expected) = view.maybe_checksum {
+                let actual = crc32(&decompressed);
+                if actual != expected {
+                    return Err(ZeckFormatError::ChecksumMismatch { expected, actual });
+                }
+            }
+            Ok(decompressed)
+        }
+        ZECK_ARCHIVE_FORMAT_VERSION => {
+            ZeckArchive::from_parts(view.original_size, view.flags, view.compressed_data)?
+                .decompress_all()
+        }
+        // Concatenating records would lose their boundaries, so they are only read by decompress_records
+        ZECK_RECORDS_FORMAT_VERSION => Err(ZeckFormatError::UnexpectedVersion {
+            found_version: view.version,
+            expected_version: ZECK_ARCHIVE_FORMAT_VERSION,
+        }),
+        _ => Err(ZeckFormatError::UnsupportedVersion {
+            found_version: view.version,
+            supported_version: ZECK_RECORDS_FORMAT_VERSION,
+        }),
+    }
+}
+
+/// Parses serialized .zeck file bytes and decompresses them in one call, the reverse of
+/// [`crate::zeck_file_format::compress::compress_to_zeck_bytes`].
+///
+/// The compressed data is read straight from `zeck_file_data` (see [`decompress_view`]), so it is never copied.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{compress_to_zeck_bytes, decompress_from_zeck_bytes};
+/// let zeck_bytes = compress_to_zeck_bytes(&[0, 0, 42]).unwrap();
+/// assert_eq!(decompress_from_zeck_bytes(&zeck_bytes).unwrap(), vec![0, 0, 42]);
+/// ```
+#[wasm_bindgen]
+pub fn decompress_from_zeck_bytes(zeck_file_data: &[u8]) -> Result<Vec<u8>, ZeckFormatError> {
+    decompress_view(&ZeckFileView::parse(zeck_file_data)?)
+}
+
+/// Returns the original size recorded in the header of serialized .zeck file bytes, without decompressing or copying
+/// anything.
+///
+/// Only the fixed-size header is read, so this is cheap even for a huge file, for example to preallocate the output
+/// buffer or to report the size. The optional header fields and compressed data are not validated.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::{compress_to_zeck_bytes, peek_original_size};
+/// let zeck_bytes = compress_to_zeck_bytes(&[0, 0, 42]).unwrap();
+/// assert_eq!(peek_original_size(&zeck_bytes).unwrap(), 3);
+/// ```
+#[wasm_bindgen]
+pub fn peek_original_size(zeck_file_data: &[u8]) -> Result<u64, ZeckFormatError> {
+    let Some(header) = zeck_file_data.get(..ZECK_HEADER_SIZE) else {
+        return Err(ZeckFormatError::HeaderTooShort {
+            actual_length: zeck_file_data.len(),
+            required_length: ZECK_HEADER_SIZE,
+        });
+    };
+    let version = header[0];
+    if !matches!(
+        version,
+        ZECK_LEGACY_FORMAT_VERSION
+            | ZECK_FORMAT_VERSION
+            | ZECK_ARCHIVE_FORMAT_VERSION
+            | ZECK_RECORDS_FORMAT_VERSION
+    ) {
+        return Err(ZeckFormatError::UnsupportedVersion {
+            found_version: version,
+            supported_version: ZECK_RECORDS_FORMAT_VERSION,
         });
     }
+    Ok(u64::from_le_bytes(
+        header[1..9]
+            .try_into()
+            .expect("Header slice is exactly 8 bytes"),
+    ))
+}
 
-    // Route to version-specific decompression
-    match zeck_file.version {
-        1 => decompress_zeck_v1(
-            &zeck_file.compressed_data,
-            zeck_file.original_size,
-            zeck_file.flags,
-        ),
+/// The number of bytes [`decompress_zeck_file_chunked`] passes to its writer at a time.
+pub const DECOMPRESS_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Decompresses data from a [`ZeckFile`] struct, passing the output to `chunk_writer` in chunks of at most
+/// [`DECOMPRESS_CHUNK_SIZE`] bytes instead of returning it as one buffer.
+///
+/// A single-file .zeck file decodes to a single integer, which is still built in memory, but its bytes are written straight
+/// from the integer's digits, and the zero padding of the original size is generated as it is written, so no second
+/// copy of the output is ever held. Inversion, delta encoding, and the checksum are applied to each chunk as it goes.
+/// Archives are decompressed one block at a time. A rotated file must be reassembled as a whole before it can be
+/// unrotated, so it falls back to [`decompress_zeck_file`] and only the writing is chunked.
+///
+/// The chunks concatenate to exactly the output of [`decompress_zeck_file`]. Errors found before any output, such as
+/// malformed compressed data, are returned without calling `chunk_writer`. A checksum mismatch can only be detected
+/// after the last chunk, so the chunks already written must then be discarded.
+///
+/// # ⚠️ Warning
+///
+/// **Compressing or decompressing data larger than 10KB (10,000 bytes) is unstable due to time and memory pressure.**
+/// The library may experience performance issues, excessive memory usage, or failures when processing data exceeding this size.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::{compress::compress_zeck_be, decompress::decompress_zeck_file_chunked};
+/// let original = vec![0, 0, 1, 2, 3];
+/// let zeck_file = compress_zeck_be(&original).unwrap();
+///
+/// let mut output = Vec::new();
+/// decompress_zeck_file_chunked(&zeck_file, |chunk| output.extend_from_slice(chunk)).unwrap();
+/// assert_eq!(output, original);
+/// ```
+pub fn decompress_zeck_file_chunked(
+    zeck_file: &ZeckFile,
+    chunk_writer: impl FnMut(&[u8]),
+) -> Result<(), ZeckFormatError> {
+    decompress_view_chunked(&zeck_file.as_view(), DECOMPRESS_CHUNK_SIZE, chunk_writer)
+}
+
+/// Implements [`decompress_zeck_file_chunked`] for any chunk size.
+fn decompress_view_chunked(
+    view: &ZeckFileView,
+    chunk_size: usize,
+    chunk_writer: impl FnMut(&[u8]),
+) -> Result<(), ZeckFormatError> {
+    view.validate()?;
+
+    match view.version {
+        ZECK_LEGACY_FORMAT_VERSION | ZECK_FORMAT_VERSION if view.maybe_rotation.is_none() => {
+            let value = decompress_zeck_payload_to_biguint(
+                view.compressed_data,
+                view.original_size,
+                view.flags,
+                view.extended_flags,
+                view.maybe_padding_bits,
+            )?;
+            let value_len = value.bits().div_ceil(8) as usize;
+            let original_size_usize = view.original_size as usize;
+            if value_len > original_size_usize {
+                return Err(ZeckFormatError::DecompressedTooLarge {
+                    expected_size: original_size_usize,
+                    actual_size: value_len,
+                });
+            }
+
+            let mut output = ChunkedOutput::new(chunk_size, chunk_writer);
+            let flags = ZeckFlags::from_byte(view.flags);
+            output.is_inverted = flags.is_inverted;
+            output.is_delta_encoded = view.extended_flags & ZECK_EXTENDED_FLAG_DELTA != 0;
+
+            // The digits are little endian u64s, and the most significant one is trimmed to the value's byte length
+            let padding_len = original_size_usize - value_len;
+            let last_digit_index = value_len.div_ceil(8).saturating_sub(1);
+            let top_digit_len = value_len - 8 * last_digit_index;
+            if flags.is_big_endian() {
+                output.write_zeros(padding_len);
+                for (index, digit) in value.iter_u64_digits().enumerate().rev() {
+                    let bytes = digit.to_be_bytes();
+                    let skip = if index == last_digit_index {
+                        8 - top_digit_len
+                    } else {
+                        0
+                    };
+                    output.write(&bytes[skip..]);
+                }
+                drop(value);
+            } else {
+                for (index, digit) in value.iter_u64_digits().enumerate() {
+                    let bytes = digit.to_le_bytes();
+                    let len = if index == last_digit_index {
+                        top_digit_len
+                    } else {
+                        8
+                    };
+                    output.write(&bytes[..len]);
+                }
+                drop(value);
+                output.write_zeros(padding_len);
+            }
+
+            let actual = output.finish();
+            match view.maybe_checksum {
+                Some(expected) if expected != actual => {
+                    Err(ZeckFormatError::ChecksumMismatch { expected, actual })
+                }
+                _ => Ok(()),
+            }
+        }
+        ZECK_ARCHIVE_FORMAT_VERSION => {
+            let archive =
+                ZeckArchive::from_parts(view.original_size, view.flags, view.compressed_data)?;
+            let mut output = ChunkedOutput::new(chunk_size, chunk_writer);
+            for index in 0..archive.block_count() {
+                output.write(&archive.block(index)?);
+            }
+            output.finish();
+            Ok(())
+        }
+        _ => {
+            let decompressed = decompress_view(view)?;
+            let mut output = ChunkedOutput::new(chunk_size, chunk_writer);
+            output.write(&decompressed);
+            output.finish();
+            Ok(())
+        }
+    }
+}
+
+/// Buffers decompressed bytes into chunks of a fixed size for a chunk writer, undoing inversion and delta encoding
+/// and updating a running CRC-32 on the way.
+struct ChunkedOutput<W: FnMut(&[u8])> {
+    chunk_size: usize,
+    chunk: Vec<u8>,
+    chunk_writer: W,
+    is_inverted: bool,
+    is_delta_encoded: bool,
+    previous: u8,
+    crc32_state: u32,
+}
+
+impl<W: FnMut(&[u8])> ChunkedOutput<W> {
+    fn new(chunk_size: usize, chunk_writer: W) -> Self {
+        Self {
+            chunk_size,
+            chunk: Vec::with_capacity(chunk_size),
+            chunk_writer,
+            is_inverted: false,
+            is_delta_encoded: false,
+            previous: 0,
+            crc32_state: CRC32_INITIAL_STATE,
+        }
+    }
+
+    /// Appends bytes, passing every chunk that fills up to the chunk writer.
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let mut byte = if self.is_inverted { !byte } else { byte };
+            if self.is_delta_encoded {
+                self.previous = self.previous.wrapping_add(byte);
+                byte = self.previous;
+            }
+            self.chunk.push(byte);
+            if self.chunk.len() == self.chunk_size {
+                self.flush();
+            }
+        }
+    }
+
+    /// Appends `len` zero bytes of padding, without materializing them all at once.
+    fn write_zeros(&mut self, len: usize) {
+        let zeros = [0u8; 256];
+        let mut remaining = len;
+        while remaining > 0 {
+            let count = remaining.min(zeros.len());
+            self.write(&zeros[..count]);
+            remaining -= count;
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.chunk.is_empty() {
+            self.crc32_state = crc32_update(self.crc32_state, &self.chunk);
+            (self.chunk_writer)(&self.chunk);
+            self.chunk.clear();
+        }
+    }
+
+    /// Writes the last partial chunk and returns the CRC-32 of everything written.
+    fn finish(mut self) -> u32 {
+        self.flush();
+        !self.crc32_state
+    }
+}
+
+/// Undoes the delta encoding pre-transform by adding every byte to the running total of the bytes before it.
+fn delta_decode(data: &mut [u8]) {
+    let mut previous = 0u8;
+    for byte in data.iter_mut() {
+        previous = previous.wrapping_add(*byte);
+        *byte = previous;
+    }
+}
+
+/// Rotates data that was rotated left by `rotation` bytes before compression back to its original order.
+fn unrotate(data: &mut [u8], rotation: u64) -> Result<(), ZeckFormatError> {
+    let original_size = data.len() as u64;
+    if rotation != 0 && rotation >= original_size {
+        return Err(ZeckFormatError::RotationOutOfRange {
+            rotation,
+            original_size,
+        });
+    }
+    data.rotate_right(rotation as usize);
+    Ok(())
+}
+
+/// Decompresses a [`ZeckFile`] created by [`crate::zeck_file_format::compress::zeckendorf_compress_u64_slice`] back
+/// into the original [`u64`] values.
+///
+/// The values are read 8 bytes at a time in the file's endianness. The file must record an element width of 8 (see
+/// [`ZECK_EXTENDED_FLAG_ELEMENT_WIDTH`](super::ZECK_EXTENDED_FLAG_ELEMENT_WIDTH)), so a file compressed from bytes that
+/// happen to be a multiple of 8 long fails with [`ZeckFormatError::ElementWidthMismatch`] instead of being
+/// misread. Validation already checks that the original size is a whole number of elements.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::{compress::{compress_zeck_le, zeckendorf_compress_u64_slice}, decompress::decompress_to_u64_vec};
+/// let zeck_file = zeckendorf_compress_u64_slice(&[7, 0, 42], Endianness::Little).unwrap();
+/// assert_eq!(decompress_to_u64_vec(&zeck_file).unwrap(), vec![7, 0, 42]);
+///
+/// assert!(decompress_to_u64_vec(&compress_zeck_le(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap()).is_err());
+/// ```
+#[wasm_bindgen]
+pub fn decompress_to_u64_vec(zeck_file: &ZeckFile) -> Result<Vec<u64>, ZeckFormatError> {
+    if zeck_file.maybe_element_width != Some(U64_SIZE as u8) {
+        return Err(ZeckFormatError::ElementWidthMismatch {
+            maybe_element_width: zeck_file.maybe_element_width,
+            element_size: U64_SIZE,
+        });
+    }
+
+    let decompressed = decompress_zeck_file(zeck_file)?;
+    let is_big_endian = zeck_file.is_big_endian();
+    Ok(decompressed
+        .chunks_exact(U64_SIZE)
+        .map(|chunk| {
+            let bytes = chunk.try_into().expect("Chunks are exactly 8 bytes");
+            if is_big_endian {
+                u64::from_be_bytes(bytes)
+            } else {
+                u64::from_le_bytes(bytes)
+            }
+        })
+        .collect())
+}
+
+/// Size of a [`u64`] value in bytes.
+const U64_SIZE: usize = std::mem::size_of::<u64>();
+
+/// Decompresses a stream of concatenated serialized .zeck files, yielding each decompressed payload in order.
+///
+/// Files are read one after another with [`ZeckFile::read_from`] until the reader reaches end of file. Because version 1
+/// files store no payload length, such a file consumes the rest of the stream; version 2 files and multi-block
+/// archives (see [`crate::zeck_file_format::archive`]) can be followed by more files. Iteration stops after the first
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::{archive::compress_zeck_archive, decompress::decompress_zeck_stream};
+/// let mut stream = compress_zeck_archive(b"hello ", 4, true).unwrap().to_bytes();
+/// stream.extend(compress_zeck_archive(b"world", 4, false).unwrap().to_bytes());
+///
+/// let payloads: Vec<Vec<u8>> = decompress_zeck_stream(&mut stream.as_slice())
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(payloads, vec![b"hello ".to_vec(), b"world".to_vec()]);
+/// ```
+pub fn decompress_zeck_stream<R: Read>(
+    reader: &mut R,
+) -> impl Iterator<Item = Result<Vec<u8>, ZeckFormatError>> + '_ {
+    let mut is_done = false;
+    std::iter::from_fn(move || {
+        if is_done {
+            return None;
+        }
+        let result = match ZeckFile::read_from(reader) {
+            Ok(Some(zeck_file)) => decompress_zeck_file(&zeck_file),
+            Ok(None) => return None,
+            Err(error) => Err(error),
+        };
+        is_done = result.is_err();
+        Some(result)
+    })
+}
+
+/// Checks that compressed data is the canonical encoding of exactly `compressed_data.len() * 8 - padding_bits` EZBA bits.
+///
+/// The padding bits must all be [`crate::SKIP_BIT`]s, and the last of the considered bits must be a [`crate::USE_BIT`],
+/// because both the greedy and lazy encodings end with one. The only exception is zero, whose greedy EZBA is a single
+/// [`crate::SKIP_BIT`] packed as `[0]` with 7 padding bits. This rejects data with extra bytes appended, which would
+/// otherwise decode to the same value.
+fn check_padding_bits(compressed_data: &[u8], padding_bits: u8) -> Result<(), ZeckFormatError> {
+    let non_canonical = ZeckFormatError::NonCanonicalPadding {
+        padding_bits,
+        compressed_length: compressed_data.len(),
+    };
+    let Some(&last_byte) = compressed_data.last() else {
+        return Err(non_canonical);
+    };
+    if padding_bits > 7 {
+        return Err(non_canonical);
+    }
+    if compressed_data == [0] && padding_bits == 7 {
+        return Ok(());
+    }
+
+    // The last considered bit must be the highest set bit of the last byte
+    let last_bit_index = 7 - padding_bits;
+    if last_byte >> last_bit_index != 1 {
+        return Err(non_canonical);
+    }
+    Ok(())
+}
+
+/// Checks that entropy decoded EZBA (or lazy EZBA) bits are whole packed bytes ending with their last
+/// [`crate::USE_BIT`] and its padding, as the compression functions write them. A whole byte of trailing
+/// [`SKIP_BIT`]s would decode to the same value, so a stream carrying one was not written by this library and is
+/// rejected as corrupt. The only exception is the greedy EZBA of zero, which packs to a single zero byte.
+///
+/// Adjacent [`crate::USE_BIT`]s need no check: every [`crate::USE_BIT`] implicitly skips the next Fibonacci index, so
+/// the bits can never encode two consecutive indices.
+fn check_canonical_ezba(ezba: &[u8], is_lazy: bool) -> Result<(), ZeckFormatError> {
+    let trailing_skip_bit_count = ezba
+        .iter()
+        .rev()
+        .take_while(|&&bit| bit == SKIP_BIT)
+        .count();
+    let is_greedy_zero = !is_lazy && ezba == [SKIP_BIT; 8];
+    let is_whole_bytes = !ezba.is_empty() && ezba.len().is_multiple_of(8);
+    if is_whole_bytes && (trailing_skip_bit_count < 8 || is_greedy_zero) {
+        return Ok(());
+    }
+    Err(ZeckFormatError::NonCanonical {
+        ezba_bit_length: ezba.len(),
+    })
+}
+
+/// Implements [`ZeckFileView::validate`].
+pub(crate) fn validate_view(view: &ZeckFileView) -> Result<(), ZeckFormatError> {
+    match view.version {
+        ZECK_LEGACY_FORMAT_VERSION => {
+            if view.flags & ZECK_FLAG_RESERVED_MASK != 0 {
+                return Err(ZeckFormatError::ReservedFlagsSet { flags: view.flags });
+            }
+            check_payload_length(view)
+        }
+        ZECK_FORMAT_VERSION => {
+            if let Some(element_width) = view.maybe_element_width
+                && (element_width == 0
+                    || !view.original_size.is_multiple_of(u64::from(element_width)))
+            {
+                return Err(ZeckFormatError::ElementSizeMismatch {
+                    original_size: view.original_size,
+                    element_size: usize::from(element_width),
+                });
+            }
+            check_codec_flags(
+                view.compressed_data,
+                view.original_size,
+                ZeckFlags::from_byte(view.flags),
+                view.extended_flags,
+                view.maybe_padding_bits,
+            )?;
+            if let Some(rotation) = view.maybe_rotation
+                && rotation != 0
+                && rotation >= view.original_size
+            {
+                return Err(ZeckFormatError::RotationOutOfRange {
+                    rotation,
+                    original_size: view.original_size,
+                });
+            }
+            // A corrupted payload is reported as such before its length and padding are checked
+            check_payload_crc(view)?;
+            check_payload_length(view)?;
+            if let Some(padding_bits) = view.maybe_padding_bits {
+                check_padding_bits(view.compressed_data, padding_bits)?;
+            }
+            Ok(())
+        }
+        ZECK_ARCHIVE_FORMAT_VERSION | ZECK_RECORDS_FORMAT_VERSION => {
+            ZeckArchive::from_parts(view.original_size, view.flags, view.compressed_data)?;
+            Ok(())
+        }
         _ => Err(ZeckFormatError::UnsupportedVersion {
-            found_version: zeck_file.version,
-            supported_version: ZECK_FORMAT_VERSION,
+            found_version: view.version,
+            supported_version: ZECK_RECORDS_FORMAT_VERSION,
         }),
     }
 }
 
-/// Version 1 decompression implementation.
+/// Checks that the compressed data of a single-file .zeck file is no longer than any compression of its original size.
+pub(crate) fn check_payload_length(view: &ZeckFileView) -> Result<(), ZeckFormatError> {
+    let max_length =
+        max_compressed_len_for_original_size(view.original_size, view.flags, view.extended_flags);
+    if view.compressed_data.len() > max_length {
+        return Err(ZeckFormatError::PayloadTooLarge {
+            payload_length: view.compressed_data.len(),
+            max_length,
+        });
+    }
+    Ok(())
+}
+
+/// Checks the CRC-32 of the compressed data against the payload CRC-32 in the header, if one is recorded.
+fn check_payload_crc(view: &ZeckFileView) -> Result<(), ZeckFormatError> {
+    let Some(expected) = view.maybe_payload_crc else {
+        return Ok(());
+    };
+    let actual = crc32(view.compressed_data);
+    if actual != expected {
+        return Err(ZeckFormatError::PayloadCrcMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// Decompresses the compressed data of a single-file .zeck file or of an archive block.
 ///
-/// This function handles decompression for .zeck format version 1, using the endianness
-/// specified in the flags byte.
-fn decompress_zeck_v1(
+/// This function handles decompression for .zeck format versions 1 and 2, using the endianness,
+/// representation, polarity, and second stage specified in the flags byte, and the sparse index list encoding from
+/// the extended flags byte. If the padding bits are recorded, the compressed data must be canonical for exactly that
+/// many bits (see [`check_padding_bits`]).
+pub(crate) fn decompress_zeck_payload(
     compressed_data: &[u8],
     original_size: u64,
     flags: u8,
+    extended_flags: u8,
+    maybe_padding_bits: Option<u8>,
 ) -> Result<Vec<u8>, ZeckFormatError> {
-    let is_big_endian = (flags & ZECK_FLAG_BIG_ENDIAN) != 0;
+    let value = decompress_zeck_payload_to_biguint(
+        compressed_data,
+        original_size,
+        flags,
+        extended_flags,
+        maybe_padding_bits,
+    )?;
+    let flags = ZeckFlags::from_byte(flags);
+    let is_big_endian = flags.is_big_endian();
 
-    let decompressed = if is_big_endian {
-        padless_zeckendorf_decompress_be_dangerous(compressed_data)
+    // Zero would convert to a single zero byte whatever the original length was, so convert it to no bytes and let the
+    // padding below restore all-zero data of any original size, including empty data.
+    let decompressed = if value.is_zero() {
+        Vec::new()
+    } else if is_big_endian {
+        value.to_bytes_be()
     } else {
-        padless_zeckendorf_decompress_le_dangerous(compressed_data)
+        value.to_bytes_le()
     };
 
     let original_size_usize = original_size as usize;
@@ -87,6 +656,179 @@ fn decompress_zeck_v1(
         });
     }
 
+    let mut restored = pad_to_original_size(decompressed, original_size_usize, is_big_endian);
+
+    // If the input bytes were inverted before compression, invert them back, including the restored padding
+    if flags.is_inverted {
+        restored.iter_mut().for_each(|byte| *byte = !*byte);
+    }
+
+    Ok(restored)
+}
+
+/// Decodes single-file compressed data to the integer it represents, before it is converted to bytes, padded to the
+/// original size, and un-inverted.
+///
+/// The padding bits and entropy coding are checked the same way as in [`decompress_zeck_payload`].
+fn decompress_zeck_payload_to_biguint(
+    compressed_data: &[u8],
+    original_size: u64,
+    flags: u8,
+    extended_flags: u8,
+    maybe_padding_bits: Option<u8>,
+) -> Result<BigUint, ZeckFormatError> {
+    let flags = ZeckFlags::from_byte(flags);
+    check_codec_flags(
+        compressed_data,
+        original_size,
+        flags,
+        extended_flags,
+        maybe_padding_bits,
+    )?;
+
+    if extended_flags & ZECK_EXTENDED_FLAG_UNCOMPRESSED != 0 {
+        // Reading the raw data as an integer lets the padding and pre-transforms be undone like any other file's
+        return Ok(if flags.is_big_endian() {
+            BigUint::from_bytes_be(compressed_data)
+        } else {
+            BigUint::from_bytes_le(compressed_data)
+        });
+    }
+
+    if extended_flags & ZECK_EXTENDED_FLAG_LUCAS != 0 {
+        if let Some(padding_bits) = maybe_padding_bits {
+            check_padding_bits(compressed_data, padding_bits)?;
+        }
+        return Ok(lucas_bits_to_biguint(compressed_data));
+    }
+
+    if extended_flags & ZECK_EXTENDED_FLAG_SPARSE != 0 {
+        return sparse_index_list_to_biguint(compressed_data, original_size);
+    }
+
+    if let Some(padding_bits) = maybe_padding_bits {
+        check_padding_bits(compressed_data, padding_bits)?;
+    }
+
+    let entropy_decoded_data: Vec<u8>;
+    let compressed_data = if flags.is_entropy_coded {
+        let max_bits = max_ezba_bits_for_original_size(original_size);
+        let Some(ezba) = entropy_decode_ezba(compressed_data, max_bits) else {
+            return Err(ZeckFormatError::InvalidEntropyCoding);
+        };
+        check_canonical_ezba(&ezba, flags.is_lazy)?;
+        entropy_decoded_data = pack_ezba_bits_to_bytes(&ezba);
+        &entropy_decoded_data
+    } else {
+        compressed_data
+    };
+
+    if flags.is_lazy {
+        Ok(lazy_ezba_bytes_to_biguint(compressed_data))
+    } else {
+        Ok(ezba_bits_to_biguint(&EzbaBits::from_packed_bytes(
+            compressed_data,
+        )))
+    }
+}
+
+/// Checks that the flags of single-file compressed data select one codec the decoders support.
+///
+/// The uncompressed fallback stores the original bytes as they are, so it allows no other codec, second stage, or
+/// padding bits, and must be exactly the original size. The Lucas codec has no lazy representation, entropy coding,
+/// or sparse form. A sparse index list replaces the greedy EZBA bits, so it cannot be combined with the lazy
+/// representation, entropy coding, or padding bits.
+fn check_codec_flags(
+    compressed_data: &[u8],
+    original_size: u64,
+    flags: ZeckFlags,
+    extended_flags: u8,
+    maybe_padding_bits: Option<u8>,
+) -> Result<(), ZeckFormatError> {
+    if extended_flags & ZECK_EXTENDED_FLAG_UNCOMPRESSED != 0
+        && (flags.is_lazy
+            || flags.is_entropy_coded
+            || extended_flags & (ZECK_EXTENDED_FLAG_SPARSE | ZECK_EXTENDED_FLAG_LUCAS) != 0
+            || maybe_padding_bits.is_some()
+            || compressed_data.len() as u64 != original_size)
+    {
+        return Err(ZeckFormatError::InvalidUncompressedFallback);
+    }
+    if extended_flags & ZECK_EXTENDED_FLAG_LUCAS != 0
+        && (flags.is_lazy
+            || flags.is_entropy_coded
+            || extended_flags & ZECK_EXTENDED_FLAG_SPARSE != 0)
+    {
+        return Err(ZeckFormatError::InvalidLucasFlags);
+    }
+    if extended_flags & ZECK_EXTENDED_FLAG_SPARSE != 0
+        && (flags.is_lazy || flags.is_entropy_coded || maybe_padding_bits.is_some())
+    {
+        return Err(ZeckFormatError::InvalidSparseIndexList);
+    }
+    Ok(())
+}
+
+/// Decodes a sparse index list (see [`crate::zeck_file_format::sparse`]) to the integer it represents.
+///
+/// The flags must already have passed [`check_codec_flags`]. The largest index is bounded the same way as the EZBA
+/// bits of entropy coded data.
+fn sparse_index_list_to_biguint(
+    compressed_data: &[u8],
+    original_size: u64,
+) -> Result<BigUint, ZeckFormatError> {
+    let max_efi = max_ezba_bits_for_original_size(original_size) as u64;
+    let ezld = decode_sparse_index_list(compressed_data, max_efi)
+        .ok_or(ZeckFormatError::InvalidSparseIndexList)?;
+    Ok(zl_to_biguint(&ezl_to_zl(&ezld)))
+}
+
+/// Returns an upper bound on the number of packed EZBA bits (greedy or lazy) for data of the given original size.
+///
+/// Each byte multiplies the value by 256, which adds log_phi(256) ≈ 11.52 Fibonacci indices, so 12 bits per byte plus
+/// a few bits for the smallest indices, the lazy terminator, and padding to a whole byte is always enough.
+fn max_ezba_bits_for_original_size(original_size: u64) -> usize {
+    usize::try_from(original_size)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(12)
+        .saturating_add(24)
+}
+
+/// Returns an upper bound on the compressed data length of a single-file .zeck file with the given original size,
+/// flags, and extended flags.
+///
+/// Entropy coding spends at most 3 bits on every 2 EZBA bits, plus two Elias gamma codes for the run counts. A sparse
+/// index list never uses two consecutive indices, so it holds at most one varint for every other EZBA bit, each at most
+/// the 10 bytes of a [`u64`] LEB128 varint.
+pub(crate) fn max_compressed_len_for_original_size(
+    original_size: u64,
+    flags: u8,
+    extended_flags: u8,
+) -> usize {
+    let max_ezba_bits = max_ezba_bits_for_original_size(original_size);
+    if extended_flags & ZECK_EXTENDED_FLAG_SPARSE != 0 {
+        return (max_ezba_bits / 2 + 1).saturating_mul(u64::BITS.div_ceil(7) as usize);
+    }
+    let max_bits = if ZeckFlags::from_byte(flags).is_entropy_coded {
+        (max_ezba_bits / 2)
+            .saturating_mul(3)
+            .saturating_add(4 * u64::BITS as usize)
+    } else {
+        max_ezba_bits
+    };
+    max_bits.div_ceil(8)
+}
+
+/// Pads decompressed data with zeros to restore the original size.
+///
+/// If the data was compressed with big endian, the padding is leading zeros, otherwise it is trailing zeros.
+fn pad_to_original_size(
+    decompressed: Vec<u8>,
+    original_size_usize: usize,
+    is_big_endian: bool,
+) -> Vec<u8> {
+    let decompressed_len = decompressed.len();
+
     // If decompressed size is smaller than original, pad with zeros, to restore the original size
     if decompressed_len < original_size_usize {
         let mut padded = Vec::with_capacity(original_size_usize);
@@ -99,9 +841,716 @@ fn decompress_zeck_v1(
             padded.extend_from_slice(&decompressed);
             padded.resize(original_size_usize, 0u8);
         }
-        Ok(padded)
+        padded
     } else {
         // Sizes match exactly
-        Ok(decompressed)
+        decompressed
+    }
+}
+
+/// Decodes data that was encoded as a lazy EZBA (see [`crate::lazy_ezba_from_biguint`]) to the integer it represents.
+fn lazy_ezba_bytes_to_biguint(compressed_data: &[u8]) -> BigUint {
+    let compressed_data_as_bits = unpack_bytes_to_ezba_bits(compressed_data);
+    let compressed_data_as_ezla = lazy_ezba_to_ezla(&compressed_data_as_bits);
+    let compressed_data_as_zla = ezl_to_zl(&compressed_data_as_ezla);
+    zl_to_biguint(&compressed_data_as_zla)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lucas::{lucas_compress_be, lucas_compress_le};
+    use crate::zeck_file_format::archive::compress_zeck_archive;
+    use crate::zeck_file_format::compress::{
+        compress_with_flags, compress_zeck_be, compress_zeck_delta, compress_zeck_entropy_coded,
+        compress_zeck_le, compress_zeck_lucas, compress_zeck_rotations, compress_zeck_sparse,
+        delta_encode, zeckendorf_compress_u64_slice,
+    };
+    use crate::zeck_file_format::file::{deserialize_zeck_file, ezba_padding_bits};
+    use crate::zeck_file_format::sparse::encode_sparse_index_list;
+    use crate::zeck_file_format::{
+        ZECK_EXTENDED_FLAG_ELEMENT_WIDTH, ZECK_EXTENDED_FLAG_MTIME, ZECK_EXTENDED_FLAG_PAYLOAD_CRC,
+        ZECK_EXTENDED_FLAG_ROTATION, ZECK_FLAG_BIG_ENDIAN, ZECK_FLAG_CHECKSUM,
+        ZECK_FLAG_ENTROPY_CODED, ZECK_FLAG_EXTENDED, ZECK_FLAG_FILENAME, ZECK_FLAG_INVERTED,
+        ZECK_FLAG_LAZY, ZECK_FLAG_PADDING_BITS,
+    };
+    use crate::{
+        Endianness, entropy_encode_ezba, memoized_zeckendorf_list_descending_for_biguint, zl_to_ezl,
+    };
+
+    /// Decompresses a file in chunks of `chunk_size` bytes, returning the chunks.
+    fn collect_chunks(
+        zeck_file: &ZeckFile,
+        chunk_size: usize,
+    ) -> Result<Vec<Vec<u8>>, ZeckFormatError> {
+        let mut chunks = Vec::new();
+        decompress_view_chunked(&zeck_file.as_view(), chunk_size, |chunk| {
+            chunks.push(chunk.to_vec())
+        })?;
+        Ok(chunks)
+    }
+
+    #[test]
+    fn chunked_decompression_concatenates_to_full_decompression() {
+        // Arrange
+        let mut original = crate::test_data::generate_random_data(3_000, 17);
+        original[..5].fill(0);
+        original[2_990..].fill(0);
+        let mut checksummed = compress_zeck_be(&original).unwrap();
+        checksummed.compute_checksum(&original);
+        let zeck_files = [
+            compress_zeck_be(&original).unwrap(),
+            compress_zeck_le(&original).unwrap(),
+            checksummed,
+            compress_zeck_delta(&original, Endianness::Little).unwrap(),
+            compress_zeck_entropy_coded(&original, Endianness::Big).unwrap(),
+            ZeckFile::new_with_flags(
+                original.len() as u64,
+                compress_with_flags(&original, ZECK_FLAG_LAZY | ZECK_FLAG_INVERTED),
+                ZECK_FLAG_LAZY | ZECK_FLAG_INVERTED,
+            ),
+            compress_zeck_rotations(&original).unwrap().1,
+            compress_zeck_archive(&original, 1_024, true).unwrap(),
+            compress_zeck_le(&[0; 20]).unwrap(),
+            compress_zeck_be(&[]).unwrap(),
+        ];
+
+        for (index, zeck_file) in zeck_files.iter().enumerate() {
+            // Act
+            let chunks = collect_chunks(zeck_file, 7).unwrap();
+
+            // Assert
+            assert!(
+                chunks
+                    .iter()
+                    .all(|chunk| !chunk.is_empty() && chunk.len() <= 7)
+            );
+            assert_eq!(
+                chunks.concat(),
+                decompress_zeck_file(zeck_file).unwrap(),
+                "file: {index}"
+            );
+        }
+    }
+
+    #[test]
+    fn chunked_decompression_uses_default_chunk_size() {
+        // Arrange
+        let original = vec![0xA5; 2 * DECOMPRESS_CHUNK_SIZE + 3];
+        let zeck_file = compress_zeck_archive(&original, 4_096, false).unwrap();
+        let mut chunk_lengths = Vec::new();
+
+        // Act
+        decompress_zeck_file_chunked(&zeck_file, |chunk| chunk_lengths.push(chunk.len())).unwrap();
+
+        // Assert
+        assert_eq!(
+            chunk_lengths,
+            vec![DECOMPRESS_CHUNK_SIZE, DECOMPRESS_CHUNK_SIZE, 3]
+        );
+    }
+
+    #[test]
+    fn chunked_decompression_reports_checksum_mismatch_after_writing() {
+        // Arrange
+        let mut zeck_file = compress_zeck_be(&[1, 2, 3]).unwrap();
+        zeck_file.maybe_checksum = Some(crc32(&[1, 2, 4]));
+        zeck_file.flags |= crate::zeck_file_format::ZECK_FLAG_CHECKSUM;
+
+        // Act
+        let result = collect_chunks(&zeck_file, 2);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::ChecksumMismatch {
+                expected: crc32(&[1, 2, 4]),
+                actual: crc32(&[1, 2, 3]),
+            })
+        );
+    }
+
+    #[test]
+    fn decompress_zeck_stream_yields_concatenated_archives_in_order() {
+        // Arrange
+        let first = compress_zeck_archive(&[1, 2, 3, 4, 5], 2, true).unwrap();
+        let second = compress_zeck_archive(&[0, 0, 9], 2, false).unwrap();
+        let mut stream = first.to_bytes();
+        stream.extend(second.to_bytes());
+
+        // Act
+        let payloads: Vec<_> = decompress_zeck_stream(&mut stream.as_slice()).collect();
+
+        // Assert
+        assert_eq!(payloads, vec![Ok(vec![1, 2, 3, 4, 5]), Ok(vec![0, 0, 9])]);
+    }
+
+    #[test]
+    fn decompress_zeck_stream_reads_single_files_between_archives() {
+        // Arrange
+        let mut stream = compress_zeck_be(&[4, 2]).unwrap().to_bytes();
+        stream.extend(compress_zeck_archive(&[7, 8], 1, true).unwrap().to_bytes());
+        stream.extend(compress_zeck_le(&[0, 1, 0]).unwrap().to_bytes());
+
+        // Act
+        let payloads: Vec<_> = decompress_zeck_stream(&mut stream.as_slice()).collect();
+
+        // Assert
+        assert_eq!(
+            payloads,
+            vec![Ok(vec![4, 2]), Ok(vec![7, 8]), Ok(vec![0, 1, 0])]
+        );
+    }
+
+    /// Builds the bytes of a version 1 file, which has no optional header fields.
+    fn legacy_zeck_bytes(data: &[u8], flags: u8) -> Vec<u8> {
+        let mut bytes = vec![ZECK_LEGACY_FORMAT_VERSION];
+        bytes.extend((data.len() as u64).to_le_bytes());
+        bytes.push(flags);
+        bytes.extend(compress_with_flags(data, flags & ZECK_FLAG_BIG_ENDIAN));
+        bytes
+    }
+
+    #[test]
+    fn version_1_files_still_decompress() {
+        // Arrange
+        let data = [0, 3, 1, 4, 1, 5, 9];
+
+        for flags in [0, ZECK_FLAG_BIG_ENDIAN] {
+            // Act
+            let result = decompress_from_zeck_bytes(&legacy_zeck_bytes(&data, flags));
+
+            // Assert
+            assert_eq!(result, Ok(data.to_vec()), "flags: {flags:#04x}");
+        }
+    }
+
+    #[test]
+    fn version_1_files_reject_flags_added_in_version_2() {
+        // Arrange
+        let bytes = legacy_zeck_bytes(&[2, 7, 1, 8], ZECK_FLAG_BIG_ENDIAN | ZECK_FLAG_CHECKSUM);
+
+        // Act
+        let result = decompress_from_zeck_bytes(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::ReservedFlagsSet {
+                flags: ZECK_FLAG_BIG_ENDIAN | ZECK_FLAG_CHECKSUM,
+            })
+        );
+    }
+
+    #[test]
+    fn decompress_zeck_stream_stops_after_truncated_header() {
+        // Arrange
+        let mut stream = compress_zeck_archive(&[7, 8], 1, true).unwrap().to_bytes();
+        stream.extend([ZECK_ARCHIVE_FORMAT_VERSION, 0, 0]);
+
+        // Act
+        let payloads: Vec<_> = decompress_zeck_stream(&mut stream.as_slice()).collect();
+
+        // Assert
+        assert_eq!(
+            payloads,
+            vec![
+                Ok(vec![7, 8]),
+                Err(ZeckFormatError::HeaderTooShort {
+                    actual_length: 3,
+                    required_length: 10,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn appended_zero_byte_is_rejected_when_padding_bits_are_recorded() {
+        // Arrange
+        let mut zeck_file = compress_zeck_be(&[0, 1, 200]).unwrap();
+        zeck_file.compressed_data.push(0);
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ZeckFormatError::NonCanonicalPadding { .. })
+        ));
+    }
+
+    #[test]
+    fn appended_zero_byte_is_ignored_when_padding_bits_are_not_recorded() {
+        // Arrange
+        let mut zeck_file = compress_zeck_be(&[0, 1, 200]).unwrap();
+        zeck_file.maybe_padding_bits = None;
+        zeck_file.compressed_data.push(0);
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(result, Ok(vec![0, 1, 200]));
+    }
+
+    #[test]
+    fn set_padding_bit_is_rejected() {
+        // Arrange
+        let mut zeck_file = compress_zeck_be(&[200]).unwrap();
+        let padding_bits = zeck_file.maybe_padding_bits.unwrap();
+        *zeck_file.compressed_data.last_mut().unwrap() |= 1 << 7;
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert!(padding_bits > 0, "the highest bit must be a padding bit");
+        assert!(matches!(
+            result,
+            Err(ZeckFormatError::NonCanonicalPadding { .. })
+        ));
+    }
+
+    #[test]
+    fn adjacent_use_bits_are_canonical() {
+        // Arrange: every use bit implicitly skips the next index, so bits [1, 1] are F(2) + F(4) = 1 + 3
+        let zeck_file = compress_zeck_be(&[4]).unwrap();
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(zeck_file.compressed_data, vec![0b11]);
+        assert_eq!(result, Ok(vec![4]));
+    }
+
+    #[test]
+    fn entropy_coded_trailing_skip_bits_are_rejected() {
+        // Arrange
+        let mut zeck_file = compress_zeck_entropy_coded(&[4], Endianness::Big).unwrap();
+        let mut ezba = unpack_bytes_to_ezba_bits(&[0b11]);
+        ezba.extend([SKIP_BIT; 8]);
+        zeck_file.compressed_data = entropy_encode_ezba(&ezba);
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::NonCanonical {
+                ezba_bit_length: 16
+            })
+        );
+    }
+
+    #[test]
+    fn all_zero_input_round_trips_with_padding_bits() {
+        // Arrange
+        let zeck_file = compress_zeck_be(&[0, 0, 0]).unwrap();
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(zeck_file.maybe_padding_bits, Some(7));
+        assert_eq!(result, Ok(vec![0, 0, 0]));
+    }
+
+    #[test]
+    fn entropy_coded_all_ones_round_trips_within_bit_limit() {
+        for size in [1, 2, 16, 255, 1024] {
+            // Arrange
+            let data = vec![0xFF; size];
+            let zeck_file = compress_zeck_entropy_coded(&data, Endianness::Big).unwrap();
+
+            // Act
+            let decompressed = decompress_zeck_file(&zeck_file);
+
+            // Assert
+            assert_eq!(decompressed, Ok(data), "size: {size}");
+        }
+    }
+
+    #[test]
+    fn entropy_coded_data_longer_than_original_size_allows_is_rejected() {
+        // Arrange
+        let mut zeck_file = compress_zeck_entropy_coded(&[1], Endianness::Big).unwrap();
+        let mut ezba = vec![0; 1_000];
+        ezba.push(1);
+        zeck_file.compressed_data = entropy_encode_ezba(&ezba);
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(result, Err(ZeckFormatError::InvalidEntropyCoding));
+    }
+
+    #[test]
+    fn sparse_index_list_above_original_size_or_with_lazy_flag_is_rejected() {
+        // Arrange
+        let data = crate::fast_doubling_fibonacci_biguint(1_000).to_bytes_be();
+        let zeck_file = compress_zeck_sparse(&data, Endianness::Big).unwrap();
+        let too_short = ZeckFile {
+            original_size: 40,
+            ..zeck_file.clone()
+        };
+        let lazy = ZeckFile {
+            flags: zeck_file.flags | ZECK_FLAG_LAZY,
+            ..zeck_file
+        };
+
+        // Act
+        let results = [too_short, lazy].map(|zeck_file| decompress_zeck_file(&zeck_file));
+
+        // Assert
+        assert_eq!(
+            results,
+            [const { Err(ZeckFormatError::InvalidSparseIndexList) }; 2]
+        );
+    }
+
+    #[test]
+    fn lucas_codec_with_entropy_coding_or_sparse_list_is_rejected() {
+        // Arrange
+        let zeck_file = compress_zeck_lucas(&[3, 1, 4], Endianness::Little).unwrap();
+        let entropy_coded = ZeckFile {
+            flags: zeck_file.flags | ZECK_FLAG_ENTROPY_CODED,
+            ..zeck_file.clone()
+        };
+        let sparse = ZeckFile {
+            extended_flags: zeck_file.extended_flags | ZECK_EXTENDED_FLAG_SPARSE,
+            ..zeck_file
+        };
+
+        // Act
+        let results = [entropy_coded, sparse].map(|zeck_file| decompress_zeck_file(&zeck_file));
+
+        // Assert
+        assert_eq!(
+            results,
+            [const { Err(ZeckFormatError::InvalidLucasFlags) }; 2]
+        );
+    }
+
+    #[test]
+    fn payload_crc_mismatch_fails_before_decompressing() {
+        // Arrange
+        let original = crate::test_data::generate_random_data(64, 5);
+        let mut zeck_file = compress_zeck_le(&original).unwrap();
+        zeck_file.compute_payload_crc();
+        let expected = zeck_file.payload_crc();
+        let mut bytes = zeck_file.to_bytes();
+        // Without the payload CRC-32, this corruption would only be found after decoding, as non-canonical padding
+        let corrupted_index = bytes.len() - 1;
+        bytes[corrupted_index] = 0;
+        let corrupted = deserialize_zeck_file(&bytes).unwrap();
+
+        // Act
+        let result = decompress_zeck_file(&corrupted);
+        let chunked_result = collect_chunks(&corrupted, 16);
+
+        // Assert
+        assert_eq!(
+            deserialize_zeck_file(&zeck_file.to_bytes()),
+            Ok(zeck_file.clone())
+        );
+        assert_eq!(decompress_zeck_file(&zeck_file), Ok(original));
+        let mismatch = ZeckFormatError::PayloadCrcMismatch {
+            expected,
+            actual: corrupted.payload_crc(),
+        };
+        assert_eq!(result, Err(mismatch.clone()));
+        assert_eq!(chunked_result, Err(mismatch));
+    }
+
+    #[test]
+    fn peek_original_size_matches_full_parse() {
+        // Arrange
+        let mut named = compress_zeck_be(&[0, 0, 7, 9]).unwrap();
+        named.maybe_filename = Some("data.bin".to_string());
+        let files = [
+            compress_zeck_le(&[]).unwrap(),
+            named,
+            compress_zeck_archive(&[5; 300], 64, false).unwrap(),
+        ];
+
+        for zeck_file in files {
+            let bytes = zeck_file.to_bytes();
+
+            // Act
+            let original_size = peek_original_size(&bytes);
+
+            // Assert
+            assert_eq!(
+                original_size,
+                Ok(deserialize_zeck_file(&bytes).unwrap().original_size)
+            );
+            assert_eq!(
+                peek_original_size(&bytes[..ZECK_HEADER_SIZE - 1]),
+                Err(ZeckFormatError::HeaderTooShort {
+                    actual_length: ZECK_HEADER_SIZE - 1,
+                    required_length: ZECK_HEADER_SIZE,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn all_zero_input_round_trips_to_original_length() {
+        for len in [0, 1, 3, 10] {
+            // Arrange
+            let data = vec![0u8; len];
+            let zeck_files = [
+                compress_zeck_be(&data).unwrap(),
+                compress_zeck_le(&data).unwrap(),
+                compress_zeck_entropy_coded(&data, Endianness::Big).unwrap(),
+            ];
+
+            for zeck_file in zeck_files {
+                // Act
+                let decompressed = decompress_zeck_file(&zeck_file);
+
+                // Assert
+                assert_eq!(zeck_file.compressed_data.len(), 1, "len: {len}");
+                assert_eq!(decompressed, Ok(data.clone()), "len: {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn u64_slices_round_trip() {
+        let inputs: [&[u64]; 5] = [
+            &[],
+            &[0],
+            &[0, 0, 0],
+            &[u64::MAX, u64::MAX],
+            &[0, 1, u64::MAX, 0x0123_4567_89AB_CDEF, 0],
+        ];
+        for values in inputs {
+            for endianness in [Endianness::Big, Endianness::Little] {
+                // Arrange
+                let zeck_file = zeckendorf_compress_u64_slice(values, endianness).unwrap();
+
+                // Act
+                let decompressed = decompress_to_u64_vec(&zeck_file);
+
+                // Assert
+                assert_eq!(
+                    decompressed.as_deref(),
+                    Ok(values),
+                    "endianness: {endianness}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn u64_vec_requires_a_recorded_element_width_of_8() {
+        let mut wrong_width = zeckendorf_compress_u64_slice(&[1, 2], Endianness::Little).unwrap();
+        wrong_width.maybe_element_width = Some(4);
+        let mut width_not_dividing = wrong_width.clone();
+        width_not_dividing.maybe_element_width = Some(3);
+        let cases = [
+            (
+                "bytes 8 long",
+                compress_zeck_le(&[1, 0, 0, 0, 0, 0, 0, 0]).unwrap(),
+                ZeckFormatError::ElementWidthMismatch {
+                    maybe_element_width: None,
+                    element_size: 8,
+                },
+            ),
+            (
+                "4 byte elements",
+                wrong_width,
+                ZeckFormatError::ElementWidthMismatch {
+                    maybe_element_width: Some(4),
+                    element_size: 8,
+                },
+            ),
+            (
+                "width not dividing the original size",
+                width_not_dividing,
+                ZeckFormatError::ElementWidthMismatch {
+                    maybe_element_width: Some(3),
+                    element_size: 8,
+                },
+            ),
+        ];
+
+        for (name, zeck_file, expected) in cases {
+            // Act
+            let result =
+                decompress_to_u64_vec(&deserialize_zeck_file(&zeck_file.to_bytes()).unwrap());
+
+            // Assert
+            assert_eq!(result, Err(expected), "{name}");
+        }
+    }
+
+    #[test]
+    fn decompress_zeck_stream_is_empty_for_empty_reader() {
+        // Act
+        let payloads: Vec<_> = decompress_zeck_stream(&mut [].as_slice()).collect();
+
+        // Assert
+        assert!(payloads.is_empty());
+    }
+
+    /// Builds a version 2 file for `data` with the given flags, filling in every optional field the flags call for
+    /// and compressing the data the way the flags describe, as far as the flags can be combined.
+    fn zeck_file_with_flags(data: &[u8], flags: u8, extended_flags: u8) -> ZeckFile {
+        let mut prepared = data.to_vec();
+        if extended_flags & ZECK_EXTENDED_FLAG_ROTATION != 0 {
+            prepared.rotate_left(1);
+        }
+        if extended_flags & ZECK_EXTENDED_FLAG_DELTA != 0 {
+            prepared = delta_encode(&prepared);
+        }
+        if flags & ZECK_FLAG_INVERTED != 0 {
+            prepared.iter_mut().for_each(|byte| *byte = !*byte);
+        }
+        let is_big_endian = flags & ZECK_FLAG_BIG_ENDIAN != 0;
+
+        let compressed_data = if extended_flags & ZECK_EXTENDED_FLAG_UNCOMPRESSED != 0 {
+            prepared
+        } else if extended_flags & ZECK_EXTENDED_FLAG_LUCAS != 0 {
+            if is_big_endian {
+                lucas_compress_be(&prepared)
+            } else {
+                lucas_compress_le(&prepared)
+            }
+        } else if extended_flags & ZECK_EXTENDED_FLAG_SPARSE != 0 {
+            let value = if is_big_endian {
+                BigUint::from_bytes_be(&prepared)
+            } else {
+                BigUint::from_bytes_le(&prepared)
+            };
+            encode_sparse_index_list(&zl_to_ezl(
+                &memoized_zeckendorf_list_descending_for_biguint(&value),
+            ))
+        } else {
+            // compress_with_flags inverts the data itself
+            if flags & ZECK_FLAG_INVERTED != 0 {
+                prepared.iter_mut().for_each(|byte| *byte = !*byte);
+            }
+            compress_with_flags(&prepared, flags)
+        };
+
+        let mut zeck_file = ZeckFile {
+            version: ZECK_FORMAT_VERSION,
+            original_size: data.len() as u64,
+            flags: if extended_flags != 0 {
+                flags | ZECK_FLAG_EXTENDED
+            } else {
+                flags
+            },
+            extended_flags,
+            maybe_padding_bits: (flags & ZECK_FLAG_PADDING_BITS != 0)
+                .then(|| ezba_padding_bits(&compressed_data)),
+            maybe_checksum: (flags & ZECK_FLAG_CHECKSUM != 0).then(|| crc32(data)),
+            maybe_filename: (flags & ZECK_FLAG_FILENAME != 0).then(|| "fuzz.bin".to_string()),
+            maybe_rotation: (extended_flags & ZECK_EXTENDED_FLAG_ROTATION != 0).then_some(1),
+            maybe_payload_crc: None,
+            maybe_mtime: (extended_flags & ZECK_EXTENDED_FLAG_MTIME != 0).then_some(1_700_000_000),
+            maybe_element_width: (extended_flags & ZECK_EXTENDED_FLAG_ELEMENT_WIDTH != 0)
+                .then_some(1),
+            compressed_data,
+        };
+        if extended_flags & ZECK_EXTENDED_FLAG_PAYLOAD_CRC != 0 {
+            zeck_file.compute_payload_crc();
+        }
+        zeck_file
+    }
+
+    #[test]
+    fn every_flag_combination_decompresses_or_errors_without_panicking() {
+        for seed in [0, crate::test_data::DEFAULT_TEST_DATA_SEED] {
+            let data = crate::test_data::generate_random_data(9, seed);
+            // The extended flag is set by the file itself whenever the extended flags byte is nonzero
+            for flags in (0..=u8::MAX).filter(|flags| flags & ZECK_FLAG_EXTENDED == 0) {
+                for extended_flags in 0..=u8::MAX {
+                    // Arrange
+                    let zeck_file = zeck_file_with_flags(&data, flags, extended_flags);
+                    let is_sparse = extended_flags & ZECK_EXTENDED_FLAG_SPARSE != 0;
+                    let is_lucas = extended_flags & ZECK_EXTENDED_FLAG_LUCAS != 0;
+                    let is_uncompressed = extended_flags & ZECK_EXTENDED_FLAG_UNCOMPRESSED != 0;
+                    let is_lazy_or_entropy_coded =
+                        flags & (ZECK_FLAG_LAZY | ZECK_FLAG_ENTROPY_CODED) != 0;
+                    let is_rejected = (is_sparse
+                        && (is_lazy_or_entropy_coded || flags & ZECK_FLAG_PADDING_BITS != 0))
+                        || (is_lucas && (is_lazy_or_entropy_coded || is_sparse))
+                        || (is_uncompressed
+                            && (is_lazy_or_entropy_coded
+                                || is_sparse
+                                || is_lucas
+                                || flags & ZECK_FLAG_PADDING_BITS != 0));
+                    // Padding bits describe packed EZBA bits, so on an entropy coded stream they only pass the
+                    // padding check when its last byte happens to be nonzero
+                    let is_entropy_coded_with_padding_bits =
+                        flags & ZECK_FLAG_ENTROPY_CODED != 0 && flags & ZECK_FLAG_PADDING_BITS != 0;
+                    let is_accepted = !(is_rejected || is_entropy_coded_with_padding_bits);
+
+                    // Act
+                    let result = decompress_zeck_file(&zeck_file);
+
+                    // Assert
+                    let context = format!(
+                        "seed: {seed}, flags: {flags:#010b}, extended flags: {extended_flags:#010b}, result: {result:?}"
+                    );
+                    if let Ok(decompressed) = &result {
+                        assert_eq!(decompressed, &data, "{context}");
+                    }
+                    assert!(!is_accepted || result.is_ok(), "{context}");
+                    assert!(!is_rejected || result.is_err(), "{context}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn uncompressed_fallback_decompresses_to_the_raw_bytes() {
+        for is_big_endian in [false, true] {
+            // Arrange
+            let original = vec![0, 0xFF, 0, 0x12, 0];
+            let zeck_file = ZeckFile::builder()
+                .original_size(original.len() as u64)
+                .compressed_data(original.clone())
+                .big_endian(is_big_endian)
+                .uncompressed_fallback(true)
+                .build();
+            let deserialized = deserialize_zeck_file(&zeck_file.to_bytes()).unwrap();
+
+            // Act
+            let decompressed = decompress_zeck_file(&deserialized);
+
+            // Assert
+            assert!(deserialized.is_uncompressed_fallback());
+            assert!(
+                !compress_zeck_le(&original)
+                    .unwrap()
+                    .is_uncompressed_fallback()
+            );
+            assert_eq!(deserialized.maybe_padding_bits, None);
+            assert_eq!(decompressed, Ok(original), "big endian: {is_big_endian}");
+        }
+    }
+
+    #[test]
+    fn uncompressed_fallback_of_the_wrong_size_is_rejected() {
+        // Arrange
+        let zeck_file = ZeckFile::builder()
+            .original_size(4)
+            .compressed_data(vec![1, 2, 3])
+            .uncompressed_fallback(true)
+            .build();
+
+        // Act
+        let result = decompress_zeck_file(&zeck_file);
+
+        // Assert
+        assert_eq!(result, Err(ZeckFormatError::InvalidUncompressedFallback));
     }
 }
@@ -1,4 +1,6 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::io;
+use std::sync::Arc;
 use tsify::Tsify;
 
 /// Errors that can occur when parsing or processing .zeck files.
@@ -19,6 +21,15 @@ pub enum ZeckFormatError {
         /// The maximum supported version
         supported_version: u8,
     },
+    /// The file is a different kind of .zeck file than the operation reads, such as a block archive passed to
+    /// [`crate::zeck_file_format::archive::decompress_records`], or an archive of records passed to
+    /// [`crate::zeck_file_format::decompress::decompress_zeck_file`].
+    UnexpectedVersion {
+        /// The version found in the header
+        found_version: u8,
+        /// The version the operation reads
+        expected_version: u8,
+    },
     /// The reserved flags in the header are set (indicating a newer format version).
     ReservedFlagsSet {
         /// The flags byte from the header
@@ -45,6 +56,182 @@ pub enum ZeckFormatError {
         /// The size that could not be converted
         size: usize,
     },
+    /// The original size is not a whole number of elements of the requested type.
+    ElementSizeMismatch {
+        /// The original size from the header, in bytes
+        original_size: u64,
+        /// The size of one element, in bytes
+        element_size: usize,
+    },
+    /// The file does not record the element width of the requested type, so it was not compressed from elements of
+    /// that type.
+    ElementWidthMismatch {
+        /// The element width recorded in the header, in bytes, if any
+        maybe_element_width: Option<u8>,
+        /// The size of one element of the requested type, in bytes
+        element_size: usize,
+    },
+    /// The requested block does not exist in the archive.
+    BlockIndexOutOfRange {
+        /// The requested block index
+        index: usize,
+        /// The number of blocks in the archive
+        block_count: usize,
+    },
+    /// A block index entry's offset and length overflow, so it cannot point inside the archive's block payloads.
+    BlockOutOfBounds {
+        /// The index of the block whose entry is invalid
+        block_index: usize,
+    },
+    /// A block index entry declares an original length above [`crate::zeck_file_format::ZECK_ARCHIVE_MAX_BLOCK_SIZE`],
+    /// or one that would make the blocks add up to more than the archive's original size.
+    InvalidBlockSize {
+        /// The index of the block whose entry is invalid
+        block_index: usize,
+        /// The original length declared for the block. This is the full 8-byte field from the block index, since a
+        /// length above the limit usually does not fit in a `u32`.
+        value: u64,
+    },
+    /// The block size requested for a multi-block archive is 0, so the data cannot be split into blocks.
+    ZeroBlockSize,
+    /// A block's compressed payload, or the compressed data of a version 2 file, is declared longer than the bytes that
+    /// remain, for example because the file was cut short.
+    Truncated {
+        /// The number of bytes declared by the block index
+        expected: usize,
+        /// The number of bytes actually present
+        actual: usize,
+    },
+    /// The checksum of a decompressed block does not match the checksum stored in the archive.
+    BlockChecksumMismatch {
+        /// The index of the block that failed verification
+        block_index: usize,
+        /// The CRC-32 stored in the block index
+        expected: u32,
+        /// The CRC-32 of the decompressed block
+        actual: u32,
+    },
+    /// The compressed data is not the canonical encoding for the padding bits recorded in the header,
+    /// for example because extra bytes were appended to it.
+    NonCanonicalPadding {
+        /// The number of padding bits recorded in the header
+        padding_bits: u8,
+        /// The length of the compressed data in bytes
+        compressed_length: usize,
+    },
+    /// The entropy coded compressed data decodes to EZBA bits that are not whole bytes ending with their last use bit
+    /// and its padding, so they are not the canonical encoding of any value.
+    NonCanonical {
+        /// The number of decoded EZBA bits
+        ezba_bit_length: usize,
+    },
+    /// The filename stored in the header is not valid UTF-8.
+    InvalidFilename,
+    /// The entropy coded compressed data is truncated, malformed, or decodes to more EZBA bits than the original size allows.
+    InvalidEntropyCoding,
+    /// The sparse index list is malformed, has indices above what the original size allows, or is combined with the
+    /// lazy representation, entropy coding, or padding bits.
+    InvalidSparseIndexList,
+    /// The Lucas codec is combined with the lazy representation, entropy coding, or a sparse index list, none of which
+    /// it supports.
+    InvalidLucasFlags,
+    /// The uncompressed fallback data is not exactly the original size, or the fallback is combined with the lazy
+    /// representation, entropy coding, padding bits, a sparse index list, or the Lucas codec.
+    InvalidUncompressedFallback,
+    /// The checksum of the decompressed data does not match the checksum stored in the header.
+    ChecksumMismatch {
+        /// The CRC-32 stored in the header
+        expected: u32,
+        /// The CRC-32 of the decompressed data
+        actual: u32,
+    },
+    /// The CRC-32 of the compressed data does not match the payload CRC-32 stored in the header, so the compressed
+    /// data was corrupted after it was written.
+    PayloadCrcMismatch {
+        /// The payload CRC-32 stored in the header
+        expected: u32,
+        /// The CRC-32 of the compressed data
+        actual: u32,
+    },
+    /// The compressed data of a single-file .zeck file is longer than any compression of the original size could be, so
+    /// the header and payload do not belong together.
+    PayloadTooLarge {
+        /// The length of the compressed data
+        payload_length: usize,
+        /// The largest compressed data length the original size and flags allow
+        max_length: usize,
+    },
+    /// Bytes follow the compressed data of a version 2 file, whose recorded payload length says where the file ends.
+    TrailingData {
+        /// The number of bytes after the end of the file
+        trailing_length: usize,
+    },
+    /// The rotation offset stored in the header is not smaller than the original size, so it cannot be undone.
+    RotationOutOfRange {
+        /// The rotation offset stored in the header
+        rotation: u64,
+        /// The original size from the header, in bytes
+        original_size: u64,
+    },
+    /// Converting the file to the requested format version would lose information that the target version cannot
+    /// represent, such as a filename, extended flags, entropy coding, or all but one block of an archive.
+    LossyVersionConversion {
+        /// The version of the file being converted
+        from_version: u8,
+        /// The requested version
+        to_version: u8,
+    },
+    /// Reading .zeck data from a stream failed.
+    Io(#[tsify(type = "string")] ZeckIoError),
+}
+
+impl From<io::Error> for ZeckFormatError {
+    fn from(error: io::Error) -> Self {
+        ZeckFormatError::Io(ZeckIoError(Arc::new(error)))
+    }
+}
+
+/// An [`io::Error`] wrapped so that [`ZeckFormatError`] can stay [`Clone`], [`PartialEq`], and serializable.
+///
+/// Two errors are equal if they have the same [`io::ErrorKind`] and message. Serializing keeps only the message.
+#[derive(Debug, Clone)]
+pub struct ZeckIoError(Arc<io::Error>);
+
+impl ZeckIoError {
+    /// Returns the kind of the underlying I/O error.
+    pub fn kind(&self) -> io::ErrorKind {
+        self.0.kind()
+    }
+
+    /// Returns the underlying I/O error.
+    pub fn io_error(&self) -> &io::Error {
+        &self.0
+    }
+}
+
+impl PartialEq for ZeckIoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl std::fmt::Display for ZeckIoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for ZeckIoError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ZeckIoError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let message = String::deserialize(deserializer)?;
+        Ok(ZeckIoError(Arc::new(io::Error::other(message))))
+    }
 }
 
 impl std::fmt::Display for ZeckFormatError {
@@ -70,6 +257,16 @@ impl std::fmt::Display for ZeckFormatError {
                     found_version, supported_version
                 )
             }
+            ZeckFormatError::UnexpectedVersion {
+                found_version,
+                expected_version,
+            } => {
+                write!(
+                    f,
+                    "Unexpected file format version: found {}, this operation reads version {}",
+                    found_version, expected_version
+                )
+            }
             ZeckFormatError::ReservedFlagsSet { flags } => {
                 write!(
                     f,
@@ -105,8 +302,194 @@ impl std::fmt::Display for ZeckFormatError {
                     size
                 )
             }
+            ZeckFormatError::ElementSizeMismatch {
+                original_size,
+                element_size,
+            } => {
+                write!(
+                    f,
+                    "Original size of {} bytes is not a multiple of the {} byte element size",
+                    original_size, element_size
+                )
+            }
+            ZeckFormatError::ElementWidthMismatch {
+                maybe_element_width,
+                element_size,
+            } => match maybe_element_width {
+                Some(element_width) => write!(
+                    f,
+                    "File records {} byte elements, but {} byte elements were requested",
+                    element_width, element_size
+                ),
+                None => write!(
+                    f,
+                    "File records no element width, but {} byte elements were requested",
+                    element_size
+                ),
+            },
+            ZeckFormatError::BlockIndexOutOfRange { index, block_count } => {
+                write!(
+                    f,
+                    "Block index {} is out of range: archive has {} blocks",
+                    index, block_count
+                )
+            }
+            ZeckFormatError::InvalidBlockSize { block_index, value } => {
+                write!(
+                    f,
+                    "Block {} declares an invalid original length of {} bytes",
+                    block_index, value
+                )
+            }
+            ZeckFormatError::BlockOutOfBounds { block_index } => {
+                write!(
+                    f,
+                    "Block {} extends past the end of the archive's block payloads",
+                    block_index
+                )
+            }
+            ZeckFormatError::ZeroBlockSize => {
+                write!(f, "Block size must be greater than 0")
+            }
+            ZeckFormatError::Truncated { expected, actual } => {
+                write!(
+                    f,
+                    "Compressed data is truncated: expected {} bytes, got {} bytes",
+                    expected, actual
+                )
+            }
+            ZeckFormatError::BlockChecksumMismatch {
+                block_index,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Block {} checksum mismatch: expected 0x{:08x}, got 0x{:08x}",
+                    block_index, expected, actual
+                )
+            }
+            ZeckFormatError::NonCanonicalPadding {
+                padding_bits,
+                compressed_length,
+            } => {
+                write!(
+                    f,
+                    "Compressed data of {} bytes is not canonical for {} padding bits",
+                    compressed_length, padding_bits
+                )
+            }
+            ZeckFormatError::NonCanonical { ezba_bit_length } => {
+                write!(
+                    f,
+                    "Compressed data decodes to {} EZBA bits that are not canonical",
+                    ezba_bit_length
+                )
+            }
+            ZeckFormatError::InvalidFilename => {
+                write!(f, "Filename in header is not valid UTF-8")
+            }
+            ZeckFormatError::InvalidEntropyCoding => {
+                write!(f, "Entropy coded compressed data is invalid")
+            }
+            ZeckFormatError::InvalidSparseIndexList => {
+                write!(f, "Sparse index list compressed data is invalid")
+            }
+            ZeckFormatError::InvalidLucasFlags => {
+                write!(
+                    f,
+                    "The Lucas codec cannot be combined with the lazy representation, entropy coding, or a sparse index list"
+                )
+            }
+            ZeckFormatError::InvalidUncompressedFallback => {
+                write!(
+                    f,
+                    "Uncompressed fallback data must be exactly the original size and cannot be combined with another encoding"
+                )
+            }
+            ZeckFormatError::ChecksumMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Checksum mismatch: expected 0x{:08x}, got 0x{:08x}",
+                    expected, actual
+                )
+            }
+            ZeckFormatError::PayloadCrcMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Payload CRC-32 mismatch: expected 0x{:08x}, got 0x{:08x}",
+                    expected, actual
+                )
+            }
+            ZeckFormatError::PayloadTooLarge {
+                payload_length,
+                max_length,
+            } => {
+                write!(
+                    f,
+                    "Compressed data is {payload_length} bytes, but the original size allows at most {max_length} bytes"
+                )
+            }
+            ZeckFormatError::TrailingData { trailing_length } => {
+                write!(
+                    f,
+                    "{trailing_length} bytes follow the end of the compressed data"
+                )
+            }
+            ZeckFormatError::RotationOutOfRange {
+                rotation,
+                original_size,
+            } => {
+                write!(
+                    f,
+                    "Rotation offset {} is out of range for original size {}",
+                    rotation, original_size
+                )
+            }
+            ZeckFormatError::LossyVersionConversion {
+                from_version,
+                to_version,
+            } => {
+                write!(
+                    f,
+                    "Converting from version {} to version {} would lose information",
+                    from_version, to_version
+                )
+            }
+            ZeckFormatError::Io(error) => {
+                write!(f, "I/O error: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ZeckFormatError {
+    /// Returns the underlying I/O error for [`ZeckFormatError::Io`], so error chains print the cause.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ZeckFormatError::Io(error) => Some(error.io_error()),
+            _ => None,
         }
     }
 }
 
-impl std::error::Error for ZeckFormatError {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn io_error_is_the_source() {
+        // Arrange
+        let error =
+            ZeckFormatError::from(io::Error::new(io::ErrorKind::UnexpectedEof, "cut short"));
+
+        // Act
+        let maybe_source = error.source();
+
+        // Assert
+        let source = maybe_source.expect("Io errors have a source");
+        assert_eq!(source.to_string(), "cut short");
+        assert!(ZeckFormatError::InvalidFilename.source().is_none());
+    }
+}
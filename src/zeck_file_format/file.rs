@@ -1,9 +1,26 @@
 //! Zeck file structure and serialization
 
+use crate::Endianness;
 use crate::zeck_file_format::{
-    ZECK_FLAG_BIG_ENDIAN, ZECK_FORMAT_VERSION, ZECK_HEADER_SIZE, error::ZeckFormatError,
+    ZECK_ARCHIVE_BLOCK_COUNT_SIZE, ZECK_ARCHIVE_FORMAT_VERSION, ZECK_ARCHIVE_INDEX_ENTRY_SIZE,
+    ZECK_CHECKSUM_SIZE, ZECK_ELEMENT_WIDTH_SIZE, ZECK_EXTENDED_FLAG_DELTA,
+    ZECK_EXTENDED_FLAG_ELEMENT_WIDTH, ZECK_EXTENDED_FLAG_LUCAS, ZECK_EXTENDED_FLAG_MTIME,
+    ZECK_EXTENDED_FLAG_PAYLOAD_CRC, ZECK_EXTENDED_FLAG_ROTATION, ZECK_EXTENDED_FLAG_SPARSE,
+    ZECK_EXTENDED_FLAG_UNCOMPRESSED, ZECK_EXTENDED_FLAGS_SIZE, ZECK_FILENAME_LENGTH_SIZE,
+    ZECK_FORMAT_VERSION, ZECK_HEADER_SIZE, ZECK_LEGACY_FORMAT_VERSION, ZECK_MTIME_SIZE,
+    ZECK_PADDING_BITS_SIZE, ZECK_PAYLOAD_CRC_SIZE, ZECK_PAYLOAD_LENGTH_SIZE,
+    ZECK_RECORDS_FORMAT_VERSION, ZECK_ROTATION_SIZE,
+    archive::ZeckArchive,
+    builder::ZeckFileBuilder,
+    checksum::crc32,
+    compress::compress_with_flags,
+    decompress::{check_payload_length, decompress_zeck_file},
+    error::ZeckFormatError,
+    flags::ZeckFlags,
+    view::ZeckFileView,
 };
 use serde::{Deserialize, Serialize};
+use std::io::{self, Read};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
@@ -16,15 +33,74 @@ use wasm_bindgen::prelude::*;
 /// Using `derive(Serialize, Deserialize, Tsify)` and `tsify(into_wasm_abi, from_wasm_abi)` on the struct is necessary
 /// to allow the struct to be used in the WebAssembly module, namely because the `compressed_data` field is
 /// a [`Vec<u8>`], which needed [`Copy`], and the [`wasm_bindgen`] attribute was insufficient to achieve this.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+///
+/// [`Eq`] and [`Hash`] compare what [`ZeckFile::to_bytes`] writes, including `compressed_data`, so files can be
+/// deduplicated by content in a [`HashSet`](std::collections::HashSet) or used as
+/// [`HashMap`](std::collections::HashMap) keys. The optional field bits of `flags` and `extended_flags` are taken from
+/// the optional fields, as when serializing, so two files that serialize to the same bytes are always equal.
+///
+/// The struct is `#[non_exhaustive]`, since new format versions add fields: outside this crate, build a file with
+/// [`ZeckFile::builder`] or a compression function. Fields added after version 1 default when deserializing with serde,
+/// so values serialized before they existed still load.
+#[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
+#[non_exhaustive]
 pub struct ZeckFile {
     /// File format version
     pub version: u8,
     /// Original uncompressed file size in bytes
     pub original_size: u64,
-    /// Flags byte (bit 0 = big endian, bits 1-7 reserved)
+    /// Flags byte (bit 0 = big endian, bit 1 = lazy representation, bit 2 = inverted polarity, bit 3 = padding bits, bit 4 = checksum, bit 5 = filename, bit 6 = entropy coded, bit 7 = extended flags)
     pub flags: u8,
+    /// Extended flags byte (bit 0 = rotation, bit 1 = delta encoded, bit 2 = sparse, bit 3 = payload CRC, bit 4 = Lucas,
+    /// bit 5 = uncompressed fallback, bit 6 = modification time, bit 7 = element width), or 0 if the file has none.
+    ///
+    /// [`ZeckFile::to_bytes`] sets [`ZECK_FLAG_EXTENDED`](super::ZECK_FLAG_EXTENDED) and writes this byte after the
+    /// header when it is nonzero.
+    #[serde(default)]
+    pub extended_flags: u8,
+    /// Number of padding bits (0-7) in the last byte of the compressed data, if recorded.
+    ///
+    /// When this is [`Some`], [`ZeckFile::to_bytes`] sets [`ZECK_FLAG_PADDING_BITS`](super::ZECK_FLAG_PADDING_BITS)
+    /// and writes this byte after the header.
+    #[serde(default)]
+    pub maybe_padding_bits: Option<u8>,
+    /// CRC-32 of the original uncompressed data, if recorded.
+    ///
+    /// When this is [`Some`], [`ZeckFile::to_bytes`] sets [`ZECK_FLAG_CHECKSUM`](super::ZECK_FLAG_CHECKSUM) and
+    /// decompression verifies the checksum.
+    #[serde(default)]
+    pub maybe_checksum: Option<u32>,
+    /// Name of the original uncompressed file, if recorded.
+    ///
+    /// When this is [`Some`], [`ZeckFile::to_bytes`] sets [`ZECK_FLAG_FILENAME`](super::ZECK_FLAG_FILENAME) and writes
+    /// the name after the header.
+    #[serde(default)]
+    pub maybe_filename: Option<String>,
+    /// Number of bytes the input was rotated left by before compression, if it was rotated.
+    ///
+    /// When this is [`Some`], [`ZeckFile::to_bytes`] sets [`ZECK_EXTENDED_FLAG_ROTATION`] and decompression rotates
+    /// the data back.
+    #[serde(default)]
+    pub maybe_rotation: Option<u64>,
+    /// CRC-32 of the compressed data, if recorded.
+    ///
+    /// When this is [`Some`], [`ZeckFile::to_bytes`] sets [`ZECK_EXTENDED_FLAG_PAYLOAD_CRC`] and decompression
+    /// checks it before decompressing. See [`ZeckFile::compute_payload_crc`].
+    #[serde(default)]
+    pub maybe_payload_crc: Option<u32>,
+    /// Modification time of the original file in seconds since the Unix epoch, if recorded.
+    ///
+    /// When this is [`Some`], [`ZeckFile::to_bytes`] sets [`ZECK_EXTENDED_FLAG_MTIME`] and writes it after the header.
+    /// Decompression ignores it; `zeck-decompress` restores it onto the output file.
+    #[serde(default)]
+    pub maybe_mtime: Option<u64>,
+    /// Width in bytes of the elements the original data is a sequence of, if recorded.
+    ///
+    /// When this is [`Some`], [`ZeckFile::to_bytes`] sets [`ZECK_EXTENDED_FLAG_ELEMENT_WIDTH`] and writes it after the
+    /// header. [`crate::zeck_file_format::compress::zeckendorf_compress_u64_slice`] records a width of 8.
+    #[serde(default)]
+    pub maybe_element_width: Option<u8>,
     /// Compressed data (without header)
     pub compressed_data: Vec<u8>,
 }
@@ -32,21 +108,495 @@ pub struct ZeckFile {
 impl ZeckFile {
     /// Creates a new ZeckFile with the default version and specified parameters.
     pub(crate) fn new(original_size: u64, compressed_data: Vec<u8>, is_big_endian: bool) -> Self {
-        let mut flags = 0u8;
-        if is_big_endian {
-            flags |= ZECK_FLAG_BIG_ENDIAN;
-        }
+        let flags = ZeckFlags {
+            endianness: if is_big_endian {
+                Endianness::Big
+            } else {
+                Endianness::Little
+            },
+            ..ZeckFlags::NONE
+        };
+        Self::new_with_flags(original_size, compressed_data, flags.to_byte())
+    }
+
+    /// Creates a new ZeckFile with the default version and an explicit flags byte.
+    ///
+    /// Every compression function builds its file through here, so compressed files are always stamped with
+    /// [`ZECK_FORMAT_VERSION`], whatever version a hand-built [`ZeckFile`] may have.
+    ///
+    /// The padding bits of the compressed data are recorded, so the file has an exact EZBA bit length, unless the
+    /// data is entropy coded, whose stream already ends at an exact bit.
+    pub(crate) fn new_with_flags(original_size: u64, compressed_data: Vec<u8>, flags: u8) -> Self {
+        let mut flags = ZeckFlags::from_byte(flags);
+        flags.has_padding_bits = !flags.is_entropy_coded;
+        let maybe_padding_bits = flags
+            .has_padding_bits
+            .then(|| ezba_padding_bits(&compressed_data));
         Self {
             version: ZECK_FORMAT_VERSION,
             original_size,
-            flags,
+            flags: flags.to_byte(),
+            extended_flags: 0,
+            maybe_padding_bits,
+            maybe_checksum: None,
+            maybe_filename: None,
+            maybe_rotation: None,
+            maybe_payload_crc: None,
+            maybe_mtime: None,
+            maybe_element_width: None,
             compressed_data,
         }
     }
 
+    /// Sets the given extended flags, and [`ZECK_FLAG_EXTENDED`](super::ZECK_FLAG_EXTENDED) in the flags byte, the same
+    /// way a deserialized file has them.
+    pub(crate) fn insert_extended_flags(&mut self, extended_flags: u8) {
+        self.extended_flags |= extended_flags;
+        self.update_flags(|flags| flags.has_extended_flags = true);
+    }
+
+    /// Returns the flags byte as a [`ZeckFlags`].
+    pub fn zeck_flags(&self) -> ZeckFlags {
+        ZeckFlags::from_byte(self.flags)
+    }
+
+    /// Applies `update` to the flags byte as a [`ZeckFlags`].
+    pub(crate) fn update_flags(&mut self, update: impl FnOnce(&mut ZeckFlags)) {
+        let mut flags = self.zeck_flags();
+        update(&mut flags);
+        self.flags = flags.to_byte();
+    }
+
+    /// Returns a [`ZeckFileBuilder`] for constructing a file of the current version from already compressed data.
+    pub fn builder() -> ZeckFileBuilder {
+        ZeckFileBuilder::new()
+    }
+
+    /// Reads one serialized .zeck file from a reader, returning `Ok(None)` if the reader is already at end of file.
+    ///
+    /// Version 2 files record their payload length, and multi-block archives (version 3) record the length of every
+    /// block payload in their block index, so exactly one file is read and the reader is left at the start of whatever
+    /// follows, which lets files be concatenated into a single stream. Version 1 files store no payload length, so their
+    /// compressed data extends to the end of the reader, and such a file can only be the last file in a stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::{archive::compress_zeck_archive, compress::compress_zeck_be, file::ZeckFile};
+    /// let zeck_file = compress_zeck_be(&[1, 2, 3]).unwrap();
+    /// let archive = compress_zeck_archive(&[1, 2, 3], 2, true).unwrap();
+    /// let mut bytes = zeck_file.to_bytes();
+    /// bytes.extend(archive.to_bytes());
+    ///
+    /// let mut reader = bytes.as_slice();
+    /// assert_eq!(ZeckFile::read_from(&mut reader).unwrap(), Some(zeck_file));
+    /// assert_eq!(ZeckFile::read_from(&mut reader).unwrap(), Some(archive));
+    /// assert_eq!(ZeckFile::read_from(&mut reader).unwrap(), None);
+    /// ```
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Option<Self>, ZeckFormatError> {
+        let mut header = [0u8; ZECK_HEADER_SIZE];
+        let header_length = read_up_to(reader, &mut header)?;
+        if header_length == 0 {
+            return Ok(None);
+        }
+        if header_length < ZECK_HEADER_SIZE {
+            return Err(ZeckFormatError::HeaderTooShort {
+                actual_length: header_length,
+                required_length: ZECK_HEADER_SIZE,
+            });
+        }
+
+        let version = header[0];
+        match version {
+            ZECK_LEGACY_FORMAT_VERSION => {
+                // The compressed data extends to the end of the reader, so the whole rest of the file is parsed at once
+                let mut zeck_file_data = header.to_vec();
+                reader.read_to_end(&mut zeck_file_data)?;
+                deserialize_zeck_file(&zeck_file_data).map(Some)
+            }
+            ZECK_FORMAT_VERSION => read_single_file_data(reader, header.to_vec())
+                .and_then(|zeck_file_data| deserialize_zeck_file(&zeck_file_data))
+                .map(Some),
+            ZECK_ARCHIVE_FORMAT_VERSION | ZECK_RECORDS_FORMAT_VERSION => Ok(Some(Self {
+                version,
+                original_size: u64::from_le_bytes(
+                    header[1..9]
+                        .try_into()
+                        .expect("Header slice is exactly 8 bytes"),
+                ),
+                flags: header[9],
+                extended_flags: 0,
+                maybe_padding_bits: None,
+                maybe_checksum: None,
+                maybe_filename: None,
+                maybe_rotation: None,
+                maybe_payload_crc: None,
+                maybe_mtime: None,
+                maybe_element_width: None,
+                compressed_data: read_archive_compressed_data(reader)?,
+            })),
+            _ => Err(ZeckFormatError::UnsupportedVersion {
+                found_version: version,
+                supported_version: ZECK_RECORDS_FORMAT_VERSION,
+            }),
+        }
+    }
+
+    /// Returns a [`ZeckFileView`] that borrows this file's compressed data and filename.
+    pub fn as_view(&self) -> ZeckFileView<'_> {
+        ZeckFileView {
+            version: self.version,
+            original_size: self.original_size,
+            flags: self.flags,
+            extended_flags: self.extended_flags,
+            maybe_padding_bits: self.maybe_padding_bits,
+            maybe_checksum: self.maybe_checksum,
+            maybe_filename: self.maybe_filename.as_deref(),
+            maybe_rotation: self.maybe_rotation,
+            maybe_payload_crc: self.maybe_payload_crc,
+            maybe_mtime: self.maybe_mtime,
+            maybe_element_width: self.maybe_element_width,
+            compressed_data: &self.compressed_data,
+        }
+    }
+
+    /// Returns the original uncompressed size in bytes, the same as [`ZeckFile::original_size`](#structfield.original_size).
+    ///
+    /// To read it from serialized bytes without parsing the whole file, use
+    /// [`crate::zeck_file_format::decompress::peek_original_size`].
+    pub fn original_size(&self) -> u64 {
+        self.original_size
+    }
+
+    /// Returns the exact size in bytes of the data [`decompress_zeck_file`] produces, or of all the records
+    /// [`crate::zeck_file_format::archive::decompress_records`] produces, without decompressing.
+    ///
+    /// For a single-file .zeck file this is the original size. A multi-block archive decompresses to the concatenation of its
+    /// blocks, and an archive of records to its records, so this is the sum of the original lengths in its block index (see
+    /// [`ZeckArchive::decompressed_size`]). If the block index does not parse, decompression fails anyway, and this
+    /// falls back to the original size in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::archive::compress_records;
+    /// let records: [&[u8]; 3] = [b"first", b"", b"third"];
+    /// let zeck_file = compress_records(&records, false).unwrap();
+    /// assert_eq!(zeck_file.decompressed_size(), 10);
+    /// ```
+    pub fn decompressed_size(&self) -> u64 {
+        if !matches!(
+            self.version,
+            ZECK_ARCHIVE_FORMAT_VERSION | ZECK_RECORDS_FORMAT_VERSION
+        ) {
+            return self.original_size;
+        }
+        ZeckArchive::from_parts(self.original_size, self.flags, &self.compressed_data)
+            .map_or(self.original_size, |archive| archive.decompressed_size())
+    }
+
+    /// Checks that the file is structurally valid without decompressing it, so untrusted input can be rejected cheaply.
+    ///
+    /// The version must be supported. A version 1 file may only have the endianness flag set (see
+    /// [`ZECK_FLAG_RESERVED_MASK`](super::ZECK_FLAG_RESERVED_MASK)). For a version 2 file, a recorded element width
+    /// must divide the original size, the flags must select a single supported codec (see [`ZeckFormatError::InvalidUncompressedFallback`],
+    /// [`ZeckFormatError::InvalidLucasFlags`], and [`ZeckFormatError::InvalidSparseIndexList`]), the rotation must be
+    /// smaller than the original size, a recorded payload CRC-32 must match the compressed data, the compressed data
+    /// must be no longer than any compression of the original size could be, and recorded padding bits must match the
+    /// end of the compressed data. A multi-block archive must have only the flags archives use and a block index whose
+    /// entries fit the archive.
+    ///
+    /// Passing does not mean the file decompresses: the encoding of the compressed data and the checksum of the
+    /// original data are only checked by [`decompress_zeck_file`], which calls this first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::{compress::compress_zeck_le, error::ZeckFormatError};
+    /// let mut zeck_file = compress_zeck_le(&[1, 2, 3]).unwrap();
+    /// assert_eq!(zeck_file.validate(), Ok(()));
+    ///
+    /// zeck_file.maybe_rotation = Some(3);
+    /// assert_eq!(
+    ///     zeck_file.validate(),
+    ///     Err(ZeckFormatError::RotationOutOfRange { rotation: 3, original_size: 3 })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ZeckFormatError> {
+        self.as_view().validate()
+    }
+
     /// Returns whether the data was compressed using big endian interpretation.
     pub fn is_big_endian(&self) -> bool {
-        (self.flags & ZECK_FLAG_BIG_ENDIAN) != 0
+        self.zeck_flags().is_big_endian()
+    }
+
+    /// Returns the [`Endianness`] the data was interpreted with during compression.
+    pub fn endianness(&self) -> Endianness {
+        self.zeck_flags().endianness
+    }
+
+    /// Returns whether the data was encoded using the lazy Fibonacci representation.
+    pub fn is_lazy(&self) -> bool {
+        self.zeck_flags().is_lazy
+    }
+
+    /// Returns whether the input bytes were bitwise inverted before compression.
+    pub fn is_inverted(&self) -> bool {
+        self.zeck_flags().is_inverted
+    }
+
+    /// Returns whether the EZBA bits were entropy coded in a second stage (see [`crate::ezba_entropy`]).
+    pub fn is_entropy_coded(&self) -> bool {
+        self.zeck_flags().is_entropy_coded
+    }
+
+    /// Returns whether the input bytes were delta encoded before compression (see [`ZECK_EXTENDED_FLAG_DELTA`]).
+    pub fn is_delta_encoded(&self) -> bool {
+        (self.extended_flags & ZECK_EXTENDED_FLAG_DELTA) != 0
+    }
+
+    /// Returns whether the compressed data is a sparse index list instead of EZBA bits (see
+    /// [`ZECK_EXTENDED_FLAG_SPARSE`]).
+    pub fn is_sparse(&self) -> bool {
+        (self.extended_flags & ZECK_EXTENDED_FLAG_SPARSE) != 0
+    }
+
+    /// Returns whether the compressed data is the experimental Lucas representation instead of the Zeckendorf
+    /// representation (see [`ZECK_EXTENDED_FLAG_LUCAS`]).
+    pub fn is_lucas(&self) -> bool {
+        (self.extended_flags & ZECK_EXTENDED_FLAG_LUCAS) != 0
+    }
+
+    /// Returns whether the compressed data is the raw input stored as is, because compressing it did not help (see
+    /// [`ZECK_EXTENDED_FLAG_UNCOMPRESSED`]).
+    ///
+    /// Without any pre-transform (inversion, delta encoding, or rotation), the compressed data of such a file is
+    /// exactly the original data, so a tool can return it without decompressing.
+    pub fn is_uncompressed_fallback(&self) -> bool {
+        (self.extended_flags & ZECK_EXTENDED_FLAG_UNCOMPRESSED) != 0
+    }
+
+    /// Returns the number of bytes the input was rotated left by before compression, or 0 if it was not rotated.
+    pub fn rotation(&self) -> u64 {
+        self.maybe_rotation.unwrap_or(0)
+    }
+
+    /// Records the CRC-32 (see [`crate::zeck_file_format::checksum::crc32`]) of the original uncompressed data, so that
+    /// [`ZeckFile::to_bytes`] writes it and decompression verifies it.
+    ///
+    /// The compression functions never compute a checksum themselves, because hashing a large input costs a full extra
+    /// pass over it. Call this when the integrity check is worth that cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::{compress::compress_zeck_be, decompress::decompress_zeck_file, file::deserialize_zeck_file};
+    /// let original = vec![1, 2, 3];
+    /// let mut zeck_file = compress_zeck_be(&original).unwrap();
+    /// assert_eq!(zeck_file.maybe_checksum, None);
+    ///
+    /// zeck_file.compute_checksum(&original);
+    /// let deserialized = deserialize_zeck_file(&zeck_file.to_bytes()).unwrap();
+    /// assert!(deserialized.maybe_checksum.is_some());
+    /// assert_eq!(decompress_zeck_file(&deserialized).unwrap(), original);
+    /// ```
+    pub fn compute_checksum(&mut self, original: &[u8]) {
+        self.maybe_checksum = Some(crc32(original));
+        self.update_flags(|flags| flags.has_checksum = true);
+    }
+
+    /// Returns the CRC-32 (see [`crate::zeck_file_format::checksum::crc32`]) of the compressed data.
+    ///
+    /// Unlike the checksum of the original data, this only hashes the compressed data, so it is cheap to compute and
+    /// to check before decompressing.
+    pub fn payload_crc(&self) -> u32 {
+        crc32(&self.compressed_data)
+    }
+
+    /// Records [`ZeckFile::payload_crc`], so that [`ZeckFile::to_bytes`] writes it and decompression checks it
+    /// before decompressing, failing fast with [`ZeckFormatError::PayloadCrcMismatch`] if the compressed data was
+    /// corrupted in transport or storage.
+    ///
+    /// Call this after the compressed data is final.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::{compress::compress_zeck_be, decompress::decompress_from_zeck_bytes, ZeckFormatError};
+    /// let mut zeck_file = compress_zeck_be(&[1, 2, 3]).unwrap();
+    /// zeck_file.compute_payload_crc();
+    /// let mut bytes = zeck_file.to_bytes();
+    /// assert_eq!(decompress_from_zeck_bytes(&bytes).unwrap(), vec![1, 2, 3]);
+    ///
+    /// *bytes.last_mut().unwrap() ^= 0b1000_0000;
+    /// assert!(matches!(
+    ///     decompress_from_zeck_bytes(&bytes),
+    ///     Err(ZeckFormatError::PayloadCrcMismatch { .. })
+    /// ));
+    /// ```
+    pub fn compute_payload_crc(&mut self) {
+        self.maybe_payload_crc = Some(self.payload_crc());
+        self.insert_extended_flags(ZECK_EXTENDED_FLAG_PAYLOAD_CRC);
+    }
+
+    /// Converts the file to a newer format version, keeping its compressed payload byte for byte.
+    ///
+    /// A version 1 file becomes a version 2 file with the same flags, since version 2 only adds optional fields. A
+    /// single file becomes a single-block version 3 archive whose block is the file's compressed data. The block's
+    /// CRC-32 is taken from the recorded checksum, or computed by decompressing the file if there is none. Converting
+    /// to the file's own version returns a copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZeckFormatError::LossyVersionConversion`] if the file has a filename, extended flags, or entropy
+    /// coding, none of which a version 3 archive can store, and [`ZeckFormatError::UnsupportedVersion`] if `version` is
+    /// older than the file's version or is not a known version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::{compress::compress_zeck_be, decompress::decompress_zeck_file, ZECK_ARCHIVE_FORMAT_VERSION};
+    /// let original = vec![1, 2, 3];
+    /// let single_file = compress_zeck_be(&original).unwrap();
+    /// let archive = single_file.upgrade_to(ZECK_ARCHIVE_FORMAT_VERSION).unwrap();
+    /// assert_eq!(archive.version, ZECK_ARCHIVE_FORMAT_VERSION);
+    /// assert_eq!(decompress_zeck_file(&archive).unwrap(), original);
+    /// ```
+    pub fn upgrade_to(&self, version: u8) -> Result<ZeckFile, ZeckFormatError> {
+        match (self.version, version) {
+            (from, to) if from == to => Ok(self.clone()),
+            (ZECK_LEGACY_FORMAT_VERSION, ZECK_FORMAT_VERSION | ZECK_ARCHIVE_FORMAT_VERSION) => {
+                ZeckFile {
+                    version: ZECK_FORMAT_VERSION,
+                    ..self.clone()
+                }
+                .upgrade_to(version)
+            }
+            (ZECK_FORMAT_VERSION, ZECK_ARCHIVE_FORMAT_VERSION) => {
+                self.wrap_as_single_block_archive()
+            }
+            _ => Err(ZeckFormatError::UnsupportedVersion {
+                found_version: version,
+                supported_version: ZECK_ARCHIVE_FORMAT_VERSION,
+            }),
+        }
+    }
+
+    /// Converts the file to an older format version where that loses nothing, keeping its compressed payload byte
+    /// for byte.
+    ///
+    /// A version 3 archive with a single block becomes a version 2 file whose compressed data is that block, with the
+    /// block's CRC-32 recorded as the checksum. An empty archive becomes an empty version 2 file. Converting to the
+    /// file's own version returns a copy. Nothing converts to version 1, which cannot record the checksum.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZeckFormatError::LossyVersionConversion`] if the archive has more than one block, since a version 2
+    /// file holds a single payload, and [`ZeckFormatError::UnsupportedVersion`] if `version` is newer than the file's
+    /// version or is not a known version. A malformed archive fails with the same errors as
+    /// [`crate::zeck_file_format::archive::ZeckArchive::open`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::{archive::compress_zeck_archive, decompress::decompress_zeck_file, ZECK_FORMAT_VERSION};
+    /// let original = vec![1, 2, 3];
+    /// let archive = compress_zeck_archive(&original, 16, true).unwrap();
+    /// let single_file = archive.downgrade_to(ZECK_FORMAT_VERSION).unwrap();
+    /// assert_eq!(single_file.version, ZECK_FORMAT_VERSION);
+    /// assert_eq!(decompress_zeck_file(&single_file).unwrap(), original);
+    /// ```
+    pub fn downgrade_to(&self, version: u8) -> Result<ZeckFile, ZeckFormatError> {
+        match (self.version, version) {
+            (from, to) if from == to => Ok(self.clone()),
+            (ZECK_ARCHIVE_FORMAT_VERSION, ZECK_FORMAT_VERSION) => {
+                self.unwrap_single_block_archive()
+            }
+            _ => Err(ZeckFormatError::UnsupportedVersion {
+                found_version: version,
+                supported_version: self.version,
+            }),
+        }
+    }
+
+    /// Wraps the compressed data of a version 2 file as the only block of a version 3 archive.
+    fn wrap_as_single_block_archive(&self) -> Result<ZeckFile, ZeckFormatError> {
+        if self.maybe_filename.is_some()
+            || self.extended_flags_to_write() != 0
+            || self.is_entropy_coded()
+        {
+            return Err(ZeckFormatError::LossyVersionConversion {
+                from_version: self.version,
+                to_version: ZECK_ARCHIVE_FORMAT_VERSION,
+            });
+        }
+
+        let block_crc32 = match self.maybe_checksum {
+            Some(checksum) => checksum,
+            None => crc32(&decompress_zeck_file(self)?),
+        };
+
+        let mut compressed_data = Vec::with_capacity(
+            ZECK_ARCHIVE_BLOCK_COUNT_SIZE
+                + ZECK_ARCHIVE_INDEX_ENTRY_SIZE
+                + self.compressed_data.len(),
+        );
+        compressed_data.extend_from_slice(&1u32.to_le_bytes());
+        compressed_data.extend_from_slice(&0u64.to_le_bytes());
+        compressed_data.extend_from_slice(&(self.compressed_data.len() as u64).to_le_bytes());
+        compressed_data.extend_from_slice(&self.original_size.to_le_bytes());
+        compressed_data.extend_from_slice(&block_crc32.to_le_bytes());
+        compressed_data.extend_from_slice(&self.compressed_data);
+
+        Ok(ZeckFile {
+            version: ZECK_ARCHIVE_FORMAT_VERSION,
+            original_size: self.original_size,
+            // The block index records the compressed length and checksum, so only the representation flags remain
+            flags: ZeckFlags {
+                has_padding_bits: false,
+                has_checksum: false,
+                has_filename: false,
+                ..self.zeck_flags()
+            }
+            .to_byte(),
+            extended_flags: 0,
+            maybe_padding_bits: None,
+            maybe_checksum: None,
+            maybe_filename: None,
+            maybe_rotation: None,
+            maybe_payload_crc: None,
+            maybe_mtime: None,
+            maybe_element_width: None,
+            compressed_data,
+        })
+    }
+
+    /// Unwraps the only block of a version 3 archive as the compressed data of a version 2 file.
+    fn unwrap_single_block_archive(&self) -> Result<ZeckFile, ZeckFormatError> {
+        let archive =
+            ZeckArchive::from_parts(self.original_size, self.flags, &self.compressed_data)?;
+        match archive.entries() {
+            [] => Ok(ZeckFile::new_with_flags(
+                0,
+                compress_with_flags(&[], self.flags),
+                self.flags,
+            )),
+            [entry] => {
+                let payload = archive
+                    .block_payload(0)
+                    .expect("The archive has exactly one block");
+                let mut zeck_file =
+                    ZeckFile::new_with_flags(entry.original_len, payload.to_vec(), self.flags);
+                zeck_file.maybe_checksum = Some(entry.crc32);
+                zeck_file.update_flags(|flags| flags.has_checksum = true);
+                Ok(zeck_file)
+            }
+            _ => Err(ZeckFormatError::LossyVersionConversion {
+                from_version: self.version,
+                to_version: ZECK_FORMAT_VERSION,
+            }),
+        }
     }
 
     /// Serializes the ZeckFile to a byte vector in .zeck file format.
@@ -54,6 +604,10 @@ impl ZeckFile {
     /// This creates a complete .zeck file with header followed by compressed data,
     /// suitable for writing to disk or transmitting over a network.
     ///
+    /// # Panics
+    ///
+    /// Panics if the filename is longer than [`u32::MAX`] bytes.
+    ///
     /// # Examples
     ///
     /// ```
@@ -64,7 +618,7 @@ impl ZeckFile {
     /// // bytes can now be written to a file
     /// ```
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut output = Vec::with_capacity(ZECK_HEADER_SIZE + self.compressed_data.len());
+        let mut output = Vec::with_capacity(self.total_size());
 
         // Version (1 byte)
         output.push(self.version);
@@ -72,8 +626,57 @@ impl ZeckFile {
         // Original size (8 bytes, little endian)
         output.extend_from_slice(&self.original_size.to_le_bytes());
 
-        // Flags (1 byte)
-        output.push(self.flags);
+        // Flags (1 byte), with the optional field flags matching which optional fields are written
+        let extended_flags = self.extended_flags_to_write();
+        output.push(self.flags_to_write());
+
+        // Extended flags (1 byte)
+        if extended_flags != 0 {
+            output.push(extended_flags);
+        }
+
+        // Padding bits (1 byte)
+        if let Some(padding_bits) = self.maybe_padding_bits {
+            output.push(padding_bits);
+        }
+
+        // Checksum (4 bytes, little endian)
+        if let Some(checksum) = self.maybe_checksum {
+            output.extend_from_slice(&checksum.to_le_bytes());
+        }
+
+        // Filename length (4 bytes, little endian) and UTF-8 bytes
+        if let Some(filename) = &self.maybe_filename {
+            let filename_length =
+                u32::try_from(filename.len()).expect("Filename must be at most u32::MAX bytes");
+            output.extend_from_slice(&filename_length.to_le_bytes());
+            output.extend_from_slice(filename.as_bytes());
+        }
+
+        // Rotation offset (8 bytes, little endian)
+        if let Some(rotation) = self.maybe_rotation {
+            output.extend_from_slice(&rotation.to_le_bytes());
+        }
+
+        // Payload CRC-32 (4 bytes, little endian)
+        if let Some(payload_crc) = self.maybe_payload_crc {
+            output.extend_from_slice(&payload_crc.to_le_bytes());
+        }
+
+        // Modification time (8 bytes, little endian)
+        if let Some(mtime) = self.maybe_mtime {
+            output.extend_from_slice(&mtime.to_le_bytes());
+        }
+
+        // Element width (1 byte)
+        if let Some(element_width) = self.maybe_element_width {
+            output.push(element_width);
+        }
+
+        // Payload length (8 bytes, little endian)
+        if self.version == ZECK_FORMAT_VERSION {
+            output.extend_from_slice(&(self.compressed_data.len() as u64).to_le_bytes());
+        }
 
         // Compressed data
         output.extend_from_slice(&self.compressed_data);
@@ -81,9 +684,191 @@ impl ZeckFile {
         output
     }
 
-    /// Returns the total size of the serialized file (header + compressed data).
+    /// Returns the total size of the serialized file (header + optional header fields that are recorded + payload length
+    /// of a version 2 file + compressed data).
     pub fn total_size(&self) -> usize {
-        ZECK_HEADER_SIZE + self.compressed_data.len()
+        let extended_flags_size = if self.extended_flags_to_write() != 0 {
+            ZECK_EXTENDED_FLAGS_SIZE
+        } else {
+            0
+        };
+        let padding_bits_size = if self.maybe_padding_bits.is_some() {
+            ZECK_PADDING_BITS_SIZE
+        } else {
+            0
+        };
+        let checksum_size = if self.maybe_checksum.is_some() {
+            ZECK_CHECKSUM_SIZE
+        } else {
+            0
+        };
+        let filename_size = self
+            .maybe_filename
+            .as_ref()
+            .map_or(0, |filename| ZECK_FILENAME_LENGTH_SIZE + filename.len());
+        let rotation_size = if self.maybe_rotation.is_some() {
+            ZECK_ROTATION_SIZE
+        } else {
+            0
+        };
+        let payload_crc_size = if self.maybe_payload_crc.is_some() {
+            ZECK_PAYLOAD_CRC_SIZE
+        } else {
+            0
+        };
+        let mtime_size = if self.maybe_mtime.is_some() {
+            ZECK_MTIME_SIZE
+        } else {
+            0
+        };
+        let element_width_size = if self.maybe_element_width.is_some() {
+            ZECK_ELEMENT_WIDTH_SIZE
+        } else {
+            0
+        };
+        let payload_length_size = if self.version == ZECK_FORMAT_VERSION {
+            ZECK_PAYLOAD_LENGTH_SIZE
+        } else {
+            0
+        };
+        ZECK_HEADER_SIZE
+            + extended_flags_size
+            + padding_bits_size
+            + checksum_size
+            + filename_size
+            + rotation_size
+            + payload_crc_size
+            + mtime_size
+            + element_width_size
+            + payload_length_size
+            + self.compressed_data.len()
+    }
+
+    /// Returns the flags byte with the optional field flags matching which optional fields are written.
+    fn flags_to_write(&self) -> u8 {
+        ZeckFlags {
+            has_padding_bits: self.maybe_padding_bits.is_some(),
+            has_checksum: self.maybe_checksum.is_some(),
+            has_filename: self.maybe_filename.is_some(),
+            has_extended_flags: self.extended_flags_to_write() != 0,
+            ..self.zeck_flags()
+        }
+        .to_byte()
+    }
+
+    /// Returns the fields [`ZeckFile::to_bytes`] writes, which [`Eq`] and [`Hash`] compare.
+    fn written_fields(&self) -> WrittenFields<'_> {
+        WrittenFields {
+            version: self.version,
+            original_size: self.original_size,
+            flags: self.flags_to_write(),
+            extended_flags: self.extended_flags_to_write(),
+            maybe_padding_bits: self.maybe_padding_bits,
+            maybe_checksum: self.maybe_checksum,
+            maybe_filename: self.maybe_filename.as_deref(),
+            maybe_rotation: self.maybe_rotation,
+            maybe_payload_crc: self.maybe_payload_crc,
+            maybe_mtime: self.maybe_mtime,
+            maybe_element_width: self.maybe_element_width,
+            compressed_data: &self.compressed_data,
+        }
+    }
+
+    /// Returns the extended flags byte with the optional field flags matching which optional fields are written.
+    fn extended_flags_to_write(&self) -> u8 {
+        let mut extended_flags = self.extended_flags
+            & !(ZECK_EXTENDED_FLAG_ROTATION
+                | ZECK_EXTENDED_FLAG_PAYLOAD_CRC
+                | ZECK_EXTENDED_FLAG_MTIME
+                | ZECK_EXTENDED_FLAG_ELEMENT_WIDTH);
+        if self.maybe_rotation.is_some() {
+            extended_flags |= ZECK_EXTENDED_FLAG_ROTATION;
+        }
+        if self.maybe_payload_crc.is_some() {
+            extended_flags |= ZECK_EXTENDED_FLAG_PAYLOAD_CRC;
+        }
+        if self.maybe_mtime.is_some() {
+            extended_flags |= ZECK_EXTENDED_FLAG_MTIME;
+        }
+        if self.maybe_element_width.is_some() {
+            extended_flags |= ZECK_EXTENDED_FLAG_ELEMENT_WIDTH;
+        }
+        extended_flags
+    }
+
+    /// Returns the ratio of the compressed data size to the original size, where values below 1.0 mean the data
+    /// got smaller. The header is not counted; see [`Self::total_size`] for the full serialized size.
+    ///
+    /// Returns 1.0 if the original size is 0, since there was nothing to compress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::ZeckFile;
+    /// let zeck_file = ZeckFile::builder().original_size(100).compressed_data(vec![1; 60]).build();
+    /// assert_eq!(zeck_file.compression_ratio(), 0.6);
+    /// assert_eq!(ZeckFile::builder().compressed_data(vec![0]).build().compression_ratio(), 1.0);
+    /// ```
+    pub fn compression_ratio(&self) -> f64 {
+        if self.original_size == 0 {
+            return 1.0;
+        }
+        self.compressed_data.len() as f64 / self.original_size as f64
+    }
+
+    /// Returns the fraction of the original size saved by compression, `1.0 - compression_ratio()`.
+    /// Negative values mean the data got larger.
+    ///
+    /// Returns 0.0 if the original size is 0, since there was nothing to compress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::ZeckFile;
+    /// let zeck_file = ZeckFile::builder().original_size(100).compressed_data(vec![1; 60]).build();
+    /// assert!((zeck_file.space_saving() - 0.4).abs() < 1e-12);
+    /// assert_eq!(ZeckFile::builder().build().space_saving(), 0.0);
+    /// ```
+    pub fn space_saving(&self) -> f64 {
+        1.0 - self.compression_ratio()
+    }
+
+    /// Returns whether two files decode to the same data in the same way, ignoring the format version.
+    ///
+    /// This compares the original size, the representation flags (endianness, lazy representation, inverted polarity,
+    /// and entropy coding), the codec (EZBA bits, sparse index list, Lucas representation, or uncompressed fallback), the
+    /// pre-transforms (delta encoding and rotation), and the compressed data. The version and the optional header fields (padding bits,
+    /// checksum, filename, payload CRC, modification time, and element width) are ignored, which makes it useful for checking that a format upgrade kept the payload
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::compress_zeck_be;
+    /// let zeck_file = compress_zeck_be(&[12, 34]).unwrap();
+    /// let mut upgraded = zeck_file.clone();
+    /// upgraded.version += 1;
+    /// upgraded.maybe_filename = Some("data.bin".to_string());
+    /// assert!(zeck_file.semantically_eq(&upgraded));
+    /// assert_ne!(zeck_file, upgraded);
+    /// ```
+    pub fn semantically_eq(&self, other: &ZeckFile) -> bool {
+        self.original_size == other.original_size
+            && self.zeck_flags().representation() == other.zeck_flags().representation()
+            && self.decoding_extended_flags() == other.decoding_extended_flags()
+            && self.rotation() == other.rotation()
+            && self.compressed_data == other.compressed_data
+    }
+
+    /// Returns only the extended flags that change how the compressed data decodes (the codec and delta encoding), with
+    /// the flags that record optional header fields cleared. A rotation of 0 decodes like no rotation, so
+    /// [`ZeckFile::semantically_eq`] compares the offset instead of its flag.
+    fn decoding_extended_flags(&self) -> u8 {
+        self.extended_flags
+            & !(ZECK_EXTENDED_FLAG_ROTATION
+                | ZECK_EXTENDED_FLAG_PAYLOAD_CRC
+                | ZECK_EXTENDED_FLAG_MTIME
+                | ZECK_EXTENDED_FLAG_ELEMENT_WIDTH)
     }
 }
 
@@ -95,16 +880,92 @@ pub fn zeck_file_is_big_endian(zeck_file: &ZeckFile) -> bool {
     zeck_file.is_big_endian()
 }
 
+#[wasm_bindgen]
+pub fn zeck_file_is_lazy(zeck_file: &ZeckFile) -> bool {
+    zeck_file.is_lazy()
+}
+
+#[wasm_bindgen]
+pub fn zeck_file_is_inverted(zeck_file: &ZeckFile) -> bool {
+    zeck_file.is_inverted()
+}
+
+#[wasm_bindgen]
+pub fn zeck_file_is_entropy_coded(zeck_file: &ZeckFile) -> bool {
+    zeck_file.is_entropy_coded()
+}
+
+#[wasm_bindgen]
+pub fn zeck_file_is_delta_encoded(zeck_file: &ZeckFile) -> bool {
+    zeck_file.is_delta_encoded()
+}
+
 #[wasm_bindgen]
 pub fn zeck_file_to_bytes(zeck_file: &ZeckFile) -> Vec<u8> {
     zeck_file.to_bytes()
 }
 
+#[wasm_bindgen]
+pub fn zeck_file_decompressed_size(zeck_file: &ZeckFile) -> u64 {
+    zeck_file.decompressed_size()
+}
+
+#[wasm_bindgen]
+pub fn zeck_file_validate(zeck_file: &ZeckFile) -> Result<(), ZeckFormatError> {
+    zeck_file.validate()
+}
+
 #[wasm_bindgen]
 pub fn zeck_file_total_size(zeck_file: &ZeckFile) -> usize {
     zeck_file.total_size()
 }
 
+#[wasm_bindgen]
+pub fn zeck_file_compression_ratio(zeck_file: &ZeckFile) -> f64 {
+    zeck_file.compression_ratio()
+}
+
+#[wasm_bindgen]
+pub fn zeck_file_space_saving(zeck_file: &ZeckFile) -> f64 {
+    zeck_file.space_saving()
+}
+
+#[wasm_bindgen]
+pub fn zeck_file_semantically_eq(zeck_file: &ZeckFile, other: &ZeckFile) -> bool {
+    zeck_file.semantically_eq(other)
+}
+
+/// The fields of a [`ZeckFile`] as [`ZeckFile::to_bytes`] writes them, with the flags bytes matching the optional fields.
+#[derive(PartialEq, Eq, Hash)]
+struct WrittenFields<'a> {
+    version: u8,
+    original_size: u64,
+    flags: u8,
+    extended_flags: u8,
+    maybe_padding_bits: Option<u8>,
+    maybe_checksum: Option<u32>,
+    maybe_filename: Option<&'a str>,
+    maybe_rotation: Option<u64>,
+    maybe_payload_crc: Option<u32>,
+    maybe_mtime: Option<u64>,
+    maybe_element_width: Option<u8>,
+    compressed_data: &'a [u8],
+}
+
+impl PartialEq for ZeckFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.written_fields() == other.written_fields()
+    }
+}
+
+impl Eq for ZeckFile {}
+
+impl std::hash::Hash for ZeckFile {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.written_fields().hash(state);
+    }
+}
+
 impl std::fmt::Display for ZeckFile {
     /// Formats the ZeckFile for display, showing key information.
     ///
@@ -132,10 +993,30 @@ impl std::fmt::Display for ZeckFile {
     }
 }
 
+impl TryFrom<&[u8]> for ZeckFile {
+    type Error = ZeckFormatError;
+
+    /// Parses a [`ZeckFile`] from raw .zeck file bytes. Delegates to [`deserialize_zeck_file`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zeck::zeck_file_format::{compress::compress_zeck_be, decompress::decompress_zeck_file, ZeckFile};
+    /// let original = vec![0, 1];
+    /// let bytes = compress_zeck_be(&original).unwrap().to_bytes();
+    /// let zeck_file = ZeckFile::try_from(bytes.as_slice()).unwrap();
+    /// assert_eq!(decompress_zeck_file(&zeck_file).unwrap(), original);
+    /// ```
+    fn try_from(zeck_file_data: &[u8]) -> Result<Self, Self::Error> {
+        deserialize_zeck_file(zeck_file_data)
+    }
+}
+
 /// Deserializes a .zeck file from raw bytes into a [`ZeckFile`] struct.
 ///
 /// This function reads the header to determine the file format version, original size, and endianness,
-/// and constructs a [`ZeckFile`] struct. To decompress the data, call [`crate::zeck_file_format::decompress::decompress_zeck_file`] on the result.
+/// and constructs a [`ZeckFile`] struct. A single-file .zeck file whose compressed data is longer than any compression
+/// of its original size could be fails with [`ZeckFormatError::PayloadTooLarge`] before the data is copied. To decompress the data, call [`crate::zeck_file_format::decompress::decompress_zeck_file`] on the result.
 ///
 /// # Examples
 ///
@@ -157,36 +1038,892 @@ impl std::fmt::Display for ZeckFile {
 /// ```
 #[wasm_bindgen]
 pub fn deserialize_zeck_file(zeck_file_data: &[u8]) -> Result<ZeckFile, ZeckFormatError> {
-    // Check header size
-    if zeck_file_data.len() < ZECK_HEADER_SIZE {
+    let view = ZeckFileView::parse(zeck_file_data)?;
+
+    // Reject a payload no compression of the original size could produce before copying it
+    if matches!(
+        view.version,
+        ZECK_LEGACY_FORMAT_VERSION | ZECK_FORMAT_VERSION
+    ) {
+        check_payload_length(&view)?;
+    }
+
+    Ok(view.to_zeck_file())
+}
+
+/// Returns the number of padding bits in the last byte of packed EZBA (or lazy EZBA) bytes.
+///
+/// Both encodings end with a [`crate::USE_BIT`], so the bit length ends at the highest set bit of the last byte.
+/// The only exception is zero, whose greedy EZBA is a single [`crate::SKIP_BIT`] packed as `[0]`.
+pub(crate) fn ezba_padding_bits(compressed_data: &[u8]) -> u8 {
+    match compressed_data.last() {
+        Some(&last_byte) if last_byte != 0 => last_byte.leading_zeros() as u8,
+        _ => 7,
+    }
+}
+
+/// Reads the optional header fields, payload length, and compressed data of a version 2 file that follow its
+/// `zeck_file_data` header, returning the bytes of the whole file.
+///
+/// Bytes are read only as far as parsing asks for them, so the reader is left at the end of the file. A corrupt payload
+/// length cannot force a huge allocation up front, since the payload is read incrementally.
+fn read_single_file_data<R: Read>(
+    reader: &mut R,
+    mut zeck_file_data: Vec<u8>,
+) -> Result<Vec<u8>, ZeckFormatError> {
+    let mut is_at_end = false;
+    loop {
+        // Once the reader is exhausted, parsing reports how much is missing
+        let wanted_length = match ZeckFileView::parse_prefix(&zeck_file_data) {
+            Ok(_) => return Ok(zeck_file_data),
+            Err(ZeckFormatError::HeaderTooShort {
+                required_length, ..
+            }) if !is_at_end => required_length,
+            Err(ZeckFormatError::Truncated { expected, actual }) if !is_at_end => {
+                (zeck_file_data.len() - actual).saturating_add(expected)
+            }
+            Err(error) => return Err(error),
+        };
+        let missing_length = (wanted_length - zeck_file_data.len()) as u64;
+        let read_length = reader
+            .take(missing_length)
+            .read_to_end(&mut zeck_file_data)?;
+        is_at_end = (read_length as u64) < missing_length;
+    }
+}
+
+/// Reads the block count, block index, and block payloads of a multi-block archive, which together make up
+/// its compressed data. The payloads end where the furthest block ends, and a stream that ends before then fails with
+/// [`ZeckFormatError::Truncated`].
+fn read_archive_compressed_data<R: Read>(reader: &mut R) -> Result<Vec<u8>, ZeckFormatError> {
+    let mut compressed_data = vec![0u8; ZECK_ARCHIVE_BLOCK_COUNT_SIZE];
+    let block_count_length = read_up_to(reader, &mut compressed_data)?;
+    if block_count_length < ZECK_ARCHIVE_BLOCK_COUNT_SIZE {
+        return Err(ZeckFormatError::HeaderTooShort {
+            actual_length: ZECK_HEADER_SIZE + block_count_length,
+            required_length: ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE,
+        });
+    }
+    let block_count = u32::from_le_bytes(
+        compressed_data[..]
+            .try_into()
+            .expect("Block count is exactly 4 bytes"),
+    ) as usize;
+
+    // Read the index incrementally so a corrupt block count cannot force a huge allocation up front
+    let mut index = Vec::new();
+    let index_size = block_count.saturating_mul(ZECK_ARCHIVE_INDEX_ENTRY_SIZE);
+    let index_length = reader.take(index_size as u64).read_to_end(&mut index)?;
+    if index_length < index_size {
         return Err(ZeckFormatError::HeaderTooShort {
-            actual_length: zeck_file_data.len(),
-            required_length: ZECK_HEADER_SIZE,
+            actual_length: ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE + index_length,
+            required_length: ZECK_HEADER_SIZE + ZECK_ARCHIVE_BLOCK_COUNT_SIZE + index_size,
+        });
+    }
+
+    let mut payloads_length = 0u64;
+    for (block_index, entry) in index
+        .chunks_exact(ZECK_ARCHIVE_INDEX_ENTRY_SIZE)
+        .enumerate()
+    {
+        let offset = u64::from_le_bytes(entry[0..8].try_into().expect("Offset is exactly 8 bytes"));
+        let compressed_len = u64::from_le_bytes(
+            entry[8..16]
+                .try_into()
+                .expect("Compressed length is exactly 8 bytes"),
+        );
+        let end = offset
+            .checked_add(compressed_len)
+            .ok_or(ZeckFormatError::BlockOutOfBounds { block_index })?;
+        payloads_length = payloads_length.max(end);
+    }
+    compressed_data.extend(index);
+
+    let actual_payloads_length = reader
+        .take(payloads_length)
+        .read_to_end(&mut compressed_data)?;
+    if (actual_payloads_length as u64) < payloads_length {
+        return Err(ZeckFormatError::Truncated {
+            expected: usize::try_from(payloads_length).unwrap_or(usize::MAX),
+            actual: actual_payloads_length,
         });
     }
 
-    // Parse header
-    let version = zeck_file_data[0];
-    let original_size = u64::from_le_bytes([
-        zeck_file_data[1],
-        zeck_file_data[2],
-        zeck_file_data[3],
-        zeck_file_data[4],
-        zeck_file_data[5],
-        zeck_file_data[6],
-        zeck_file_data[7],
-        zeck_file_data[8],
-    ]);
-    let flags = zeck_file_data[9];
-
-    // Extract compressed data (everything after the header)
-    let compressed_data = zeck_file_data[ZECK_HEADER_SIZE..].to_vec();
-
-    // Construct and return ZeckFile
-    Ok(ZeckFile {
-        version,
-        original_size,
-        flags,
-        compressed_data,
-    })
+    Ok(compressed_data)
+}
+
+/// Fills as much of `buf` as the reader provides, stopping early only at end of file, and returns the number of bytes read.
+fn read_up_to<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zeck_file_format::archive::{
+        compress_records, compress_zeck_archive, decompress_records,
+    };
+    use crate::zeck_file_format::compress::{
+        BestCompressionResult, compress_zeck_best, compress_zeck_le,
+    };
+    use crate::zeck_file_format::decompress::max_compressed_len_for_original_size;
+    use crate::zeck_file_format::sparse::encode_sparse_index_list;
+    use crate::zeck_file_format::{
+        ZECK_FLAG_BIG_ENDIAN, ZECK_FLAG_CHECKSUM, ZECK_FLAG_ENTROPY_CODED, ZECK_FLAG_LAZY,
+        ZECK_FLAG_PADDING_BITS,
+    };
+
+    #[test]
+    fn hash_set_dedupes_equal_files() {
+        // Arrange
+        let file = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap();
+        let renamed = ZeckFile {
+            maybe_filename: Some("pi.bin".to_string()),
+            ..file.clone()
+        };
+        let different_data = compress_zeck_le(&[2, 7, 1, 8]).unwrap();
+
+        // Act
+        let files: std::collections::HashSet<ZeckFile> =
+            [file.clone(), renamed, file.clone(), different_data]
+                .into_iter()
+                .collect();
+
+        // Assert
+        assert_eq!(files.len(), 3);
+        assert!(files.contains(&file));
+    }
+
+    #[test]
+    fn eq_and_hash_follow_the_optional_fields_not_the_raw_flag_bits() {
+        // Arrange
+        let file = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap();
+        let stale_flags = ZeckFile {
+            flags: file.flags | ZECK_FLAG_CHECKSUM,
+            extended_flags: ZECK_EXTENDED_FLAG_MTIME,
+            ..file.clone()
+        };
+        let with_checksum = ZeckFile {
+            maybe_checksum: Some(0),
+            ..file.clone()
+        };
+
+        // Act
+        let files: std::collections::HashSet<ZeckFile> =
+            [file.clone(), stale_flags.clone(), with_checksum.clone()]
+                .into_iter()
+                .collect();
+
+        // Assert
+        assert_eq!(stale_flags.to_bytes(), file.to_bytes());
+        assert_eq!(stale_flags, file);
+        assert_ne!(with_checksum, file);
+        assert_eq!(files.len(), 2);
+    }
+
+    #[test]
+    fn semantically_eq_ignores_version_but_not_representation() {
+        // Arrange
+        let single_file = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap();
+        let archive = ZeckFile {
+            version: ZECK_ARCHIVE_FORMAT_VERSION,
+            maybe_checksum: Some(0xDEAD_BEEF),
+            ..single_file.clone()
+        };
+        let big_endian = ZeckFile {
+            flags: single_file.flags | ZECK_FLAG_BIG_ENDIAN,
+            ..single_file.clone()
+        };
+        let lucas = ZeckFile {
+            extended_flags: single_file.extended_flags | ZECK_EXTENDED_FLAG_LUCAS,
+            ..single_file.clone()
+        };
+        let sparse = ZeckFile {
+            extended_flags: single_file.extended_flags | ZECK_EXTENDED_FLAG_SPARSE,
+            ..single_file.clone()
+        };
+        let with_mtime = ZeckFile {
+            extended_flags: single_file.extended_flags | ZECK_EXTENDED_FLAG_MTIME,
+            maybe_mtime: Some(1_700_000_000),
+            ..single_file.clone()
+        };
+
+        // Act
+        let single_file_matches_archive = single_file.semantically_eq(&archive);
+        let single_file_matches_big_endian = single_file.semantically_eq(&big_endian);
+        let single_file_matches_lucas = single_file.semantically_eq(&lucas);
+        let single_file_matches_sparse = single_file.semantically_eq(&sparse);
+        let single_file_matches_with_mtime = single_file.semantically_eq(&with_mtime);
+
+        // Assert
+        assert_eq!(single_file.version, ZECK_FORMAT_VERSION);
+        assert!(single_file_matches_archive);
+        assert!(archive.semantically_eq(&single_file));
+        assert!(!single_file_matches_big_endian);
+        assert!(!single_file_matches_lucas);
+        assert!(!single_file_matches_sparse);
+        assert!(single_file_matches_with_mtime);
+    }
+
+    #[test]
+    fn computed_checksum_is_serialized_and_verified() {
+        // Arrange
+        let original = vec![3, 1, 4, 1, 5];
+        let mut zeck_file = compress_zeck_le(&original).unwrap();
+        let size_without_checksum = zeck_file.total_size();
+
+        // Act
+        zeck_file.compute_checksum(&original);
+        let deserialized = deserialize_zeck_file(&zeck_file.to_bytes()).unwrap();
+        let mut corrupted = deserialized.clone();
+        corrupted.maybe_checksum = Some(crc32(&[3, 1, 4, 1, 6]));
+
+        // Assert
+        assert_eq!(
+            zeck_file.total_size(),
+            size_without_checksum + ZECK_CHECKSUM_SIZE
+        );
+        assert_eq!(deserialized, zeck_file);
+        assert_eq!(decompress_zeck_file(&deserialized), Ok(original));
+        assert!(matches!(
+            decompress_zeck_file(&corrupted),
+            Err(ZeckFormatError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn file_without_checksum_omits_it_and_decompresses() {
+        // Arrange
+        let original = vec![3, 1, 4, 1, 5];
+        let zeck_file = compress_zeck_le(&original).unwrap();
+
+        // Act
+        let bytes = zeck_file.to_bytes();
+        let deserialized = deserialize_zeck_file(&bytes).unwrap();
+
+        // Assert
+        assert_eq!(bytes[ZECK_HEADER_SIZE - 1] & ZECK_FLAG_CHECKSUM, 0);
+        assert_eq!(deserialized.maybe_checksum, None);
+        assert_eq!(decompress_zeck_file(&deserialized), Ok(original));
+    }
+
+    #[test]
+    fn upgraded_single_file_decompresses_to_original_and_downgrades_back() {
+        // Arrange
+        let original = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        let single_file = compress_zeck_le(&original).unwrap();
+
+        // Act
+        let archive = single_file.upgrade_to(ZECK_ARCHIVE_FORMAT_VERSION).unwrap();
+        let archive_bytes = archive.to_bytes();
+        let downgraded = deserialize_zeck_file(&archive_bytes)
+            .unwrap()
+            .downgrade_to(ZECK_FORMAT_VERSION)
+            .unwrap();
+
+        // Assert
+        assert_eq!(archive.version, ZECK_ARCHIVE_FORMAT_VERSION);
+        assert!(archive_bytes.ends_with(&single_file.compressed_data));
+        assert_eq!(decompress_zeck_file(&archive), Ok(original.clone()));
+        assert_eq!(downgraded.maybe_checksum, Some(crc32(&original)));
+        assert!(downgraded.semantically_eq(&single_file));
+        assert_eq!(decompress_zeck_file(&downgraded), Ok(original));
+    }
+
+    #[test]
+    fn lossy_version_conversions_are_rejected() {
+        // Arrange
+        let mut named_single_file = compress_zeck_le(&[1, 2, 3]).unwrap();
+        named_single_file.maybe_filename = Some("named.bin".to_string());
+        let multi_block_archive = compress_zeck_archive(&[1, 2, 3, 4], 2, false).unwrap();
+
+        // Act
+        let upgraded = named_single_file.upgrade_to(ZECK_ARCHIVE_FORMAT_VERSION);
+        let downgraded = multi_block_archive.downgrade_to(ZECK_FORMAT_VERSION);
+        let wrong_direction = multi_block_archive.upgrade_to(ZECK_FORMAT_VERSION);
+
+        // Assert
+        assert_eq!(
+            upgraded,
+            Err(ZeckFormatError::LossyVersionConversion {
+                from_version: ZECK_FORMAT_VERSION,
+                to_version: ZECK_ARCHIVE_FORMAT_VERSION,
+            })
+        );
+        assert_eq!(
+            downgraded,
+            Err(ZeckFormatError::LossyVersionConversion {
+                from_version: ZECK_ARCHIVE_FORMAT_VERSION,
+                to_version: ZECK_FORMAT_VERSION,
+            })
+        );
+        assert!(matches!(
+            wrong_direction,
+            Err(ZeckFormatError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn empty_archive_downgrades_to_empty_single_file() {
+        // Arrange
+        let archive = compress_zeck_archive(&[], 8, true).unwrap();
+
+        // Act
+        let single_file = archive.downgrade_to(ZECK_FORMAT_VERSION).unwrap();
+
+        // Assert
+        assert_eq!(single_file.original_size, 0);
+        assert_eq!(decompress_zeck_file(&single_file), Ok(vec![]));
+    }
+
+    #[test]
+    fn to_bytes_round_trips_each_endianness_and_payload() {
+        for endianness in [Endianness::Big, Endianness::Little] {
+            for compressed_data in [vec![], vec![1], vec![0b1010_0101, 3]] {
+                for original_size in [0, 2, u64::MAX - 1, u64::MAX] {
+                    // Arrange
+                    let zeck_file = ZeckFile::builder()
+                        .original_size(original_size)
+                        .compressed_data(compressed_data.clone())
+                        .big_endian(endianness == Endianness::Big)
+                        .build();
+
+                    // Act
+                    let deserialized = deserialize_zeck_file(&zeck_file.to_bytes());
+
+                    // Assert
+                    assert_eq!(deserialized, Ok(zeck_file));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn to_bytes_round_trips_every_optional_field() {
+        // Arrange
+        let mut zeck_file = ZeckFile::builder()
+            .original_size(u64::MAX)
+            .compressed_data(vec![0xAB, 0x01])
+            .big_endian(true)
+            .delta_encoded(true)
+            .checksum(Some(0xDEAD_BEEF))
+            .filename(Some("round trip.bin".to_string()))
+            .mtime(Some(u64::MAX - 2))
+            .build();
+        zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_ROTATION);
+        zeck_file.maybe_rotation = Some(u64::MAX - 1);
+        zeck_file.compute_payload_crc();
+
+        // Act
+        let bytes = zeck_file.to_bytes();
+        let deserialized = deserialize_zeck_file(&bytes);
+
+        // Assert
+        assert_eq!(bytes.len(), zeck_file.total_size());
+        assert_eq!(deserialized, Ok(zeck_file));
+    }
+
+    #[test]
+    fn mtime_round_trips_when_present_and_absent() {
+        for maybe_mtime in [None, Some(0), Some(1_700_000_000)] {
+            // Arrange
+            let zeck_file = ZeckFile::builder()
+                .original_size(2)
+                .compressed_data(vec![0b1010_0101])
+                .mtime(maybe_mtime)
+                .build();
+
+            // Act
+            let bytes = zeck_file.to_bytes();
+            let deserialized = deserialize_zeck_file(&bytes).unwrap();
+
+            // Assert
+            assert_eq!(deserialized.maybe_mtime, maybe_mtime);
+            assert_eq!(
+                deserialized.extended_flags & ZECK_EXTENDED_FLAG_MTIME != 0,
+                maybe_mtime.is_some()
+            );
+            assert_eq!(bytes.len(), zeck_file.total_size());
+            assert_eq!(
+                decompress_zeck_file(&deserialized),
+                decompress_zeck_file(&zeck_file)
+            );
+        }
+    }
+
+    #[test]
+    fn decompressed_size_matches_decompressed_data_for_every_variant() {
+        // Arrange
+        let data: Vec<u8> = (0..100).collect();
+        let records: [&[u8]; 4] = [b"first", b"", b"third", &[0, 0]];
+        let mut lying_archive = compress_zeck_archive(&data, 32, true).unwrap();
+        lying_archive.original_size += 1;
+        let variants = [
+            ("plain", compress_zeck_le(&[0, 1, 2, 0]).unwrap()),
+            ("empty", compress_zeck_le(&[]).unwrap()),
+            ("block", compress_zeck_archive(&data, 32, false).unwrap()),
+            ("record", compress_records(&records, false).unwrap()),
+            ("lying header", lying_archive),
+        ];
+
+        for (name, zeck_file) in variants {
+            // Act
+            let decompressed_size = zeck_file.decompressed_size();
+
+            // Assert
+            let decompressed_len = if zeck_file.version == ZECK_RECORDS_FORMAT_VERSION {
+                decompress_records(&zeck_file).unwrap().concat().len()
+            } else {
+                decompress_zeck_file(&zeck_file).unwrap().len()
+            };
+            assert_eq!(decompressed_size, decompressed_len as u64, "{name}");
+        }
+    }
+
+    #[test]
+    fn validate_accepts_compressed_files() {
+        // Arrange
+        let data: Vec<u8> = (0..100).collect();
+        let records: [&[u8]; 3] = [b"first", b"", b"third"];
+        let mut rotated = compress_zeck_le(&data).unwrap();
+        rotated.maybe_rotation = Some(99);
+        rotated.compute_payload_crc();
+        let variants = [
+            ("plain", compress_zeck_le(&[0, 1, 2, 0]).unwrap()),
+            ("empty", compress_zeck_le(&[]).unwrap()),
+            ("rotated with payload crc", rotated),
+            ("block", compress_zeck_archive(&data, 32, true).unwrap()),
+            ("record", compress_records(&records, false).unwrap()),
+        ];
+
+        for (name, zeck_file) in variants {
+            // Act
+            let result = zeck_file.validate();
+
+            // Assert
+            assert_eq!(result, Ok(()), "{name}");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_each_structural_failure_before_decompressing() {
+        // Arrange
+        let data = [3, 1, 4, 1, 5];
+        let file = compress_zeck_le(&data).unwrap();
+        let padding_bits = file.maybe_padding_bits.unwrap();
+        let max_length = max_compressed_len_for_original_size(
+            file.original_size,
+            file.flags,
+            file.extended_flags,
+        );
+        let archive = compress_zeck_archive(&[7; 100], 32, false).unwrap();
+        let mut corrupted = file.clone();
+        corrupted.compute_payload_crc();
+        corrupted.compressed_data[0] ^= 1;
+        let cases = [
+            (
+                "unsupported version",
+                ZeckFile {
+                    version: ZECK_RECORDS_FORMAT_VERSION + 1,
+                    ..file.clone()
+                },
+                ZeckFormatError::UnsupportedVersion {
+                    found_version: ZECK_RECORDS_FORMAT_VERSION + 1,
+                    supported_version: ZECK_RECORDS_FORMAT_VERSION,
+                },
+            ),
+            (
+                "archive with a checksum flag",
+                ZeckFile {
+                    flags: archive.flags | ZECK_FLAG_CHECKSUM,
+                    ..archive.clone()
+                },
+                ZeckFormatError::ReservedFlagsSet {
+                    flags: archive.flags | ZECK_FLAG_CHECKSUM,
+                },
+            ),
+            (
+                "archive blocks larger than the original size",
+                ZeckFile {
+                    original_size: 0,
+                    ..archive.clone()
+                },
+                ZeckFormatError::InvalidBlockSize {
+                    block_index: 0,
+                    value: 32,
+                },
+            ),
+            (
+                "element width not dividing the original size",
+                ZeckFile {
+                    maybe_element_width: Some(data.len() as u8 + 1),
+                    ..file.clone()
+                },
+                ZeckFormatError::ElementSizeMismatch {
+                    original_size: data.len() as u64,
+                    element_size: data.len() + 1,
+                },
+            ),
+            (
+                "zero element width",
+                ZeckFile {
+                    maybe_element_width: Some(0),
+                    ..file.clone()
+                },
+                ZeckFormatError::ElementSizeMismatch {
+                    original_size: data.len() as u64,
+                    element_size: 0,
+                },
+            ),
+            (
+                "uncompressed fallback shorter than the original size",
+                ZeckFile {
+                    extended_flags: ZECK_EXTENDED_FLAG_UNCOMPRESSED,
+                    maybe_padding_bits: None,
+                    compressed_data: data[..4].to_vec(),
+                    ..file.clone()
+                },
+                ZeckFormatError::InvalidUncompressedFallback,
+            ),
+            (
+                "entropy coded Lucas codec",
+                ZeckFile {
+                    flags: file.flags | ZECK_FLAG_ENTROPY_CODED,
+                    extended_flags: ZECK_EXTENDED_FLAG_LUCAS,
+                    ..file.clone()
+                },
+                ZeckFormatError::InvalidLucasFlags,
+            ),
+            (
+                "sparse index list with padding bits",
+                ZeckFile {
+                    extended_flags: ZECK_EXTENDED_FLAG_SPARSE,
+                    ..file.clone()
+                },
+                ZeckFormatError::InvalidSparseIndexList,
+            ),
+            (
+                "rotation not smaller than the original size",
+                ZeckFile {
+                    maybe_rotation: Some(data.len() as u64),
+                    ..file.clone()
+                },
+                ZeckFormatError::RotationOutOfRange {
+                    rotation: data.len() as u64,
+                    original_size: data.len() as u64,
+                },
+            ),
+            (
+                "corrupted payload",
+                corrupted.clone(),
+                ZeckFormatError::PayloadCrcMismatch {
+                    expected: file.payload_crc(),
+                    actual: corrupted.payload_crc(),
+                },
+            ),
+            (
+                "payload longer than any compression",
+                ZeckFile {
+                    maybe_padding_bits: None,
+                    compressed_data: vec![1; max_length + 1],
+                    ..file.clone()
+                },
+                ZeckFormatError::PayloadTooLarge {
+                    payload_length: max_length + 1,
+                    max_length,
+                },
+            ),
+            (
+                "more than 7 padding bits",
+                ZeckFile {
+                    maybe_padding_bits: Some(8),
+                    ..file.clone()
+                },
+                ZeckFormatError::NonCanonicalPadding {
+                    padding_bits: 8,
+                    compressed_length: file.compressed_data.len(),
+                },
+            ),
+            (
+                "padding bits not matching the payload length",
+                ZeckFile {
+                    compressed_data: [file.compressed_data.as_slice(), &[0]].concat(),
+                    ..file.clone()
+                },
+                ZeckFormatError::NonCanonicalPadding {
+                    padding_bits,
+                    compressed_length: file.compressed_data.len() + 1,
+                },
+            ),
+        ];
+
+        for (name, zeck_file, expected) in cases {
+            // Act
+            let result = zeck_file.validate();
+
+            // Assert
+            assert_eq!(result, Err(expected.clone()), "{name}");
+            assert_eq!(decompress_zeck_file(&zeck_file), Err(expected), "{name}");
+        }
+    }
+
+    #[test]
+    fn padding_bits_round_trip_through_bytes() {
+        // Arrange
+        let zeck_file = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap();
+
+        // Act
+        let deserialized = deserialize_zeck_file(&zeck_file.to_bytes()).unwrap();
+
+        // Assert
+        assert!(zeck_file.maybe_padding_bits.is_some());
+        assert_eq!(deserialized, zeck_file);
+    }
+
+    #[test]
+    fn file_without_padding_bits_flag_still_decompresses() {
+        // Arrange
+        let zeck_file = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap();
+        let mut bytes = vec![ZECK_FORMAT_VERSION];
+        bytes.extend(zeck_file.original_size.to_le_bytes());
+        bytes.push(zeck_file.flags & !ZECK_FLAG_PADDING_BITS);
+        bytes.extend((zeck_file.compressed_data.len() as u64).to_le_bytes());
+        bytes.extend(&zeck_file.compressed_data);
+
+        // Act
+        let deserialized = deserialize_zeck_file(&bytes).unwrap();
+
+        // Assert
+        assert_eq!(deserialized.maybe_padding_bits, None);
+        assert_eq!(decompress_zeck_file(&deserialized), Ok(vec![3, 1, 4, 1, 5]));
+    }
+
+    #[test]
+    fn missing_padding_bits_byte_is_header_too_short() {
+        // Arrange
+        let bytes = [
+            ZECK_FORMAT_VERSION,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            ZECK_FLAG_PADDING_BITS,
+        ];
+
+        // Act
+        let result = deserialize_zeck_file(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::HeaderTooShort {
+                actual_length: 10,
+                required_length: 11,
+            })
+        );
+    }
+
+    #[test]
+    fn archive_block_longer_than_stream_is_truncated() {
+        // Arrange
+        let mut bytes = vec![ZECK_ARCHIVE_FORMAT_VERSION];
+        bytes.extend(1u64.to_le_bytes());
+        bytes.push(0);
+        bytes.extend(1u32.to_le_bytes());
+        // A single block claiming 5 compressed bytes, followed by only 2
+        bytes.extend(0u64.to_le_bytes());
+        bytes.extend(5u64.to_le_bytes());
+        bytes.extend(1u64.to_le_bytes());
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend([1, 2]);
+
+        // Act
+        let result = ZeckFile::read_from(&mut bytes.as_slice());
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::Truncated {
+                expected: 5,
+                actual: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn concatenated_best_compressions_read_back_one_at_a_time() {
+        // Arrange
+        let first_data = b"first file, first file, first file".to_vec();
+        let second_data = vec![0, 0, 200, 100, 50, 25];
+        let best_file = |data: &[u8]| match compress_zeck_best(data).unwrap() {
+            BestCompressionResult::BigEndianBest { zeck_file, .. }
+            | BestCompressionResult::LittleEndianBest { zeck_file, .. } => zeck_file,
+            BestCompressionResult::Neither { .. } => compress_zeck_le(data).unwrap(),
+        };
+        let first = best_file(&first_data);
+        let second = best_file(&second_data);
+        let mut bytes = first.to_bytes();
+        bytes.extend(second.to_bytes());
+        let mut reader = bytes.as_slice();
+
+        // Act
+        let read_first = ZeckFile::read_from(&mut reader).unwrap().unwrap();
+        let read_second = ZeckFile::read_from(&mut reader).unwrap().unwrap();
+        let read_end = ZeckFile::read_from(&mut reader).unwrap();
+
+        // Assert
+        assert_eq!(read_first, first);
+        assert_eq!(read_second, second);
+        assert_eq!(read_end, None);
+        assert_eq!(decompress_zeck_file(&read_first), Ok(first_data));
+        assert_eq!(decompress_zeck_file(&read_second), Ok(second_data));
+    }
+
+    #[test]
+    fn single_file_shorter_than_its_payload_length_is_truncated() {
+        // Arrange
+        let zeck_file = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap();
+        let bytes = zeck_file.to_bytes();
+        let cut = &bytes[..bytes.len() - 1];
+        let payload_length = zeck_file.compressed_data.len();
+
+        // Act
+        let read = ZeckFile::read_from(&mut &cut[..]);
+        let deserialized = deserialize_zeck_file(cut);
+
+        // Assert
+        let expected = ZeckFormatError::Truncated {
+            expected: payload_length,
+            actual: payload_length - 1,
+        };
+        assert_eq!(read, Err(expected.clone()));
+        assert_eq!(deserialized, Err(expected));
+    }
+
+    #[test]
+    fn bytes_after_a_single_file_are_rejected() {
+        // Arrange
+        let mut bytes = compress_zeck_le(&[3, 1, 4, 1, 5]).unwrap().to_bytes();
+        bytes.extend([0, 0]);
+
+        // Act
+        let result = deserialize_zeck_file(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::TrailingData { trailing_length: 2 })
+        );
+    }
+
+    #[test]
+    fn payload_too_large_for_original_size_is_rejected() {
+        // Arrange
+        let mut bytes = vec![ZECK_FORMAT_VERSION];
+        bytes.extend(1u64.to_le_bytes());
+        bytes.push(0);
+        bytes.extend(1_000u64.to_le_bytes());
+        bytes.extend([0xFF; 1_000]);
+
+        // Act
+        let result = deserialize_zeck_file(&bytes);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ZeckFormatError::PayloadTooLarge {
+                payload_length: 1_000,
+                max_length: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn sparse_index_list_longer_than_the_ezba_limit_round_trips() {
+        // Arrange
+        // 232 = 1 + 3 + 8 + 21 + 55 + 144 uses every other EFI up to 10, so its sparse index list takes one byte per term
+        let ezld = vec![10, 8, 6, 4, 2, 0];
+        let max_length = max_compressed_len_for_original_size(1, 0, 0);
+        let mut zeck_file = ZeckFile::new_with_flags(1, encode_sparse_index_list(&ezld), 0);
+        zeck_file.update_flags(|flags| flags.has_padding_bits = false);
+        zeck_file.maybe_padding_bits = None;
+        zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_SPARSE);
+        assert_eq!(zeck_file.compressed_data.len(), max_length + 1);
+
+        // Act
+        let result = deserialize_zeck_file(&zeck_file.to_bytes());
+
+        // Assert
+        assert_eq!(result.as_ref(), Ok(&zeck_file));
+        assert_eq!(zeck_file.validate(), Ok(()));
+        assert_eq!(decompress_zeck_file(&zeck_file), Ok(vec![232]));
+    }
+
+    #[test]
+    fn sparse_index_list_too_large_for_original_size_is_rejected() {
+        // Arrange
+        let max_length = max_compressed_len_for_original_size(1, 0, ZECK_EXTENDED_FLAG_SPARSE);
+        let mut zeck_file = ZeckFile::new_with_flags(1, vec![0x80; 1_000], 0);
+        zeck_file.update_flags(|flags| flags.has_padding_bits = false);
+        zeck_file.maybe_padding_bits = None;
+        zeck_file.insert_extended_flags(ZECK_EXTENDED_FLAG_SPARSE);
+
+        // Act
+        let deserialized = deserialize_zeck_file(&zeck_file.to_bytes());
+        let validated = zeck_file.validate();
+
+        // Assert
+        let expected = ZeckFormatError::PayloadTooLarge {
+            payload_length: 1_000,
+            max_length,
+        };
+        assert_eq!(deserialized, Err(expected.clone()));
+        assert_eq!(validated, Err(expected));
+    }
+
+    #[test]
+    fn payloads_of_every_encoding_are_within_the_size_limit() {
+        for length in [0, 1, 2, 7, 64, 300] {
+            for byte in [0x55, 0xAA, 0xFF] {
+                for flags in [
+                    0,
+                    ZECK_FLAG_BIG_ENDIAN,
+                    ZECK_FLAG_LAZY,
+                    ZECK_FLAG_ENTROPY_CODED,
+                    ZECK_FLAG_LAZY | ZECK_FLAG_ENTROPY_CODED | ZECK_FLAG_BIG_ENDIAN,
+                ] {
+                    // Arrange
+                    let data = vec![byte; length];
+                    let zeck_file = ZeckFile::new_with_flags(
+                        length as u64,
+                        compress_with_flags(&data, flags),
+                        flags,
+                    );
+
+                    // Act
+                    let deserialized = deserialize_zeck_file(&zeck_file.to_bytes());
+
+                    // Assert
+                    assert_eq!(
+                        deserialized,
+                        Ok(zeck_file),
+                        "length: {length}, flags: {flags}"
+                    );
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,206 @@
+//! Typed view of the .zeck header flags byte
+
+use crate::Endianness;
+use crate::zeck_file_format::{
+    ZECK_FLAG_BIG_ENDIAN, ZECK_FLAG_CHECKSUM, ZECK_FLAG_ENTROPY_CODED, ZECK_FLAG_EXTENDED,
+    ZECK_FLAG_FILENAME, ZECK_FLAG_INVERTED, ZECK_FLAG_LAZY, ZECK_FLAG_PADDING_BITS,
+};
+
+/// Every flag of the flags byte, in bit order.
+const ZECK_FLAGS: [u8; 8] = [
+    ZECK_FLAG_BIG_ENDIAN,
+    ZECK_FLAG_LAZY,
+    ZECK_FLAG_INVERTED,
+    ZECK_FLAG_PADDING_BITS,
+    ZECK_FLAG_CHECKSUM,
+    ZECK_FLAG_FILENAME,
+    ZECK_FLAG_ENTROPY_CODED,
+    ZECK_FLAG_EXTENDED,
+];
+
+// Each flag is its own bit, so every byte converts to a distinct ZeckFlags and back
+const _: () = {
+    let mut index = 0;
+    while index < ZECK_FLAGS.len() {
+        assert!(
+            ZECK_FLAGS[index] == 1 << index,
+            "Each flag must be the bit matching its position in ZECK_FLAGS"
+        );
+        index += 1;
+    }
+};
+
+/// The flags byte of a .zeck header (see [`crate::zeck_file_format::ZeckFile::flags`]) as named fields.
+///
+/// Every bit of the flags byte is assigned, so every byte converts with [`ZeckFlags::from_byte`], and
+/// [`ZeckFlags::to_byte`] gives the same byte back.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::Endianness;
+/// # use zeck::zeck_file_format::{compress::compress_zeck_be, flags::ZeckFlags};
+/// let zeck_file = compress_zeck_be(&[1, 2, 3]).unwrap();
+/// let flags = ZeckFlags::from_byte(zeck_file.flags);
+/// assert_eq!(flags.endianness, Endianness::Big);
+/// assert!(!flags.is_lazy);
+/// assert!(flags.has_padding_bits);
+/// assert_eq!(flags.to_byte(), zeck_file.flags);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZeckFlags {
+    /// The byte order the data was interpreted with, [`ZECK_FLAG_BIG_ENDIAN`]
+    pub endianness: Endianness,
+    /// Whether the data was encoded as a lazy EZBA, [`ZECK_FLAG_LAZY`]
+    pub is_lazy: bool,
+    /// Whether the input bytes were bitwise inverted before compression, [`ZECK_FLAG_INVERTED`]
+    pub is_inverted: bool,
+    /// Whether a padding bits byte follows the header, [`ZECK_FLAG_PADDING_BITS`]
+    pub has_padding_bits: bool,
+    /// Whether a CRC-32 of the original data follows the header, [`ZECK_FLAG_CHECKSUM`]
+    pub has_checksum: bool,
+    /// Whether the original filename follows the header, [`ZECK_FLAG_FILENAME`]
+    pub has_filename: bool,
+    /// Whether the EZBA bits were entropy coded in a second stage, [`ZECK_FLAG_ENTROPY_CODED`]
+    pub is_entropy_coded: bool,
+    /// Whether an extended flags byte follows the header, [`ZECK_FLAG_EXTENDED`]
+    pub has_extended_flags: bool,
+}
+
+impl ZeckFlags {
+    /// Flags with every bit clear: little endian, greedy representation, normal polarity, no second stage, and no
+    /// optional header fields.
+    pub const NONE: ZeckFlags = ZeckFlags::from_byte(0);
+
+    /// Parses a flags byte.
+    pub const fn from_byte(flags: u8) -> Self {
+        Self {
+            endianness: if flags & ZECK_FLAG_BIG_ENDIAN != 0 {
+                Endianness::Big
+            } else {
+                Endianness::Little
+            },
+            is_lazy: flags & ZECK_FLAG_LAZY != 0,
+            is_inverted: flags & ZECK_FLAG_INVERTED != 0,
+            has_padding_bits: flags & ZECK_FLAG_PADDING_BITS != 0,
+            has_checksum: flags & ZECK_FLAG_CHECKSUM != 0,
+            has_filename: flags & ZECK_FLAG_FILENAME != 0,
+            is_entropy_coded: flags & ZECK_FLAG_ENTROPY_CODED != 0,
+            has_extended_flags: flags & ZECK_FLAG_EXTENDED != 0,
+        }
+    }
+
+    /// Returns the flags byte.
+    pub const fn to_byte(self) -> u8 {
+        let bits = [
+            matches!(self.endianness, Endianness::Big),
+            self.is_lazy,
+            self.is_inverted,
+            self.has_padding_bits,
+            self.has_checksum,
+            self.has_filename,
+            self.is_entropy_coded,
+            self.has_extended_flags,
+        ];
+        let mut flags = 0;
+        let mut index = 0;
+        while index < bits.len() {
+            if bits[index] {
+                flags |= ZECK_FLAGS[index];
+            }
+            index += 1;
+        }
+        flags
+    }
+
+    /// Returns whether the data was interpreted as a big endian integer.
+    pub const fn is_big_endian(self) -> bool {
+        matches!(self.endianness, Endianness::Big)
+    }
+
+    /// Returns only the flags that change how the compressed data decodes (endianness, lazy representation, inverted
+    /// polarity, and entropy coding), with the flags that record optional header fields cleared.
+    pub const fn representation(self) -> Self {
+        Self {
+            has_padding_bits: false,
+            has_checksum: false,
+            has_filename: false,
+            has_extended_flags: false,
+            ..self
+        }
+    }
+}
+
+impl Default for ZeckFlags {
+    /// Returns [`ZeckFlags::NONE`].
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl From<u8> for ZeckFlags {
+    fn from(flags: u8) -> Self {
+        Self::from_byte(flags)
+    }
+}
+
+impl From<ZeckFlags> for u8 {
+    fn from(flags: ZeckFlags) -> Self {
+        flags.to_byte()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_flags_byte_round_trips() {
+        for byte in 0..=u8::MAX {
+            // Act
+            let flags = ZeckFlags::from_byte(byte);
+
+            // Assert
+            assert_eq!(flags.to_byte(), byte, "byte: {byte:#010b}");
+            assert_eq!(u8::from(ZeckFlags::from(byte)), byte, "byte: {byte:#010b}");
+        }
+    }
+
+    #[test]
+    fn each_field_maps_to_its_own_bit() {
+        for (index, flag) in ZECK_FLAGS.into_iter().enumerate() {
+            // Act
+            let flags = ZeckFlags::from_byte(flag);
+
+            // Assert
+            let set_fields = [
+                flags.is_big_endian(),
+                flags.is_lazy,
+                flags.is_inverted,
+                flags.has_padding_bits,
+                flags.has_checksum,
+                flags.has_filename,
+                flags.is_entropy_coded,
+                flags.has_extended_flags,
+            ];
+            for (field_index, is_set) in set_fields.into_iter().enumerate() {
+                assert_eq!(is_set, field_index == index, "flag: {flag:#010b}");
+            }
+        }
+    }
+
+    #[test]
+    fn representation_keeps_only_decoding_flags() {
+        // Arrange
+        let flags = ZeckFlags::from_byte(u8::MAX);
+
+        // Act
+        let representation = flags.representation();
+
+        // Assert
+        assert_eq!(
+            representation.to_byte(),
+            ZECK_FLAG_BIG_ENDIAN | ZECK_FLAG_LAZY | ZECK_FLAG_INVERTED | ZECK_FLAG_ENTROPY_CODED
+        );
+    }
+}
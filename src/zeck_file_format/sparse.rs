@@ -0,0 +1,136 @@
+//! Sparse index list encoding of the Zeckendorf representation
+//!
+//! A number with only a few Fibonacci terms but a large top index, such as F(1000000), has an EZBA that is almost all
+//! [`crate::SKIP_BIT`]s, one per Fibonacci index below the top one. The sparse index list stores the EZLD (Effective
+//! Zeckendorf List Descending) instead, so its size grows with the number of terms rather than with the top index.
+//!
+//! The encoded bytes are a sequence of [LEB128](https://en.wikipedia.org/wiki/LEB128) varints:
+//! - the largest effective Fibonacci index
+//! - for each following index, its difference from the previous, larger index
+//!
+//! The Zeckendorf representation never uses two consecutive Fibonacci numbers, so every difference is at least 2.
+//! Zero has an empty Zeckendorf representation and encodes to no bytes.
+
+/// Encodes an EZLD (Effective Zeckendorf List Descending) as a sparse index list.
+///
+/// Use [`decode_sparse_index_list`] to restore the list.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::sparse::{decode_sparse_index_list, encode_sparse_index_list};
+/// let ezld = vec![1_000, 10, 0];
+/// let encoded = encode_sparse_index_list(&ezld);
+/// // 1000, then the differences 990 and 10
+/// assert_eq!(encoded, vec![0xE8, 0x07, 0xDE, 0x07, 10]);
+/// assert_eq!(decode_sparse_index_list(&encoded, 1_000), Some(ezld));
+/// ```
+pub fn encode_sparse_index_list(effective_zeckendorf_list_descending: &[u64]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    let mut maybe_previous_efi = None;
+    for &efi in effective_zeckendorf_list_descending {
+        let value = match maybe_previous_efi {
+            Some(previous_efi) => previous_efi - efi,
+            None => efi,
+        };
+        write_varint(&mut encoded, value);
+        maybe_previous_efi = Some(efi);
+    }
+    encoded
+}
+
+/// Decodes a sparse index list back to an EZLD (Effective Zeckendorf List Descending).
+///
+/// Returns [`None`] if the bytes are not a sparse index list as [`encode_sparse_index_list`] writes them: a varint is
+/// truncated, overflows a [`u64`], or uses more bytes than it needs, two indices are less than 2 apart, an index
+/// would be negative, or the largest index is above `max_efi`.
+pub fn decode_sparse_index_list(encoded: &[u8], max_efi: u64) -> Option<Vec<u64>> {
+    let mut effective_zeckendorf_list_descending: Vec<u64> = Vec::new();
+    let mut remaining = encoded;
+    while !remaining.is_empty() {
+        let value = read_varint(&mut remaining)?;
+        let efi = match effective_zeckendorf_list_descending.last() {
+            Some(&previous_efi) if value >= 2 => previous_efi.checked_sub(value)?,
+            Some(_) => return None,
+            None if value <= max_efi => value,
+            None => return None,
+        };
+        effective_zeckendorf_list_descending.push(efi);
+    }
+    Some(effective_zeckendorf_list_descending)
+}
+
+/// Appends `value` as an unsigned LEB128 varint.
+fn write_varint(encoded: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        encoded.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    encoded.push(value as u8);
+}
+
+/// Reads an unsigned LEB128 varint from the front of `remaining`, rejecting encodings longer than necessary.
+fn read_varint(remaining: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for (index, &byte) in remaining.iter().enumerate() {
+        let shift = 7 * index as u32;
+        let bits = u64::from(byte & 0x7F);
+        if shift >= u64::BITS || (bits << shift) >> shift != bits {
+            return None;
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            // A final zero byte after other bytes adds nothing, so the value has a shorter encoding
+            if byte == 0 && index > 0 {
+                return None;
+            }
+            *remaining = &remaining[index + 1..];
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse_index_list_round_trips() {
+        for ezld in [
+            vec![],
+            vec![0],
+            vec![127],
+            vec![128],
+            vec![u64::MAX, 2, 0],
+            vec![1_000_000, 999_998, 500, 3, 1],
+        ] {
+            // Arrange
+            let encoded = encode_sparse_index_list(&ezld);
+
+            // Act
+            let decoded = decode_sparse_index_list(&encoded, u64::MAX);
+
+            // Assert
+            assert_eq!(decoded, Some(ezld));
+        }
+    }
+
+    #[test]
+    fn decode_sparse_index_list_rejects_malformed_lists() {
+        for (encoded, max_efi) in [
+            (vec![0x80], 100),          // Truncated varint
+            (vec![0x85, 0x00], 100),    // Longer than necessary
+            (vec![0xFF; 10], u64::MAX), // Overflows a u64
+            (vec![10, 1], 100),         // Consecutive indices
+            (vec![10, 11], 100),        // Negative index
+            (vec![101], 100),           // Above the largest index
+        ] {
+            // Act
+            let decoded = decode_sparse_index_list(&encoded, max_efi);
+
+            // Assert
+            assert_eq!(decoded, None, "encoded: {encoded:?}");
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Zero-copy view of a serialized .zeck file
+
+use crate::zeck_file_format::{
+    ZECK_CHECKSUM_SIZE, ZECK_ELEMENT_WIDTH_SIZE, ZECK_EXTENDED_FLAG_ELEMENT_WIDTH,
+    ZECK_EXTENDED_FLAG_MTIME, ZECK_EXTENDED_FLAG_PAYLOAD_CRC, ZECK_EXTENDED_FLAG_ROTATION,
+    ZECK_EXTENDED_FLAGS_SIZE, ZECK_FILENAME_LENGTH_SIZE, ZECK_FORMAT_VERSION, ZECK_HEADER_SIZE,
+    ZECK_MTIME_SIZE, ZECK_PADDING_BITS_SIZE, ZECK_PAYLOAD_CRC_SIZE, ZECK_PAYLOAD_LENGTH_SIZE,
+    ZECK_ROTATION_SIZE, decompress::validate_view, error::ZeckFormatError, file::ZeckFile,
+    flags::ZeckFlags,
+};
+
+/// A parsed .zeck file that borrows its compressed data and filename from the serialized bytes instead of copying them.
+///
+/// The fields mean the same as the fields of [`ZeckFile`]. Parsing a view only reads the header and optional header
+/// fields, so it is cheap even for a large memory-mapped file, and
+/// [`crate::zeck_file_format::decompress::decompress_view`] decompresses straight from the borrowed bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use zeck::zeck_file_format::{compress::compress_zeck_be, decompress::decompress_view, view::ZeckFileView};
+/// let original = vec![0, 1, 2, 3];
+/// let bytes = compress_zeck_be(&original).unwrap().to_bytes();
+///
+/// let view = ZeckFileView::parse(&bytes).unwrap();
+/// assert_eq!(view.original_size, 4);
+/// assert_eq!(decompress_view(&view).unwrap(), original);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZeckFileView<'a> {
+    /// File format version
+    pub version: u8,
+    /// Original uncompressed file size in bytes
+    pub original_size: u64,
+    /// Flags byte, see [`ZeckFile::flags`]
+    pub flags: u8,
+    /// Extended flags byte, see [`ZeckFile::extended_flags`]
+    pub extended_flags: u8,
+    /// Number of padding bits (0-7) in the last byte of the compressed data, if recorded.
+    pub maybe_padding_bits: Option<u8>,
+    /// CRC-32 of the original uncompressed data, if recorded.
+    pub maybe_checksum: Option<u32>,
+    /// Name of the original uncompressed file, if recorded.
+    pub maybe_filename: Option<&'a str>,
+    /// Number of bytes the input was rotated left by before compression, if it was rotated.
+    pub maybe_rotation: Option<u64>,
+    /// CRC-32 of the compressed data, if recorded.
+    pub maybe_payload_crc: Option<u32>,
+    /// Modification time of the original file in seconds since the Unix epoch, if recorded.
+    pub maybe_mtime: Option<u64>,
+    /// Width in bytes of the elements the original data is a sequence of, if recorded.
+    pub maybe_element_width: Option<u8>,
+    /// Compressed data (without header)
+    pub compressed_data: &'a [u8],
+}
+
+impl<'a> ZeckFileView<'a> {
+    /// Parses the header and optional header fields of raw .zeck file bytes, borrowing the compressed data that
+    /// follows them.
+    ///
+    /// Only version 2 files have optional header fields and a payload length. Their compressed data must be exactly
+    /// the recorded length: fewer bytes fail with [`ZeckFormatError::Truncated`], and more with
+    /// [`ZeckFormatError::TrailingData`]. For any other version, everything after the header is the compressed data,
+    /// and the version is checked when decompressing.
+    pub fn parse(zeck_file_data: &'a [u8]) -> Result<Self, ZeckFormatError> {
+        let (view, length) = Self::parse_prefix(zeck_file_data)?;
+        if length < zeck_file_data.len() {
+            return Err(ZeckFormatError::TrailingData {
+                trailing_length: zeck_file_data.len() - length,
+            });
+        }
+        Ok(view)
+    }
+
+    /// Parses one .zeck file from the start of `zeck_file_data`, returning it and its length in bytes.
+    ///
+    /// A version 2 file ends where its recorded payload length says, so it may be followed by more data. Files of any
+    /// other version extend to the end of the data.
+    pub(crate) fn parse_prefix(zeck_file_data: &'a [u8]) -> Result<(Self, usize), ZeckFormatError> {
+        let mut cursor = HeaderCursor {
+            bytes: zeck_file_data,
+            position: 0,
+        };
+
+        // Parse header
+        let [version] = cursor.take_array()?;
+        let original_size = u64::from_le_bytes(cursor.take_array()?);
+        let [flags] = cursor.take_array()?;
+
+        let mut view = Self {
+            version,
+            original_size,
+            flags,
+            extended_flags: 0,
+            maybe_padding_bits: None,
+            maybe_checksum: None,
+            maybe_filename: None,
+            maybe_rotation: None,
+            maybe_payload_crc: None,
+            maybe_mtime: None,
+            maybe_element_width: None,
+            compressed_data: &[],
+        };
+
+        // Version 2 files may record optional fields following the header, in the order they are stored
+        if version == ZECK_FORMAT_VERSION {
+            let flags = ZeckFlags::from_byte(flags);
+            if flags.has_extended_flags {
+                let [extended_flags] = cursor.take_array::<ZECK_EXTENDED_FLAGS_SIZE>()?;
+                view.extended_flags = extended_flags;
+            }
+
+            if flags.has_padding_bits {
+                let [padding_bits] = cursor.take_array::<ZECK_PADDING_BITS_SIZE>()?;
+                view.maybe_padding_bits = Some(padding_bits);
+            }
+
+            if flags.has_checksum {
+                let checksum: [u8; ZECK_CHECKSUM_SIZE] = cursor.take_array()?;
+                view.maybe_checksum = Some(u32::from_le_bytes(checksum));
+            }
+
+            if flags.has_filename {
+                let filename_length: [u8; ZECK_FILENAME_LENGTH_SIZE] = cursor.take_array()?;
+                let filename = cursor.take(u32::from_le_bytes(filename_length) as usize)?;
+                let filename =
+                    std::str::from_utf8(filename).map_err(|_| ZeckFormatError::InvalidFilename)?;
+                view.maybe_filename = Some(filename);
+            }
+
+            if view.extended_flags & ZECK_EXTENDED_FLAG_ROTATION != 0 {
+                let rotation: [u8; ZECK_ROTATION_SIZE] = cursor.take_array()?;
+                view.maybe_rotation = Some(u64::from_le_bytes(rotation));
+            }
+
+            if view.extended_flags & ZECK_EXTENDED_FLAG_PAYLOAD_CRC != 0 {
+                let payload_crc: [u8; ZECK_PAYLOAD_CRC_SIZE] = cursor.take_array()?;
+                view.maybe_payload_crc = Some(u32::from_le_bytes(payload_crc));
+            }
+
+            if view.extended_flags & ZECK_EXTENDED_FLAG_MTIME != 0 {
+                let mtime: [u8; ZECK_MTIME_SIZE] = cursor.take_array()?;
+                view.maybe_mtime = Some(u64::from_le_bytes(mtime));
+            }
+
+            if view.extended_flags & ZECK_EXTENDED_FLAG_ELEMENT_WIDTH != 0 {
+                let [element_width] = cursor.take_array::<ZECK_ELEMENT_WIDTH_SIZE>()?;
+                view.maybe_element_width = Some(element_width);
+            }
+
+            // The compressed data is exactly the recorded payload length
+            let payload_length: [u8; ZECK_PAYLOAD_LENGTH_SIZE] = cursor.take_array()?;
+            let payload_length = u64::from_le_bytes(payload_length);
+            let remaining = zeck_file_data.len() - cursor.position;
+            let payload_length = usize::try_from(payload_length)
+                .ok()
+                .filter(|&payload_length| payload_length <= remaining)
+                .ok_or(ZeckFormatError::Truncated {
+                    expected: usize::try_from(payload_length).unwrap_or(usize::MAX),
+                    actual: remaining,
+                })?;
+            view.compressed_data = cursor.take(payload_length)?;
+            return Ok((view, cursor.position));
+        }
+
+        // Everything after the header is the compressed data of any other version
+        view.compressed_data = &zeck_file_data[cursor.position..];
+        Ok((view, zeck_file_data.len()))
+    }
+
+    /// Checks the header and the layout of the compressed data without decompressing anything, see
+    /// [`ZeckFile::validate`].
+    pub fn validate(&self) -> Result<(), ZeckFormatError> {
+        validate_view(self)
+    }
+
+    /// Copies the view into an owned [`ZeckFile`].
+    pub fn to_zeck_file(&self) -> ZeckFile {
+        ZeckFile {
+            version: self.version,
+            original_size: self.original_size,
+            flags: self.flags,
+            extended_flags: self.extended_flags,
+            maybe_padding_bits: self.maybe_padding_bits,
+            maybe_checksum: self.maybe_checksum,
+            maybe_filename: self.maybe_filename.map(str::to_string),
+            maybe_rotation: self.maybe_rotation,
+            maybe_payload_crc: self.maybe_payload_crc,
+            maybe_mtime: self.maybe_mtime,
+            maybe_element_width: self.maybe_element_width,
+            compressed_data: self.compressed_data.to_vec(),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ZeckFileView<'a> {
+    type Error = ZeckFormatError;
+
+    /// Parses a [`ZeckFileView`] from raw .zeck file bytes. Delegates to [`ZeckFileView::parse`].
+    fn try_from(zeck_file_data: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::parse(zeck_file_data)
+    }
+}
+
+/// Reads the header fields of serialized .zeck file bytes in order, reporting the full length needed if they run out.
+struct HeaderCursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> HeaderCursor<'a> {
+    /// Returns the next `length` bytes and advances past them.
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ZeckFormatError> {
+        let required_length = self.position.saturating_add(length);
+        let Some(field) = self.bytes.get(self.position..required_length) else {
+            return Err(ZeckFormatError::HeaderTooShort {
+                actual_length: self.bytes.len(),
+                required_length: required_length.max(ZECK_HEADER_SIZE),
+            });
+        };
+        self.position = required_length;
+        Ok(field)
+    }
+
+    /// Returns the next `N` bytes as an array and advances past them.
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], ZeckFormatError> {
+        let field = self.take(N)?;
+        Ok(field.try_into().expect("The field is exactly N bytes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zeck_file_format::compress::compress_zeck_rotations;
+    use crate::zeck_file_format::decompress::decompress_view;
+
+    #[test]
+    fn view_borrows_compressed_data_and_decompresses() {
+        // Arrange
+        let original = vec![255, 0, 255];
+        let (_, mut zeck_file) = compress_zeck_rotations(&original).unwrap();
+        zeck_file.maybe_filename = Some("view.txt".to_string());
+        let bytes = zeck_file.to_bytes();
+
+        // Act
+        let view = ZeckFileView::parse(&bytes).unwrap();
+        let decompressed = decompress_view(&view).unwrap();
+
+        // Assert
+        let payload_start = bytes.len() - zeck_file.compressed_data.len();
+        assert!(std::ptr::eq(view.compressed_data, &bytes[payload_start..]));
+        assert_eq!(view.maybe_filename, Some("view.txt"));
+        assert_eq!(view.maybe_rotation, Some(1));
+        let deserialized = view.to_zeck_file();
+        assert_eq!(deserialized.as_view(), view);
+        assert_eq!(decompressed, original);
+    }
+}
@@ -0,0 +1,90 @@
+//! Lives in its own integration test binary so no other test can warm the global caches between clearing them and
+//! checking their lengths. The tests in this binary still run in parallel with each other, so each one holds
+//! [`CACHE_LOCK`] while it touches the caches.
+
+use num_bigint::BigUint;
+use std::sync::{Mutex, MutexGuard};
+use zeck::{
+    FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE, clear_all_caches, load_fibonacci_cache,
+    memoized_fast_doubling_fibonacci_biguint, memoized_slow_fibonacci_biguint_iterative,
+    memoized_zeckendorf_list_descending_for_biguint, padless_zeckendorf_compress_be_dangerous,
+    save_fibonacci_cache,
+};
+
+/// Serializes the tests that clear the global caches.
+static CACHE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Locks [`CACHE_LOCK`], ignoring poisoning from a failed test so the other tests still run.
+fn lock_caches() -> MutexGuard<'static, ()> {
+    CACHE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[test]
+fn clear_all_caches_resets_caches_to_baseline() {
+    // Arrange
+    let _guard = lock_caches();
+    memoized_fast_doubling_fibonacci_biguint(1_000);
+    memoized_slow_fibonacci_biguint_iterative(1_000);
+    memoized_zeckendorf_list_descending_for_biguint(&BigUint::from(123_456_789u64));
+    padless_zeckendorf_compress_be_dangerous(&[0xAB; 64]);
+
+    // Act
+    clear_all_caches();
+
+    // Assert
+    let fast_doubling_cache = FAST_DOUBLING_FIBONACCI_BIGUINT_CACHE
+        .read()
+        .expect("Failed to read fast doubling Fibonacci cache");
+    let mut cached_indices: Vec<u64> = fast_doubling_cache.keys().copied().collect();
+    cached_indices.sort_unstable();
+    assert_eq!(cached_indices, vec![0, 1]);
+}
+
+#[test]
+fn clear_all_caches_preserves_results() {
+    // Arrange
+    let _guard = lock_caches();
+    let expected = memoized_fast_doubling_fibonacci_biguint(300);
+
+    // Act
+    clear_all_caches();
+
+    // Assert
+    assert_eq!(memoized_fast_doubling_fibonacci_biguint(300), expected);
+    assert_eq!(memoized_slow_fibonacci_biguint_iterative(300), expected);
+}
+
+#[test]
+fn fibonacci_cache_round_trips_through_disk() {
+    // Arrange
+    let _guard = lock_caches();
+    let directory = std::env::temp_dir();
+    let path = directory.join(format!(
+        "zeck_fibonacci_cache_round_trip_{}.bin",
+        std::process::id()
+    ));
+    let reloaded_path = directory.join(format!(
+        "zeck_fibonacci_cache_round_trip_reloaded_{}.bin",
+        std::process::id()
+    ));
+    let expected = memoized_slow_fibonacci_biguint_iterative(500);
+    save_fibonacci_cache(&path).unwrap();
+    // Simulate a fresh process by resetting the caches to their initial state
+    clear_all_caches();
+
+    // Act
+    let result = load_fibonacci_cache(&path);
+
+    // Assert
+    result.unwrap();
+    // Saving again before anything warms the cache writes exactly what was loaded
+    save_fibonacci_cache(&reloaded_path).unwrap();
+    let saved = std::fs::read(&path).unwrap();
+    let reloaded = std::fs::read(&reloaded_path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&reloaded_path).unwrap();
+    assert_eq!(reloaded, saved);
+    assert_eq!(memoized_slow_fibonacci_biguint_iterative(500), expected);
+}
@@ -0,0 +1,84 @@
+//! Golden file regression tests for the .zeck output.
+//!
+//! Every `<name>.bin` file in `tests/corpus/` is an input, and `<name>.zeck` next to it is the output
+//! [`compress_to_zeck_bytes`] is expected to produce for it. Any change to the compression algorithm or file format that
+//! alters the output fails here. If the change is intended, regenerate the golden files with
+//! `ZECK_UPDATE_CORPUS=1 cargo test --test corpus` and commit them.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use zeck::{compress_to_zeck_bytes, decompress_from_zeck_bytes};
+
+/// Set this environment variable to rewrite the golden files instead of comparing against them.
+const UPDATE_CORPUS_VAR: &str = "ZECK_UPDATE_CORPUS";
+
+/// Returns the paths of the corpus files with the given extension, sorted by name.
+fn corpus_files(extension: &str) -> Vec<PathBuf> {
+    let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&corpus_dir)
+        .expect("Failed to read the corpus directory")
+        .map(|entry| entry.expect("Failed to read a corpus entry").path())
+        .filter(|path| path.extension().is_some_and(|found| found == extension))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "The corpus has no .{extension} files");
+    paths
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().unwrap().to_string_lossy().into_owned()
+}
+
+#[test]
+fn corpus_inputs_compress_to_their_golden_files() {
+    let is_updating = env::var_os(UPDATE_CORPUS_VAR).is_some();
+    for input_path in corpus_files("bin") {
+        // Arrange
+        let input = fs::read(&input_path).unwrap();
+        let golden_path = input_path.with_extension("zeck");
+
+        // Act
+        let compressed = compress_to_zeck_bytes(&input).unwrap();
+
+        // Assert
+        if is_updating {
+            fs::write(&golden_path, &compressed).unwrap();
+            continue;
+        }
+        let golden = fs::read(&golden_path).unwrap_or_else(|error| {
+            panic!(
+                "Failed to read {}: {error}; run with {UPDATE_CORPUS_VAR}=1 to create it",
+                file_name(&golden_path)
+            )
+        });
+        assert_eq!(
+            compressed,
+            golden,
+            "The compressed output of {} changed; if intended, run with {UPDATE_CORPUS_VAR}=1",
+            file_name(&input_path)
+        );
+    }
+}
+
+#[test]
+fn corpus_golden_files_decompress_to_their_inputs() {
+    for golden_path in corpus_files("zeck") {
+        // Arrange
+        let golden = fs::read(&golden_path).unwrap();
+        let input_path = golden_path.with_extension("bin");
+        let input = fs::read(&input_path).unwrap_or_else(|error| {
+            panic!(
+                "Failed to read {}, the input for {}: {error}",
+                file_name(&input_path),
+                file_name(&golden_path)
+            )
+        });
+
+        // Act
+        let decompressed = decompress_from_zeck_bytes(&golden);
+
+        // Assert
+        assert_eq!(decompressed, Ok(input), "{}", file_name(&golden_path));
+    }
+}
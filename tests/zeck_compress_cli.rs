@@ -0,0 +1,256 @@
+//! Runs the `zeck-compress` binary end to end, so it only builds with the `cli_tools` feature.
+
+#![cfg(feature = "cli_tools")]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Returns a fresh temporary directory for one test.
+fn temp_dir(test_name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "zeck_compress_cli_{}_{}",
+        test_name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Failed to create temporary directory");
+    dir
+}
+
+#[test]
+fn emits_compressed_value_as_decimal_and_hex() {
+    for (emit, expected) in [("decimal", "53724349824\n"), ("hex", "c82388580\n")] {
+        // Arrange
+        let dir = temp_dir(emit);
+        let input_path = dir.join("data.bin");
+        fs::write(&input_path, [0xFF, 0x00, 0x10, 0x20]).unwrap();
+
+        // Act
+        let output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+            .arg(&input_path)
+            .args(["--endian", "big", "--emit", emit])
+            .output()
+            .expect("Failed to run zeck-compress");
+
+        // Assert
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+        assert!(!dir.join("data.bin.zeck").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[test]
+fn compresses_empty_input_to_zero_length_file() {
+    // Arrange
+    let dir = temp_dir("empty");
+    let input_path = dir.join("empty.bin");
+    fs::write(&input_path, []).unwrap();
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .args(["--endian", "big"])
+        .output()
+        .expect("Failed to run zeck-compress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    let zeck_bytes = fs::read(dir.join("empty.bin.zeck")).unwrap();
+    let zeck_file = zeck::deserialize_zeck_file(&zeck_bytes).unwrap();
+    assert_eq!(zeck_file.original_size, 0);
+    assert_eq!(zeck::decompress_zeck_file(&zeck_file), Ok(Vec::new()));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn json_stats_prints_parseable_statistics() {
+    // Arrange
+    let dir = temp_dir("json_stats");
+    let input_path = dir.join("data.bin");
+    let data = vec![0u8; 64];
+    fs::write(&input_path, &data).unwrap();
+    let mtime = 1_700_000_000;
+    fs::File::options()
+        .write(true)
+        .open(&input_path)
+        .unwrap()
+        .set_modified(UNIX_EPOCH + Duration::from_secs(mtime))
+        .unwrap();
+    let mut zeck_file = zeck::compress_zeck(&data, zeck::EndianChoice::Best).unwrap();
+    zeck_file.maybe_mtime = Some(mtime);
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .arg("--json-stats")
+        .output()
+        .expect("Failed to run zeck-compress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["endianness"], zeck_file.endianness().to_string());
+    assert_eq!(stats["original_size"], 64);
+    assert_eq!(stats["compressed_size"], zeck_file.compressed_data.len());
+    assert_eq!(stats["total_size"], zeck_file.total_size());
+    assert_eq!(stats["ratio"], zeck_file.compression_ratio());
+    assert_eq!(
+        stats["be_size"],
+        zeck::compressed_byte_length(&data, zeck::Endianness::Big)
+    );
+    assert_eq!(
+        stats["le_size"],
+        zeck::compressed_byte_length(&data, zeck::Endianness::Little)
+    );
+    assert_eq!(
+        fs::read(dir.join("data.bin.zeck")).unwrap(),
+        zeck_file.to_bytes()
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn json_stats_reports_the_skipped_size_as_null_above_the_best_threshold() {
+    // Arrange
+    let dir = temp_dir("json_stats_predicted");
+    let input_path = dir.join("data.bin");
+    // Trailing zero bytes make little endian the predicted endianness
+    let mut data = vec![0u8; zeck::zeck_file_format::compress::ZECK_BEST_EXHAUSTIVE_THRESHOLD + 1];
+    data[..4].copy_from_slice(&[3, 1, 4, 1]);
+    fs::write(&input_path, &data).unwrap();
+    let zeck_file = zeck::compress_zeck_le(&data).unwrap();
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .arg("--json-stats")
+        .output()
+        .expect("Failed to run zeck-compress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["endianness"], "little");
+    assert_eq!(stats["be_size"], serde_json::Value::Null);
+    assert_eq!(stats["le_size"], zeck_file.compressed_data.len());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Big endian size: not computed"), "{stderr}");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn json_stats_only_reports_the_size_of_a_fixed_endianness() {
+    // Arrange
+    let dir = temp_dir("json_stats_big");
+    let input_path = dir.join("data.bin");
+    let data = vec![0, 0, 9, 8, 7];
+    fs::write(&input_path, &data).unwrap();
+    let zeck_file = zeck::compress_zeck_be(&data).unwrap();
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .args(["--endian", "big", "--json-stats"])
+        .output()
+        .expect("Failed to run zeck-compress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["be_size"], zeck_file.compressed_data.len());
+    assert_eq!(stats["le_size"], serde_json::Value::Null);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn quiet_suppresses_statistics() {
+    // Arrange
+    let dir = temp_dir("quiet");
+    let input_path = dir.join("data.bin");
+    fs::write(&input_path, [1, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+
+    // Act
+    let verbose_output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .output()
+        .expect("Failed to run zeck-compress");
+    let quiet_output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .arg("--quiet")
+        .output()
+        .expect("Failed to run zeck-compress");
+
+    // Assert
+    assert!(verbose_output.status.success(), "{verbose_output:?}");
+    assert!(!verbose_output.stderr.is_empty());
+    assert!(quiet_output.status.success(), "{quiet_output:?}");
+    assert!(quiet_output.stderr.is_empty(), "{quiet_output:?}");
+    assert!(dir.join("data.bin.zeck").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compare_prints_both_sizes_without_writing_output() {
+    // Arrange
+    let dir = temp_dir("compare");
+    let input_path = dir.join("data.bin");
+    let data = vec![7, 0, 0, 0, 0, 0, 0, 0, 0, 0xFF];
+    fs::write(&input_path, &data).unwrap();
+    let preview = zeck::exact_compression_preview(&data);
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .arg("--compare")
+        .output()
+        .expect("Failed to run zeck-compress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let be_size = zeck::compress_zeck_be(&data).unwrap().compressed_data.len();
+    let le_size = zeck::compress_zeck_le(&data).unwrap().compressed_data.len();
+    assert_eq!((preview.be_size, preview.le_size), (be_size, le_size));
+    assert!(
+        stdout.contains(&format!("Big endian size: {be_size} bytes")),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(&format!("Little endian size: {le_size} bytes")),
+        "{stdout}"
+    );
+    assert!(!dir.join("data.bin.zeck").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dry_run_prints_summary_without_writing_output() {
+    // Arrange
+    let dir = temp_dir("dry_run");
+    let input_path = dir.join("data.bin");
+    let data = vec![1, 0, 0, 0, 0, 0, 0, 0];
+    fs::write(&input_path, &data).unwrap();
+    let compressed_size = zeck::compress_zeck_le(&data).unwrap().compressed_data.len();
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-compress"))
+        .arg(&input_path)
+        .args(["--endian", "little", "--dry-run"])
+        .output()
+        .expect("Failed to run zeck-compress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    let expected_output_path = dir.join("data.bin.zeck");
+    let expected = format!(
+        "Output: {}\nEndianness: little endian\nOriginal size: 8 bytes\nCompressed data size: {compressed_size} bytes (ratio {:.4})\nFavorable: yes\n",
+        expected_output_path.display(),
+        compressed_size as f64 / 8.0
+    );
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+    assert!(!expected_output_path.exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,244 @@
+//! Runs the `zeck-decompress` binary end to end, so it only builds with the `cli_tools` feature.
+
+#![cfg(feature = "cli_tools")]
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, UNIX_EPOCH};
+use zeck::zeck_file_format::checksum::crc32;
+use zeck::{
+    Endianness, ZeckFile, compress_zeck_be, compress_zeck_entropy_coded, compress_zeck_le,
+    padless_zeckendorf_compress_be_dangerous, padless_zeckendorf_compress_le_dangerous,
+};
+
+/// Runs `zeck-decompress` with the given arguments, piping `input` to its stdin.
+fn run_with_stdin(args: &[&str], input: &[u8]) -> std::process::Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_zeck-decompress"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run zeck-decompress");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input)
+        .expect("Failed to write to stdin");
+    child
+        .wait_with_output()
+        .expect("Failed to wait for zeck-decompress")
+}
+
+/// Returns a fresh temporary directory for one test.
+fn temp_dir(test_name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "zeck_decompress_cli_{}_{}",
+        test_name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Failed to create temporary directory");
+    dir
+}
+
+#[test]
+fn decompresses_zeck_file_without_endian_flag() {
+    for (name, zeck_file) in [
+        ("little", compress_zeck_le(&[0, 1, 2, 3, 0]).unwrap()),
+        (
+            "big",
+            compress_zeck_entropy_coded(&[0, 1, 2, 3, 0], Endianness::Big).unwrap(),
+        ),
+    ] {
+        // Arrange
+        let dir = temp_dir(name);
+        let input_path = dir.join("data.bin.zeck");
+        fs::write(&input_path, zeck_file.to_bytes()).unwrap();
+
+        // Act
+        let output = Command::new(env!("CARGO_BIN_EXE_zeck-decompress"))
+            .arg(&input_path)
+            .output()
+            .expect("Failed to run zeck-decompress");
+
+        // Assert
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(fs::read(dir.join("data.bin")).unwrap(), vec![0, 1, 2, 3, 0]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[test]
+fn decompresses_raw_legacy_file_with_endian_flag() {
+    // Arrange
+    let dir = temp_dir("legacy");
+    let input_path = dir.join("data.raw");
+    fs::write(
+        &input_path,
+        padless_zeckendorf_compress_be_dangerous(&[1, 2, 3]),
+    )
+    .unwrap();
+    let output_path = dir.join("data.bin");
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-decompress"))
+        .arg(&input_path)
+        .args(["--endian", "big", "-o"])
+        .arg(&output_path)
+        .output()
+        .expect("Failed to run zeck-decompress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(fs::read(&output_path).unwrap(), vec![1, 2, 3]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn auto_detects_endianness_of_piped_raw_input_from_checksum() {
+    for (endianness, compressed) in [
+        (
+            "big",
+            padless_zeckendorf_compress_be_dangerous(b"zeckendorf"),
+        ),
+        (
+            "little",
+            padless_zeckendorf_compress_le_dangerous(b"zeckendorf"),
+        ),
+    ] {
+        // Arrange
+        let checksum = format!("{:08x}", crc32(b"zeckendorf"));
+
+        // Act
+        let output = run_with_stdin(&["--auto", "--checksum", &checksum], &compressed);
+
+        // Assert
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(output.stdout, b"zeckendorf");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(
+            stderr.contains(&format!("Endianness used: {endianness} endian")),
+            "{stderr}"
+        );
+    }
+}
+
+#[test]
+fn auto_without_checksum_asks_for_explicit_endianness() {
+    // Act
+    let output = run_with_stdin(
+        &["--auto"],
+        &padless_zeckendorf_compress_be_dangerous(&[1, 2, 3]),
+    );
+
+    // Assert
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("--endian big or --endian little"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn quiet_suppresses_statistics() {
+    // Arrange
+    let zeck_bytes = compress_zeck_le(&[0, 1, 2, 3, 0]).unwrap().to_bytes();
+
+    // Act
+    let verbose_output = run_with_stdin(&[], &zeck_bytes);
+    let quiet_output = run_with_stdin(&["--quiet"], &zeck_bytes);
+
+    // Assert
+    assert!(verbose_output.status.success(), "{verbose_output:?}");
+    assert!(!verbose_output.stderr.is_empty());
+    assert!(quiet_output.status.success(), "{quiet_output:?}");
+    assert!(quiet_output.stderr.is_empty(), "{quiet_output:?}");
+    assert_eq!(quiet_output.stdout, vec![0, 1, 2, 3, 0]);
+}
+
+#[test]
+fn emits_decompressed_value_as_decimal_in_the_file_endianness() {
+    for (name, zeck_file) in [
+        ("emit_big", compress_zeck_be(&[0x30, 0x39]).unwrap()),
+        ("emit_little", compress_zeck_le(&[0x39, 0x30]).unwrap()),
+    ] {
+        // Arrange
+        let dir = temp_dir(name);
+        let input_path = dir.join("number.zeck");
+        fs::write(&input_path, zeck_file.to_bytes()).unwrap();
+
+        // Act
+        let output = Command::new(env!("CARGO_BIN_EXE_zeck-decompress"))
+            .arg(&input_path)
+            .args(["--emit", "decimal"])
+            .output()
+            .expect("Failed to run zeck-decompress");
+
+        // Assert
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "12345\n");
+        assert!(!dir.join("number").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[test]
+fn max_output_rejects_larger_output_without_writing_it() {
+    // Arrange
+    let dir = temp_dir("max_output");
+    let input_path = dir.join("ones.zeck");
+    let zeck_file = compress_zeck_entropy_coded(&[0xFF; 4096], Endianness::Little).unwrap();
+    fs::write(&input_path, zeck_file.to_bytes()).unwrap();
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-decompress"))
+        .arg(&input_path)
+        .args(["--max-output", "1024"])
+        .output()
+        .expect("Failed to run zeck-decompress");
+
+    // Assert
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("4096 bytes, more than the --max-output limit of 1024 bytes"),
+        "{stderr}"
+    );
+    assert!(!dir.join("ones").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn restores_recorded_mtime_onto_output_file() {
+    // Arrange
+    let dir = temp_dir("mtime");
+    let input_path = dir.join("data.bin.zeck");
+    let compressed = compress_zeck_le(&[0, 1, 2, 3, 0]).unwrap();
+    let zeck_file = ZeckFile::builder()
+        .original_size(compressed.original_size)
+        .compressed_data(compressed.compressed_data)
+        .mtime(Some(1_700_000_000))
+        .build();
+    fs::write(&input_path, zeck_file.to_bytes()).unwrap();
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_zeck-decompress"))
+        .arg(&input_path)
+        .output()
+        .expect("Failed to run zeck-decompress");
+
+    // Assert
+    assert!(output.status.success(), "{output:?}");
+    let output_path = dir.join("data.bin");
+    assert_eq!(fs::read(&output_path).unwrap(), vec![0, 1, 2, 3, 0]);
+    assert_eq!(
+        fs::metadata(&output_path).unwrap().modified().unwrap(),
+        UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}